@@ -1,13 +1,27 @@
 use std::path::PathBuf;
 
+use crate::models::CreationStep;
+
 #[derive(Debug, thiserror::Error)]
 pub enum OrchestratorError {
     #[error("slot '{0}' already exists")]
     SlotAlreadyExists(String),
 
+    #[error("invalid slot name: {0}")]
+    InvalidSlotName(String),
+
     #[error("slot '{0}' not found")]
     SlotNotFound(String),
 
+    #[error("slot creation failed at {step}: {message}")]
+    CreationFailed { step: CreationStep, message: String },
+
+    #[error("slot '{0}' has no failed creation to retry")]
+    NothingToRetry(String),
+
+    #[error("cannot reconcile: {0}")]
+    Reconcile(String),
+
     #[error("config file not found at {0}")]
     ConfigNotFound(PathBuf),
 
@@ -17,6 +31,12 @@ pub enum OrchestratorError {
     #[error("git operation failed: {0}")]
     Git(String),
 
+    #[error("refusing to push protected branch '{0}'")]
+    ProtectedBranch(String),
+
+    #[error("forge operation failed: {0}")]
+    Forge(String),
+
     #[error("agent host operation failed: {0}")]
     AgentHost(String),
 
@@ -38,12 +58,31 @@ pub enum OrchestratorError {
     #[error("blueprint validation failed: {0}")]
     BlueprintValidation(String),
 
+    #[error("prompt '{0}' not found in the prompt library")]
+    PromptNotFound(String),
+
+    #[error("template rendering failed: {0}")]
+    Template(String),
+
+    #[error("another orchestrator is already managing {0}")]
+    WorkspaceLocked(String),
+
     #[error("state persistence failed: {0}")]
     State(String),
 
+    #[error("daemon error: {0}")]
+    Daemon(String),
+
+    /// An error reported by the daemon, already formatted on its side.
+    #[error("{0}")]
+    Remote(String),
+
     #[error("process failed: {0}")]
     Process(String),
 
+    #[error("telemetry receiver failed: {0}")]
+    Telemetry(String),
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
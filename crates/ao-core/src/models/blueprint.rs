@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::config::CloneStrategy;
+
 /// A blueprint defines a reusable set of slot configurations for quick setup.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Blueprint {
@@ -22,6 +24,8 @@ pub struct BlueprintDefaults {
     pub auto_spawn_agent: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub agent: Option<BlueprintAgentConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clone_strategy: Option<CloneStrategy>,
 }
 
 /// Agent configuration for a blueprint slot.
@@ -29,6 +33,10 @@ pub struct BlueprintDefaults {
 pub struct BlueprintAgentConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prompt_template: Option<String>,
+    /// Name of a prompt in the prompt library, used when `prompt_template`
+    /// isn't set. Its tools and max turns apply unless set here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_ref: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub allowed_tools: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -49,6 +57,8 @@ pub struct BlueprintSlotEntry {
     pub auto_spawn_agent: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub agent: Option<BlueprintAgentConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clone_strategy: Option<CloneStrategy>,
 }
 
 /// A fully resolved slot configuration ready for creation.
@@ -62,4 +72,6 @@ pub struct ResolvedBlueprintSlot {
     pub prompt: Option<String>,
     pub allowed_tools: Option<String>,
     pub max_turns: Option<u32>,
+    /// `None` falls back to the source repo's `provisioning.strategy`.
+    pub clone_strategy: Option<CloneStrategy>,
 }
@@ -1,12 +1,132 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct OrchestratorConfig {
     pub apphost: String,
     #[serde(default)]
-    pub setup: Vec<String>,
+    pub setup: Vec<SetupCommand>,
+    /// Default time limit for each `setup` command.
+    #[serde(default = "default_setup_timeout_secs")]
+    pub setup_timeout_secs: u64,
     #[serde(default)]
     pub port_overrides: HashMap<String, u16>,
+    #[serde(default)]
+    pub agent: AgentConfig,
+    #[serde(default)]
+    pub provisioning: ProvisioningConfig,
+    #[serde(default)]
+    pub push: PushConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+}
+
+fn default_setup_timeout_secs() -> u64 {
+    900
+}
+
+impl OrchestratorConfig {
+    /// Time limit for each `push.pre_push` command.
+    pub fn pre_push_timeout_secs(&self) -> u64 {
+        self.push
+            .pre_push_timeout_secs
+            .unwrap_or(self.setup_timeout_secs)
+    }
+}
+
+/// A `setup` entry: a plain shell command, or a command with its own timeout.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum SetupCommand {
+    Simple(String),
+    Detailed {
+        run: String,
+        #[serde(default)]
+        timeout_secs: Option<u64>,
+    },
+}
+
+impl SetupCommand {
+    pub fn command(&self) -> &str {
+        match self {
+            SetupCommand::Simple(run) | SetupCommand::Detailed { run, .. } => run,
+        }
+    }
+
+    /// This command's own timeout, falling back to `default_secs`.
+    pub fn timeout_secs(&self, default_secs: u64) -> u64 {
+        match self {
+            SetupCommand::Detailed {
+                timeout_secs: Some(secs),
+                ..
+            } => *secs,
+            _ => default_secs,
+        }
+    }
+}
+
+/// How a slot's working copy is created from the source repository.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloneStrategy {
+    /// Independent `git clone`.
+    #[default]
+    Clone,
+    /// `git worktree add` from the local source repo; objects and refs are shared.
+    Worktree,
+    /// `git clone --reference`, borrowing objects from a local repo.
+    Reference,
+    /// `git clone --shared`, borrowing objects from the local source repo.
+    Shared,
+}
+
+/// Slot provisioning settings under the `provisioning:` key. Read from the
+/// source repo before the slot's working copy exists.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProvisioningConfig {
+    #[serde(default)]
+    pub strategy: CloneStrategy,
+    /// Local repo whose objects `reference` clones borrow (relative to the
+    /// source repo). Defaults to the source itself when it is a local path.
+    #[serde(default)]
+    pub reference_repo: Option<String>,
+}
+
+/// Push settings under the `push:` key.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PushConfig {
+    /// Branches a slot must never push, in addition to its own base branch.
+    #[serde(default)]
+    pub protected_branches: Vec<String>,
+    /// Shell commands run in the clone before pushing; any failure aborts the push.
+    #[serde(default)]
+    pub pre_push: Vec<String>,
+    /// Time limit for each `pre_push` command; `setup_timeout_secs` if unset.
+    #[serde(default)]
+    pub pre_push_timeout_secs: Option<u64>,
+}
+
+/// OpenTelemetry settings under the `telemetry:` key.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TelemetryConfig {
+    /// Run an OTLP/HTTP receiver for each slot and point the AppHost's
+    /// OpenTelemetry exporter variables at it.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Also send the AppHost's resources' logs and traces to the receiver.
+    /// They then no longer reach the Aspire dashboard. Needs `enabled`.
+    #[serde(default)]
+    pub capture_resources: bool,
+}
+
+/// Per-repo agent settings under the `agent:` key.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AgentConfig {
+    /// Path (relative to the repo root) of a system prompt template file.
+    #[serde(default)]
+    pub system_prompt_file: Option<String>,
+    /// Also write `.aspire-orchestrator-services.env` next to the JSON manifest.
+    #[serde(default)]
+    pub services_env_file: bool,
 }
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+/// A step of the slot creation pipeline, in execution order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CreationStep {
+    /// Create the working copy with the slot's clone strategy.
+    Clone,
+    /// Check out the requested branch.
+    Checkout,
+    /// Load and validate `.aspire-orchestrator.yaml`.
+    Config,
+    /// Allocate ports for `port_overrides`.
+    Ports,
+    /// Run the `setup` commands.
+    Setup,
+    /// Spawn the agent with the initial prompt, if one was given.
+    Agent,
+}
+
+impl CreationStep {
+    pub fn next(self) -> Option<CreationStep> {
+        match self {
+            CreationStep::Clone => Some(CreationStep::Checkout),
+            CreationStep::Checkout => Some(CreationStep::Config),
+            CreationStep::Config => Some(CreationStep::Ports),
+            CreationStep::Ports => Some(CreationStep::Setup),
+            CreationStep::Setup => Some(CreationStep::Agent),
+            CreationStep::Agent => None,
+        }
+    }
+}
+
+impl std::fmt::Display for CreationStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            CreationStep::Clone => "clone",
+            CreationStep::Checkout => "checkout",
+            CreationStep::Config => "config",
+            CreationStep::Ports => "ports",
+            CreationStep::Setup => "setup",
+            CreationStep::Agent => "agent",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Persisted progress of a slot that has not finished creation, with the
+/// inputs needed to resume it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreationState {
+    /// The step to run next, or the one that failed.
+    pub step: CreationStep,
+    /// Set when `step` failed (or was interrupted) and is waiting for a retry.
+    #[serde(default)]
+    pub failed: bool,
+    /// First `setup` command still to run, so a retry skips completed ones.
+    #[serde(default)]
+    pub setup_index: usize,
+    /// Branch requested at creation time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Initial agent prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+impl CreationState {
+    pub fn new(branch: Option<&str>, prompt: Option<&str>) -> Self {
+        Self {
+            step: CreationStep::Clone,
+            failed: false,
+            setup_index: 0,
+            branch: branch.map(str::to_string),
+            prompt: prompt.filter(|p| !p.is_empty()).map(str::to_string),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Commit counts relative to another ref.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AheadBehind {
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+/// A multi-step git operation left in progress in the working tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GitOperation {
+    Rebase,
+    Merge,
+    CherryPick,
+}
+
+/// How a rebase step (start, continue, or skip) ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RebaseOutcome {
+    Completed,
+    /// The rebase stopped; these paths have unresolved conflicts.
+    Conflicted(Vec<String>),
+}
+
+/// The most recent commit on the checked-out branch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSummary {
+    pub hash: String,
+    pub subject: String,
+    pub author: String,
+    pub time: DateTime<Utc>,
+}
+
+/// Snapshot of a slot clone's git state.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStatus {
+    pub branch: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<String>,
+    /// Ahead/behind the upstream tracking branch, if one is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vs_upstream: Option<AheadBehind>,
+    /// Ahead/behind `origin/<base_branch>`, if that ref exists.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vs_base: Option<AheadBehind>,
+    pub staged: u32,
+    pub unstaged: u32,
+    pub untracked: u32,
+    pub conflicted: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation: Option<GitOperation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_commit: Option<CommitSummary>,
+}
+
+impl GitStatus {
+    /// Whether the working tree has any uncommitted changes.
+    pub fn is_dirty(&self) -> bool {
+        self.staged + self.unstaged + self.untracked + self.conflicted > 0
+    }
+
+    /// Whether the clone is mid-operation or has unmerged paths.
+    pub fn has_conflicts(&self) -> bool {
+        self.conflicted > 0 || self.operation.is_some()
+    }
+}
@@ -1,10 +1,29 @@
 pub mod blueprint;
 pub mod config;
+pub mod creation;
+pub mod diff;
 pub mod discovery;
+pub mod git_status;
+pub mod problem;
+pub mod prompt;
+pub mod pull_request;
+pub mod reconcile;
 pub mod repo_candidate;
 pub mod slot;
+pub mod telemetry;
 
-pub use config::OrchestratorConfig;
+pub use config::{
+    AgentConfig, CloneStrategy, OrchestratorConfig, ProvisioningConfig, PushConfig, SetupCommand,
+    TelemetryConfig,
+};
+pub use creation::{CreationState, CreationStep};
+pub use diff::{DiffHunk, DiffLine, DiffLineKind, DiffTarget, FileChange, FileDiff};
 pub use discovery::DiscoveredServices;
+pub use git_status::{AheadBehind, CommitSummary, GitOperation, GitStatus, RebaseOutcome};
+pub use problem::{Problem, ProblemSeverity};
+pub use prompt::SavedPrompt;
+pub use pull_request::{CheckState, PrState, PullRequest, ReviewState};
+pub use reconcile::{Discrepancy, ReconcileAction};
 pub use repo_candidate::RepoCandidate;
-pub use slot::{AgentStatus, PortAllocation, Slot, SlotStatus};
+pub use slot::{AgentStatus, PortAllocation, RebaseStatus, Slot, SlotStatus};
+pub use telemetry::{ErrorSpan, TelemetrySummary};
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether a build problem stops the build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProblemSeverity {
+    Error,
+    Warning,
+}
+
+/// A compiler or MSBuild diagnostic from a slot's last build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Problem {
+    pub severity: ProblemSeverity,
+    /// The diagnostic ID, e.g. `CS1002` or `MSB3027`.
+    pub code: String,
+    /// The file it was reported in, or the tool (`CSC`) for errors without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    pub message: String,
+    /// The project being built.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+impl Problem {
+    pub fn is_error(&self) -> bool {
+        self.severity == ProblemSeverity::Error
+    }
+
+    /// Where it was reported, as MSBuild writes it, e.g. `src/Foo.cs(12,5)`.
+    pub fn location(&self) -> Option<String> {
+        let file = self.file.as_deref()?;
+        Some(match (self.line, self.column) {
+            (Some(line), Some(column)) => format!("{file}({line},{column})"),
+            (Some(line), None) => format!("{file}({line})"),
+            _ => file.to_string(),
+        })
+    }
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// A reusable agent prompt from the prompt library (`.slots/prompts/`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedPrompt {
+    /// Defaults to the file stem when the file doesn't set one.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Template text; interpolated like a blueprint `prompt_template`.
+    #[serde(default)]
+    pub body: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_tools: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_turns: Option<u32>,
+}
@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle state of a pull request on the forge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PrState {
+    #[default]
+    Open,
+    Draft,
+    Merged,
+    Closed,
+}
+
+impl PrState {
+    /// Whether the PR can still change and is worth polling.
+    pub fn is_open(self) -> bool {
+        matches!(self, PrState::Open | PrState::Draft)
+    }
+}
+
+/// Aggregate review decision on a pull request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReviewState {
+    /// No review decision yet (or reviews are not required).
+    #[default]
+    None,
+    ReviewRequired,
+    ChangesRequested,
+    Approved,
+}
+
+/// Rolled-up CI check result for a pull request's head commit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckState {
+    /// No checks reported.
+    #[default]
+    None,
+    Pending,
+    Passing,
+    Failing,
+}
+
+/// A pull request opened for a slot's branch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequest {
+    pub number: u64,
+    pub url: String,
+    #[serde(default)]
+    pub state: PrState,
+    #[serde(default)]
+    pub review: ReviewState,
+    #[serde(default)]
+    pub checks: CheckState,
+    /// When the state above was last fetched from the forge.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+/// A mismatch between `state.json` and the slots directory, found at startup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Discrepancy {
+    /// A slot whose working copy no longer exists.
+    #[serde(rename_all = "camelCase")]
+    MissingClone { slot: String, path: String },
+    /// A directory under `.slots/` that no slot owns. Only git working
+    /// copies can be adopted.
+    #[serde(rename_all = "camelCase")]
+    OrphanDirectory {
+        name: String,
+        path: String,
+        is_repo: bool,
+    },
+    /// `.agent-host.json` left behind by an agent host that has exited.
+    #[serde(rename_all = "camelCase")]
+    StaleAgentHost { slot: String, pid: u32 },
+    /// The clone has a different branch checked out than the slot records.
+    #[serde(rename_all = "camelCase")]
+    BranchMismatch {
+        slot: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+/// What to do about a [`Discrepancy`]; see [`Discrepancy::action_label`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReconcileAction {
+    Adopt,
+    Remove,
+    Fix,
+}
+
+impl Discrepancy {
+    /// The slot or directory name this is about.
+    pub fn name(&self) -> &str {
+        match self {
+            Discrepancy::MissingClone { slot, .. }
+            | Discrepancy::StaleAgentHost { slot, .. }
+            | Discrepancy::BranchMismatch { slot, .. } => slot,
+            Discrepancy::OrphanDirectory { name, .. } => name,
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        match self {
+            Discrepancy::MissingClone { path, .. } => format!("working copy missing at {path}"),
+            Discrepancy::OrphanDirectory { is_repo: true, .. } => {
+                "git working copy with no slot".to_string()
+            }
+            Discrepancy::OrphanDirectory { .. } => "directory with no slot".to_string(),
+            Discrepancy::StaleAgentHost { pid, .. } => {
+                format!("agent host (pid {pid}) is no longer running")
+            }
+            Discrepancy::BranchMismatch {
+                expected, actual, ..
+            } => format!("on {actual}, expected {expected}"),
+        }
+    }
+
+    /// What `action` does here, or `None` if it doesn't apply.
+    pub fn action_label(&self, action: ReconcileAction) -> Option<String> {
+        use ReconcileAction::*;
+        let label = match (self, action) {
+            (Discrepancy::MissingClone { .. }, Remove) => "forget slot".to_string(),
+            (Discrepancy::MissingClone { .. }, Fix) => "re-create".to_string(),
+            (Discrepancy::OrphanDirectory { is_repo: true, .. }, Adopt) => "adopt".to_string(),
+            (Discrepancy::OrphanDirectory { .. }, Remove) => "delete".to_string(),
+            (Discrepancy::StaleAgentHost { .. }, Fix) => "clean up".to_string(),
+            (Discrepancy::BranchMismatch { actual, .. }, Adopt) => format!("keep {actual}"),
+            (Discrepancy::BranchMismatch { expected, .. }, Fix) => {
+                format!("check out {expected}")
+            }
+            _ => return None,
+        };
+        Some(label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn actions_depend_on_kind() {
+        let orphan = Discrepancy::OrphanDirectory {
+            name: "old".into(),
+            path: "/w/.slots/old".into(),
+            is_repo: false,
+        };
+        assert_eq!(orphan.action_label(ReconcileAction::Adopt), None);
+        assert_eq!(
+            orphan.action_label(ReconcileAction::Remove).as_deref(),
+            Some("delete")
+        );
+
+        let mismatch = Discrepancy::BranchMismatch {
+            slot: "a".into(),
+            expected: "feature".into(),
+            actual: "main".into(),
+        };
+        assert_eq!(
+            mismatch.action_label(ReconcileAction::Adopt).as_deref(),
+            Some("keep main")
+        );
+        assert_eq!(mismatch.action_label(ReconcileAction::Remove), None);
+
+        let json = serde_json::to_value(&mismatch).unwrap();
+        assert_eq!(json["kind"], "branchMismatch");
+        assert_eq!(json["expected"], "feature");
+    }
+}
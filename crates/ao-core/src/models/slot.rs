@@ -3,12 +3,22 @@ use std::path::PathBuf;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::config::CloneStrategy;
+use super::creation::CreationState;
 use super::discovery::DiscoveredServices;
+use super::problem::Problem;
+use super::pull_request::PullRequest;
+use super::telemetry::TelemetrySummary;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum SlotStatus {
     Provisioning,
+    /// Running `setup` command `step` (0-based) of `total`.
+    SettingUp {
+        step: usize,
+        total: usize,
+    },
     Ready,
     Starting,
     Running,
@@ -16,6 +26,17 @@ pub enum SlotStatus {
     Error,
 }
 
+impl std::fmt::Display for SlotStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlotStatus::SettingUp { step, total } => {
+                write!(f, "Setting up {}/{total}", step + 1)
+            }
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum AgentStatus {
@@ -26,6 +47,16 @@ pub enum AgentStatus {
     Stopped,
 }
 
+/// Where the slot's clone is in a rebase onto its base branch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RebaseStatus {
+    #[default]
+    None,
+    Rebasing,
+    Conflicted,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct PortAllocation {
@@ -39,9 +70,39 @@ pub struct Slot {
     pub name: String,
     pub repo_path: String,
     pub branch: String,
+    /// The branch the clone started on (the source repo's default branch).
+    /// Empty for slots saved before it was recorded, until
+    /// `SlotManager::load_state` reads it from the clone's `origin/HEAD`.
+    #[serde(default)]
+    pub base_branch: String,
     pub clone_path: String,
+    /// How `clone_path` was provisioned; decides the cleanup on destroy.
+    #[serde(default)]
+    pub clone_strategy: CloneStrategy,
     pub status: SlotStatus,
+    /// Why the slot is in `Error`, e.g. the setup command that failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Creation pipeline progress; `None` once the slot is fully created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creation: Option<CreationState>,
     pub agent_status: AgentStatus,
+    #[serde(default)]
+    pub rebase_status: RebaseStatus,
+    /// Paths with unresolved conflicts while `rebase_status` is `Conflicted`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicted_files: Vec<String>,
+    /// Pull request opened for `branch`, with its last polled review/CI state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<PullRequest>,
+    /// Diagnostics from the last build in the slot's Aspire or setup output;
+    /// cleared when a build succeeds.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problems: Vec<Problem>,
+    /// Collected by the slot's OTLP receiver while Aspire runs with
+    /// `telemetry.enabled`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<TelemetrySummary>,
     pub port_allocations: Vec<PortAllocation>,
     pub services: DiscoveredServices,
     pub created_at: DateTime<Utc>,
@@ -53,15 +114,53 @@ pub struct Slot {
     pub last_agent_output_at: Option<DateTime<Utc>>,
 }
 
+/// Files the orchestrator writes into a slot's working copy, as git exclude
+/// patterns, so they never show as changes or get committed.
+pub const ORCHESTRATOR_FILES: &[&str] = &[".agent-host.json", ".aspire-orchestrator-*"];
+
+/// Directories under `.slots/` that hold orchestrator data, so no slot may
+/// take their name.
+pub const RESERVED_SLOT_NAMES: &[&str] = &["blueprints", "logs", "prompts"];
+
 impl Slot {
+    /// Check that `name` can name a slot: it becomes a directory under
+    /// `.slots/` and a tmux session name.
+    pub fn validate_name(name: &str) -> std::result::Result<(), String> {
+        if name.is_empty() {
+            return Err("name is required".into());
+        }
+        if name.starts_with('.') {
+            return Err("name can't start with '.'".into());
+        }
+        if RESERVED_SLOT_NAMES.contains(&name) {
+            return Err(format!("'{name}' is reserved"));
+        }
+        if let Some(c) = name
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        {
+            return Err(format!("'{c}' not allowed (use letters, digits, - _ .)"));
+        }
+        Ok(())
+    }
+
     pub fn new(name: String, repo_path: String, branch: String, clone_path: String) -> Self {
         Self {
             name,
             repo_path,
             branch,
+            base_branch: String::new(),
             clone_path,
+            clone_strategy: CloneStrategy::Clone,
             status: SlotStatus::Provisioning,
+            last_error: None,
+            creation: None,
             agent_status: AgentStatus::None,
+            rebase_status: RebaseStatus::None,
+            conflicted_files: Vec::new(),
+            pull_request: None,
+            problems: Vec::new(),
+            telemetry: None,
             port_allocations: Vec::new(),
             services: DiscoveredServices::default(),
             created_at: Utc::now(),
@@ -71,6 +170,11 @@ impl Slot {
         }
     }
 
+    /// Whether creation stopped on a failed step that can be retried.
+    pub fn creation_failed(&self) -> bool {
+        self.creation.as_ref().is_some_and(|c| c.failed)
+    }
+
     pub fn agent_host_file(&self) -> PathBuf {
         PathBuf::from(&self.clone_path).join(".agent-host.json")
     }
@@ -82,4 +186,12 @@ impl Slot {
     pub fn agent_log_path(&self) -> PathBuf {
         PathBuf::from(&self.clone_path).join(".aspire-orchestrator-agent.log")
     }
+
+    pub fn services_manifest_path(&self) -> PathBuf {
+        PathBuf::from(&self.clone_path).join(".aspire-orchestrator-services.json")
+    }
+
+    pub fn services_env_path(&self) -> PathBuf {
+        PathBuf::from(&self.clone_path).join(".aspire-orchestrator-services.env")
+    }
 }
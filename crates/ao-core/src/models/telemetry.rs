@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Most error spans kept on a slot; older ones are dropped.
+pub const MAX_ERROR_SPANS: usize = 20;
+
+/// What a slot's OTLP receiver has collected since Aspire started.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetrySummary {
+    /// Log records received.
+    pub logs: u64,
+    /// Spans received, including failed ones.
+    pub spans: u64,
+    /// The most recent spans with an error status, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub error_spans: Vec<ErrorSpan>,
+}
+
+impl TelemetrySummary {
+    /// Keep a failed span, dropping the oldest beyond [`MAX_ERROR_SPANS`].
+    pub fn push_error(&mut self, span: ErrorSpan) {
+        self.error_spans.push(span);
+        if self.error_spans.len() > MAX_ERROR_SPANS {
+            self.error_spans.remove(0);
+        }
+    }
+}
+
+/// A span that ended with an error status.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorSpan {
+    /// When the span ended.
+    pub time: DateTime<Utc>,
+    /// The `service.name` of the resource that reported it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    /// The span name, e.g. `GET /orders/{id}`.
+    pub name: String,
+    /// The status message, or the message of the exception it recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Hex trace ID, for finding the trace in the Aspire dashboard.
+    pub trace_id: String,
+}
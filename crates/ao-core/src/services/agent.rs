@@ -1,38 +1,111 @@
+use crate::error::Result;
 use crate::models::Slot;
+use crate::services::prompt_template::{self, TemplateContext};
 
 const DEFAULT_ALLOWED_TOOLS: &str = "Bash,Read,Glob,Grep,Write,Edit,WebFetch,WebSearch,Task";
 
-/// Build the system prompt appended to the Claude agent.
+/// The built-in system prompt, used when the repo does not configure
+/// `agent.system_prompt_file`. Lines are joined with spaces after rendering.
+pub const DEFAULT_SYSTEM_PROMPT_TEMPLATE: &str = "\
+You are working in slot '{slot_name}' on branch '{branch}'.
+Your working directory is {clone_path}.
+Before starting work, create a feature branch from the current branch using git checkout -b.
+Use conventional branch names (e.g., feature/short-description, fix/short-description).
+Commit your work frequently. Push when you have a meaningful set of changes.
+Live service URLs are kept up to date in {services_file}; re-read it if a URL stops responding or Aspire restarts.
+{#if dashboard_url}Dashboard: {dashboard_url}{/if}
+{#each services}{name}: {url}
+{/each}
+{#if has_urls}Use these URLs for browser testing.{/if}
+";
+
+/// Build the default system prompt appended to the Claude agent.
 pub fn build_system_prompt(slot: &Slot) -> String {
-    let mut lines = vec![
-        format!(
-            "You are working in slot '{}' on branch '{}'.",
-            slot.name, slot.branch
-        ),
-        format!("Your working directory is {}.", slot.clone_path),
-        "Before starting work, create a feature branch from the current branch using git checkout -b.".to_string(),
-        "Use conventional branch names (e.g., feature/short-description, fix/short-description).".to_string(),
-        "Commit your work frequently. Push when you have a meaningful set of changes.".to_string(),
-    ];
+    let rendered = prompt_template::render(
+        DEFAULT_SYSTEM_PROMPT_TEMPLATE,
+        &TemplateContext::for_slot(slot),
+    )
+    .unwrap_or_default();
 
-    if let Some(ref dashboard) = slot.services.dashboard_url {
-        lines.push(format!("Dashboard: {dashboard}"));
-    }
+    rendered
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
 
-    for (name, url) in &slot.services.service_urls {
-        lines.push(format!("{name}: {url}"));
-    }
+/// Render a repo-provided system prompt template for a slot.
+/// `slot_index` is the slot's 1-based position in the slot list.
+pub fn render_system_prompt(template: &str, slot: &Slot, slot_index: usize) -> Result<String> {
+    let mut ctx = TemplateContext::for_slot(slot);
+    ctx.set("slot_index", slot_index.to_string());
+    prompt_template::render(template, &ctx)
+}
 
-    if slot.services.dashboard_url.is_some() || !slot.services.service_urls.is_empty() {
-        lines.push("Use these URLs for browser testing.".to_string());
-    }
+/// Build the prompt that asks an agent to finish a rebase stopped on conflicts.
+/// Kept on one line so it can be typed into a running agent session.
+pub fn build_conflict_prompt(slot: &Slot) -> String {
+    let files = if slot.conflicted_files.is_empty() {
+        "(run `git status` to list them)".to_string()
+    } else {
+        slot.conflicted_files.join(", ")
+    };
+    format!(
+        "Rebasing branch '{}' onto origin/{} in {} stopped with conflicts in: {files}. \
+         Resolve each conflict, keeping the intent of both sides, and `git add` the file. \
+         Then run `git -c core.editor=true rebase --continue` and repeat until the rebase completes. \
+         Do not run `git rebase --abort`. Build and run the tests once the rebase is done.",
+        slot.branch, slot.base_branch, slot.clone_path
+    )
+}
+
+/// Most problems listed in a problems prompt; the rest are counted.
+const MAX_PROMPT_PROBLEMS: usize = 20;
 
-    lines.join(" ")
+/// Build the prompt that asks an agent to fix the problems from the slot's
+/// last build. Kept on one line so it can be typed into a running agent session.
+pub fn build_problems_prompt(slot: &Slot) -> String {
+    let errors = slot.problems.iter().filter(|p| p.is_error()).count();
+    let warnings = slot.problems.len() - errors;
+    let outcome = if errors > 0 {
+        format!("failed with {errors} error(s) and {warnings} warning(s)")
+    } else {
+        format!("succeeded with {warnings} warning(s)")
+    };
+    let mut listed: Vec<String> = slot
+        .problems
+        .iter()
+        // Errors first, in case the list is cut short
+        .filter(|p| p.is_error())
+        .chain(slot.problems.iter().filter(|p| !p.is_error()))
+        .take(MAX_PROMPT_PROBLEMS)
+        .map(|p| {
+            let kind = if p.is_error() { "error" } else { "warning" };
+            match p.location() {
+                Some(location) => format!("{location}: {kind} {}: {}", p.code, p.message),
+                None => format!("{kind} {}: {}", p.code, p.message),
+            }
+        })
+        .collect();
+    if slot.problems.len() > MAX_PROMPT_PROBLEMS {
+        listed.push(format!(
+            "and {} more",
+            slot.problems.len() - MAX_PROMPT_PROBLEMS
+        ));
+    }
+    format!(
+        "The last build of branch '{}' in {} {outcome}: {}. \
+         Fix them, then build again to check they are gone.",
+        slot.branch,
+        slot.clone_path,
+        listed.join("; ")
+    )
 }
 
 /// Build the `claude` CLI command as an argument list (for direct process spawning).
 pub fn build_claude_command(
-    slot: &Slot,
+    system_prompt: &str,
     prompt: Option<&str>,
     allowed_tools: Option<&str>,
     max_turns: Option<u32>,
@@ -44,9 +117,8 @@ pub fn build_claude_command(
         tools.to_string(),
     ];
 
-    let system_prompt = build_system_prompt(slot);
     args.push("--append-system-prompt".to_string());
-    args.push(system_prompt);
+    args.push(system_prompt.to_string());
 
     if let Some(turns) = max_turns {
         args.push("--max-turns".to_string());
@@ -66,16 +138,28 @@ pub fn build_claude_command(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{AgentStatus, DiscoveredServices, Slot, SlotStatus};
+    use crate::models::{
+        AgentStatus, CloneStrategy, DiscoveredServices, Problem, ProblemSeverity, RebaseStatus,
+        Slot, SlotStatus,
+    };
 
     fn test_slot() -> Slot {
         Slot {
             name: "test-1".into(),
             repo_path: "/repo".into(),
             branch: "main".into(),
+            base_branch: "main".into(),
             clone_path: "/clone/test-1".into(),
+            clone_strategy: CloneStrategy::Clone,
             status: SlotStatus::Running,
+            last_error: None,
+            creation: None,
             agent_status: AgentStatus::None,
+            rebase_status: RebaseStatus::None,
+            conflicted_files: vec![],
+            pull_request: None,
+            problems: vec![],
+            telemetry: None,
             port_allocations: vec![],
             services: DiscoveredServices::default(),
             created_at: chrono::Utc::now(),
@@ -107,10 +191,33 @@ mod tests {
         assert!(prompt.contains("Use these URLs for browser testing."));
     }
 
+    #[test]
+    fn build_system_prompt_matches_legacy_format() {
+        let slot = test_slot();
+        let prompt = build_system_prompt(&slot);
+        assert!(prompt.starts_with("You are working in slot 'test-1' on branch 'main'. Your"));
+        assert!(prompt.contains("Push when you have a meaningful set of changes."));
+        assert!(prompt.contains("/clone/test-1/.aspire-orchestrator-services.json"));
+        assert!(!prompt.contains('{'));
+    }
+
+    #[test]
+    fn render_system_prompt_with_index_and_base() {
+        let slot = test_slot();
+        let prompt = render_system_prompt(
+            "Slot #{slot_index} ({slot_name}) rebases onto {base_branch}.",
+            &slot,
+            3,
+        )
+        .unwrap();
+        assert_eq!(prompt, "Slot #3 (test-1) rebases onto main.");
+    }
+
     #[test]
     fn build_command_basic() {
         let slot = test_slot();
-        let args = build_claude_command(&slot, Some("Fix the bug"), None, None);
+        let args =
+            build_claude_command(&build_system_prompt(&slot), Some("Fix the bug"), None, None);
         assert_eq!(args[0], "claude");
         assert!(args.contains(&"--allowedTools".to_string()));
         assert!(args.contains(&"--append-system-prompt".to_string()));
@@ -121,7 +228,7 @@ mod tests {
     #[test]
     fn build_command_with_max_turns() {
         let slot = test_slot();
-        let args = build_claude_command(&slot, Some("Test"), None, Some(10));
+        let args = build_claude_command(&build_system_prompt(&slot), Some("Test"), None, Some(10));
         assert!(args.contains(&"--max-turns".to_string()));
         assert!(args.contains(&"10".to_string()));
     }
@@ -129,8 +236,44 @@ mod tests {
     #[test]
     fn build_command_custom_tools() {
         let slot = test_slot();
-        let args = build_claude_command(&slot, None, Some("Read,Write"), None);
+        let args =
+            build_claude_command(&build_system_prompt(&slot), None, Some("Read,Write"), None);
         assert!(args.contains(&"--allowedTools".to_string()));
         assert!(args.contains(&"Read,Write".to_string()));
     }
+
+    #[test]
+    fn conflict_prompt_lists_files() {
+        let mut slot = test_slot();
+        slot.conflicted_files = vec!["src/a.rs".into(), "src/b.rs".into()];
+        let prompt = build_conflict_prompt(&slot);
+        assert!(prompt.contains("onto origin/main"));
+        assert!(prompt.contains("src/a.rs, src/b.rs"));
+        assert!(prompt.contains("rebase --continue"));
+        assert!(!prompt.contains('\n'));
+    }
+
+    #[test]
+    fn problems_prompt_lists_errors_first() {
+        let mut slot = test_slot();
+        let problem = |severity, code: &str, line| Problem {
+            severity,
+            code: code.into(),
+            file: Some("src/Orders.cs".into()),
+            line: Some(line),
+            column: Some(5),
+            message: "bad".into(),
+            project: None,
+        };
+        slot.problems = vec![
+            problem(ProblemSeverity::Warning, "CS0168", 3),
+            problem(ProblemSeverity::Error, "CS1002", 12),
+        ];
+        let prompt = build_problems_prompt(&slot);
+        assert!(prompt.contains("failed with 1 error(s) and 1 warning(s)"));
+        assert!(prompt.contains(
+            "src/Orders.cs(12,5): error CS1002: bad; src/Orders.cs(3,5): warning CS0168: bad."
+        ));
+        assert!(!prompt.contains('\n'));
+    }
 }
@@ -212,6 +212,24 @@ pub async fn is_running(name: &str, slots_dir: &Path) -> Result<bool> {
     }
 }
 
+/// PID recorded in a slot's `.agent-host.json` when that host has exited,
+/// or `None` if there is no file or the host is still running.
+pub async fn stale_pid(name: &str, slots_dir: &Path) -> Option<u32> {
+    let info = read_host_info(name, slots_dir).await.ok()?;
+    (!is_pid_alive(info.pid)).then_some(info.pid)
+}
+
+/// Delete a slot's `.agent-host.json` if its host has exited.
+pub async fn remove_stale(name: &str, slots_dir: &Path) -> Result<()> {
+    if stale_pid(name, slots_dir).await.is_some() {
+        let info_path = slots_dir.join(name).join(".agent-host.json");
+        tokio::fs::remove_file(&info_path)
+            .await
+            .map_err(|e| OrchestratorError::AgentHost(format!("remove host info: {e}")))?;
+    }
+    Ok(())
+}
+
 async fn read_host_info(name: &str, slots_dir: &Path) -> Result<AgentHostInfo> {
     let info_path = slots_dir.join(name).join(".agent-host.json");
     read_host_info_from_path(&info_path).await
@@ -225,7 +243,7 @@ async fn read_host_info_from_path(path: &PathBuf) -> Result<AgentHostInfo> {
         .map_err(|e| OrchestratorError::AgentHost(format!("parse host info: {e}")))
 }
 
-fn is_pid_alive(pid: u32) -> bool {
+pub(crate) fn is_pid_alive(pid: u32) -> bool {
     #[cfg(windows)]
     {
         use std::os::windows::io::FromRawHandle;
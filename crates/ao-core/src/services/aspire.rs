@@ -1,33 +1,48 @@
 use std::path::Path;
 use std::process::Stdio;
 
+use chrono::{DateTime, Utc};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::{Child, Command};
 use tokio::sync::mpsc;
 
 use crate::error::{OrchestratorError, Result};
-use crate::models::{OrchestratorConfig, PortAllocation};
+use crate::models::{OrchestratorConfig, PortAllocation, TelemetryConfig};
+
+use super::otlp;
 
 /// Build environment variable map from port allocations.
-pub fn build_env_vars(allocations: &[PortAllocation]) -> Vec<(String, String)> {
-    allocations
+pub fn build_env_vars(
+    allocations: &[PortAllocation],
+    telemetry: &TelemetryConfig,
+) -> Vec<(String, String)> {
+    let mut vars: Vec<_> = allocations
         .iter()
         .map(|a| (a.name.clone(), a.port.to_string()))
-        .collect()
+        .collect();
+    // Point the AppHost's OpenTelemetry exporter at the slot's receiver
+    if let Some(otlp) = allocations.iter().find(|a| a.name == otlp::OTLP_PORT_NAME) {
+        vars.extend(otlp::exporter_env(otlp.port));
+        if telemetry.capture_resources {
+            vars.extend(otlp::resource_exporter_env(otlp.port));
+        }
+    }
+    vars
 }
 
 /// Spawn the Aspire AppHost as a direct child process.
 ///
 /// Returns the child process handle and a channel receiver that streams
-/// combined stdout/stderr lines for log capture and service discovery.
+/// combined stdout/stderr lines, each with the time it was read, for log
+/// capture and service discovery.
 pub async fn start(
     clone_path: &Path,
     config: &OrchestratorConfig,
     port_allocations: &[PortAllocation],
-) -> Result<(Child, mpsc::UnboundedReceiver<String>)> {
+) -> Result<(Child, mpsc::UnboundedReceiver<(DateTime<Utc>, String)>)> {
     let apphost_path = clone_path.join(&config.apphost);
 
-    let env_vars = build_env_vars(port_allocations);
+    let env_vars = build_env_vars(port_allocations, &config.telemetry);
 
     let mut cmd = Command::new("dotnet");
     cmd.args([
@@ -57,7 +72,7 @@ pub async fn start(
             let reader = BufReader::new(stdout);
             let mut lines = reader.lines();
             while let Ok(Some(line)) = lines.next_line().await {
-                let _ = tx_out.send(line);
+                let _ = tx_out.send((Utc::now(), line));
             }
         });
     }
@@ -69,7 +84,7 @@ pub async fn start(
             let reader = BufReader::new(stderr);
             let mut lines = reader.lines();
             while let Ok(Some(line)) = lines.next_line().await {
-                let _ = tx_err.send(line);
+                let _ = tx_err.send((Utc::now(), line));
             }
         });
     }
@@ -92,7 +107,7 @@ mod tests {
 
     #[test]
     fn build_env_vars_empty() {
-        let vars = build_env_vars(&[]);
+        let vars = build_env_vars(&[], &TelemetryConfig::default());
         assert!(vars.is_empty());
     }
 
@@ -108,9 +123,45 @@ mod tests {
                 port: 5001,
             },
         ];
-        let vars = build_env_vars(&allocs);
+        let vars = build_env_vars(&allocs, &TelemetryConfig::default());
         assert_eq!(vars.len(), 2);
         assert_eq!(vars[0], ("VITE_PORT".into(), "15000".into()));
         assert_eq!(vars[1], ("API_PORT".into(), "5001".into()));
     }
+
+    #[test]
+    fn build_env_vars_points_exporter_at_otlp_port() {
+        let allocs = vec![PortAllocation {
+            name: "OTLP_PORT".into(),
+            port: 4318,
+        }];
+        let mut telemetry = TelemetryConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let vars = build_env_vars(&allocs, &telemetry);
+        assert_eq!(
+            vars,
+            [
+                ("OTLP_PORT".into(), "4318".into()),
+                (
+                    "OTEL_EXPORTER_OTLP_ENDPOINT".into(),
+                    "http://127.0.0.1:4318".into()
+                ),
+                ("OTEL_EXPORTER_OTLP_PROTOCOL".into(), "http/protobuf".into()),
+            ]
+        );
+
+        // Resources' logs and traces are only taken from the dashboard on request
+        telemetry.capture_resources = true;
+        let vars = build_env_vars(&allocs, &telemetry);
+        assert!(vars.contains(&(
+            "OTEL_EXPORTER_OTLP_LOGS_ENDPOINT".into(),
+            "http://127.0.0.1:4318/v1/logs".into()
+        )));
+        assert!(vars.contains(&(
+            "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT".into(),
+            "http://127.0.0.1:4318/v1/traces".into()
+        )));
+    }
 }
@@ -4,7 +4,9 @@ use crate::error::{OrchestratorError, Result};
 use crate::models::blueprint::{
     Blueprint, BlueprintAgentConfig, BlueprintSlotEntry, ResolvedBlueprintSlot,
 };
-use crate::models::Slot;
+use crate::models::{CloneStrategy, SavedPrompt, Slot};
+use crate::services::prompt_library;
+use crate::services::prompt_template::{self, TemplateContext};
 
 /// Manages blueprint YAML files in the `.slots/blueprints/` directory.
 pub struct BlueprintStore {
@@ -128,6 +130,8 @@ impl BlueprintStore {
                 auto_start_aspire: None,
                 auto_spawn_agent: None,
                 agent: None,
+                clone_strategy: (s.clone_strategy != CloneStrategy::Clone)
+                    .then_some(s.clone_strategy),
             })
             .collect();
 
@@ -178,15 +182,20 @@ pub fn validate(blueprint: &Blueprint) -> std::result::Result<(), Vec<String>> {
 }
 
 /// Interpolate template variables in a string.
-/// Supports `{slot_name}` and `{branch}`.
+/// Supports `{slot_name}` and `{branch}` plus the `prompt_template` block syntax;
+/// a malformed template is returned unchanged.
 pub fn interpolate(template: &str, slot_name: &str, branch: &str) -> String {
-    template
-        .replace("{slot_name}", slot_name)
-        .replace("{branch}", branch)
+    let mut ctx = TemplateContext::new();
+    ctx.set("slot_name", slot_name).set("branch", branch);
+    prompt_template::render(template, &ctx).unwrap_or_else(|_| template.to_string())
 }
 
 /// Resolve a blueprint into a list of fully-specified slot configurations.
-pub fn resolve(blueprint: &Blueprint) -> Result<Vec<ResolvedBlueprintSlot>> {
+/// `prompts` is the prompt library that `agent.prompt_ref` names refer to.
+pub fn resolve(
+    blueprint: &Blueprint,
+    prompts: &[SavedPrompt],
+) -> Result<Vec<ResolvedBlueprintSlot>> {
     if let Err(errors) = validate(blueprint) {
         return Err(OrchestratorError::BlueprintValidation(errors.join("; ")));
     }
@@ -196,6 +205,7 @@ pub fn resolve(blueprint: &Blueprint) -> Result<Vec<ResolvedBlueprintSlot>> {
     let default_auto_start = defaults.and_then(|d| d.auto_start_aspire).unwrap_or(false);
     let default_auto_spawn = defaults.and_then(|d| d.auto_spawn_agent).unwrap_or(false);
     let default_agent = defaults.and_then(|d| d.agent.as_ref());
+    let default_clone_strategy = defaults.and_then(|d| d.clone_strategy);
 
     let mut resolved = Vec::new();
 
@@ -221,13 +231,30 @@ pub fn resolve(blueprint: &Blueprint) -> Result<Vec<ResolvedBlueprintSlot>> {
         // Resolve agent config: slot-level overrides default-level
         let agent_config = merge_agent_config(default_agent, slot.agent.as_ref());
 
+        let prompt_ref = match agent_config.as_ref().and_then(|a| a.prompt_ref.as_deref()) {
+            Some(name) => Some(prompt_library::find(prompts, name).ok_or_else(|| {
+                OrchestratorError::BlueprintValidation(format!(
+                    "Slot '{}' references unknown prompt '{name}'",
+                    slot.name
+                ))
+            })?),
+            None => None,
+        };
+
         let prompt = agent_config
             .as_ref()
             .and_then(|a| a.prompt_template.as_deref())
+            .or(prompt_ref.map(|p| p.body.as_str()))
             .map(|t| interpolate(t, &slot.name, branch_str));
 
-        let allowed_tools = agent_config.as_ref().and_then(|a| a.allowed_tools.clone());
-        let max_turns = agent_config.as_ref().and_then(|a| a.max_turns);
+        let allowed_tools = agent_config
+            .as_ref()
+            .and_then(|a| a.allowed_tools.clone())
+            .or_else(|| prompt_ref.and_then(|p| p.allowed_tools.clone()));
+        let max_turns = agent_config
+            .as_ref()
+            .and_then(|a| a.max_turns)
+            .or(prompt_ref.and_then(|p| p.max_turns));
 
         resolved.push(ResolvedBlueprintSlot {
             name: slot.name.clone(),
@@ -238,6 +265,7 @@ pub fn resolve(blueprint: &Blueprint) -> Result<Vec<ResolvedBlueprintSlot>> {
             prompt,
             allowed_tools,
             max_turns,
+            clone_strategy: slot.clone_strategy.or(default_clone_strategy),
         });
     }
 
@@ -253,11 +281,21 @@ fn merge_agent_config(
         (None, None) => None,
         (Some(d), None) => Some(d.clone()),
         (None, Some(s)) => Some(s.clone()),
-        (Some(d), Some(s)) => Some(BlueprintAgentConfig {
-            prompt_template: s.prompt_template.clone().or(d.prompt_template.clone()),
-            allowed_tools: s.allowed_tools.clone().or(d.allowed_tools.clone()),
-            max_turns: s.max_turns.or(d.max_turns),
-        }),
+        (Some(d), Some(s)) => {
+            // A slot that names its own prompt replaces the default one, whichever
+            // form either of them uses
+            let prompt_source = if s.prompt_template.is_some() || s.prompt_ref.is_some() {
+                s
+            } else {
+                d
+            };
+            Some(BlueprintAgentConfig {
+                prompt_template: prompt_source.prompt_template.clone(),
+                prompt_ref: prompt_source.prompt_ref.clone(),
+                allowed_tools: s.allowed_tools.clone().or(d.allowed_tools.clone()),
+                max_turns: s.max_turns.or(d.max_turns),
+            })
+        }
     }
 }
 
@@ -302,6 +340,7 @@ mod tests {
                 auto_start_aspire: None,
                 auto_spawn_agent: None,
                 agent: None,
+                clone_strategy: None,
             }],
         };
         let err = validate(&bp).unwrap_err();
@@ -318,6 +357,7 @@ mod tests {
                 auto_start_aspire: None,
                 auto_spawn_agent: None,
                 agent: None,
+                clone_strategy: None,
             }),
             slots: vec![BlueprintSlotEntry {
                 name: "slot1".to_string(),
@@ -326,6 +366,7 @@ mod tests {
                 auto_start_aspire: None,
                 auto_spawn_agent: None,
                 agent: None,
+                clone_strategy: None,
             }],
         };
         assert!(validate(&bp).is_ok());
@@ -342,9 +383,11 @@ mod tests {
                 auto_spawn_agent: Some(true),
                 agent: Some(BlueprintAgentConfig {
                     prompt_template: Some("Work on {branch} in {slot_name}".to_string()),
+                    prompt_ref: None,
                     allowed_tools: Some("Bash,Read".to_string()),
                     max_turns: Some(50),
                 }),
+                clone_strategy: Some(CloneStrategy::Worktree),
             }),
             slots: vec![
                 BlueprintSlotEntry {
@@ -355,9 +398,11 @@ mod tests {
                     auto_spawn_agent: None,
                     agent: Some(BlueprintAgentConfig {
                         prompt_template: Some("Custom prompt for {slot_name}".to_string()),
+                        prompt_ref: None,
                         allowed_tools: None,
                         max_turns: None,
                     }),
+                    clone_strategy: None,
                 },
                 BlueprintSlotEntry {
                     name: "ui-dashboard".to_string(),
@@ -366,11 +411,12 @@ mod tests {
                     auto_start_aspire: Some(false),
                     auto_spawn_agent: None,
                     agent: None,
+                    clone_strategy: Some(CloneStrategy::Clone),
                 },
             ],
         };
 
-        let resolved = resolve(&bp).unwrap();
+        let resolved = resolve(&bp, &[]).unwrap();
         assert_eq!(resolved.len(), 2);
 
         // First slot: overridden prompt, inherited tools/turns
@@ -384,10 +430,12 @@ mod tests {
         assert_eq!(resolved[0].max_turns, Some(50));
         assert!(resolved[0].auto_start_aspire);
         assert!(resolved[0].auto_spawn_agent);
+        assert_eq!(resolved[0].clone_strategy, Some(CloneStrategy::Worktree));
 
         // Second slot: default prompt, overridden auto_start
         assert_eq!(resolved[1].name, "ui-dashboard");
         assert!(!resolved[1].auto_start_aspire);
+        assert_eq!(resolved[1].clone_strategy, Some(CloneStrategy::Clone));
         assert!(resolved[1].auto_spawn_agent);
         assert_eq!(
             resolved[1].prompt.as_deref(),
@@ -395,6 +443,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_prompt_ref() {
+        let slot = |name: &str, agent: Option<BlueprintAgentConfig>| BlueprintSlotEntry {
+            name: name.to_string(),
+            branch: Some(format!("feature/{name}")),
+            source: Some("/path/to/repo".to_string()),
+            auto_start_aspire: None,
+            auto_spawn_agent: None,
+            agent,
+            clone_strategy: None,
+        };
+        let mut bp = Blueprint {
+            name: "refs".to_string(),
+            description: None,
+            defaults: Some(BlueprintDefaults {
+                source: None,
+                auto_start_aspire: None,
+                auto_spawn_agent: None,
+                agent: Some(BlueprintAgentConfig {
+                    prompt_template: Some("Default for {slot_name}".to_string()),
+                    prompt_ref: None,
+                    allowed_tools: None,
+                    max_turns: None,
+                }),
+                clone_strategy: None,
+            }),
+            slots: vec![slot(
+                "tests",
+                Some(BlueprintAgentConfig {
+                    prompt_template: None,
+                    prompt_ref: Some("fix-tests".to_string()),
+                    allowed_tools: None,
+                    max_turns: Some(5),
+                }),
+            )],
+        };
+        let prompts = vec![SavedPrompt {
+            name: "fix-tests".to_string(),
+            description: None,
+            body: "Fix the failing tests on {branch}.".to_string(),
+            allowed_tools: Some("Bash,Read".to_string()),
+            max_turns: Some(30),
+        }];
+
+        // The slot's reference replaces the default template; its own max
+        // turns win over the prompt's
+        let resolved = resolve(&bp, &prompts).unwrap();
+        assert_eq!(
+            resolved[0].prompt.as_deref(),
+            Some("Fix the failing tests on feature/tests.")
+        );
+        assert_eq!(resolved[0].allowed_tools.as_deref(), Some("Bash,Read"));
+        assert_eq!(resolved[0].max_turns, Some(5));
+
+        let err = resolve(&bp, &[]).unwrap_err();
+        assert!(err.to_string().contains("unknown prompt 'fix-tests'"));
+
+        bp.slots = vec![slot("other", None)];
+        let resolved = resolve(&bp, &[]).unwrap();
+        assert_eq!(resolved[0].prompt.as_deref(), Some("Default for other"));
+    }
+
     #[test]
     fn test_snapshot_from_slots() {
         use crate::models::Slot;
@@ -436,6 +546,7 @@ mod tests {
                 auto_start_aspire: None,
                 auto_spawn_agent: None,
                 agent: None,
+                clone_strategy: None,
             }),
             slots: vec![BlueprintSlotEntry {
                 name: "slot1".to_string(),
@@ -444,6 +555,7 @@ mod tests {
                 auto_start_aspire: None,
                 auto_spawn_agent: None,
                 agent: None,
+                clone_strategy: None,
             }],
         };
 
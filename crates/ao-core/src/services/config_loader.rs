@@ -24,6 +24,7 @@ pub fn load(repo_path: &Path) -> Result<OrchestratorConfig> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::CloneStrategy;
     use std::fs;
 
     #[test]
@@ -57,6 +58,69 @@ port_overrides:
         assert!(config.port_overrides.is_empty());
     }
 
+    #[test]
+    fn parse_setup_with_timeouts() {
+        let dir = tempfile::tempdir().unwrap();
+        let yaml = r#"
+apphost: src/App.AppHost/App.AppHost.csproj
+setup_timeout_secs: 120
+setup:
+  - dotnet restore
+  - run: npm install
+    timeout_secs: 600
+"#;
+        fs::write(dir.path().join(CONFIG_FILENAME), yaml).unwrap();
+        let config = load(dir.path()).unwrap();
+        assert_eq!(config.setup[0].command(), "dotnet restore");
+        assert_eq!(config.setup[0].timeout_secs(config.setup_timeout_secs), 120);
+        assert_eq!(config.setup[1].command(), "npm install");
+        assert_eq!(config.setup[1].timeout_secs(config.setup_timeout_secs), 600);
+    }
+
+    #[test]
+    fn parse_agent_section() {
+        let dir = tempfile::tempdir().unwrap();
+        let yaml = r#"
+apphost: src/App.AppHost/App.AppHost.csproj
+agent:
+  system_prompt_file: .aspire-orchestrator/agent-prompt.md
+"#;
+        fs::write(dir.path().join(CONFIG_FILENAME), yaml).unwrap();
+        let config = load(dir.path()).unwrap();
+        assert_eq!(
+            config.agent.system_prompt_file.as_deref(),
+            Some(".aspire-orchestrator/agent-prompt.md")
+        );
+    }
+
+    #[test]
+    fn parse_provisioning_section() {
+        let dir = tempfile::tempdir().unwrap();
+        let yaml = r#"
+apphost: src/App.AppHost/App.AppHost.csproj
+provisioning:
+  strategy: worktree
+  reference_repo: ../mirror
+"#;
+        fs::write(dir.path().join(CONFIG_FILENAME), yaml).unwrap();
+        let config = load(dir.path()).unwrap();
+        assert_eq!(config.provisioning.strategy, CloneStrategy::Worktree);
+        assert_eq!(
+            config.provisioning.reference_repo.as_deref(),
+            Some("../mirror")
+        );
+    }
+
+    #[test]
+    fn parse_telemetry_section() {
+        let dir = tempfile::tempdir().unwrap();
+        let yaml = "apphost: src/App.AppHost/App.AppHost.csproj\ntelemetry:\n  enabled: true\n";
+        fs::write(dir.path().join(CONFIG_FILENAME), yaml).unwrap();
+        let telemetry = load(dir.path()).unwrap().telemetry;
+        assert!(telemetry.enabled);
+        assert!(!telemetry.capture_resources);
+    }
+
     #[test]
     fn missing_config_returns_error() {
         let dir = tempfile::tempdir().unwrap();
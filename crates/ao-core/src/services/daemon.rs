@@ -0,0 +1,809 @@
+//! Long-running orchestrator daemon. It owns a `SlotManager` and serves a
+//! line-delimited JSON control API on localhost, so the TUI and CLI can attach
+//! and detach without taking Aspire or agent processes down with them.
+//!
+//! Each request is one JSON line carrying the token from `.daemon.json`, e.g.
+//! `{"token":"...","method":"start","params":{"name":"a"}}`, answered by one
+//! line: `{"ok":true,"result":...}` or `{"ok":false,"error":"..."}`. A request
+//! without the right token is refused and its connection closed, as is one
+//! that sends no token within [`AUTH_TIMEOUT`] or a line over
+//! [`MAX_REQUEST_LINE`] bytes. A `subscribe`
+//! request turns its connection into a stream of [`Event`] lines.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc, watch};
+
+use super::agent_host::is_pid_alive;
+use super::log_tailer::LogLine;
+use super::slot_manager::SlotManager;
+use crate::error::{OrchestratorError, Result};
+use crate::models::{CloneStrategy, DiffTarget, Discrepancy, ReconcileAction, Slot};
+
+const INFO_FILE: &str = ".daemon.json";
+
+/// How often the daemon checks for slot changes to publish.
+const SLOT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Log lines buffered per subscriber before it starts skipping.
+const LOG_BUFFER: usize = 1024;
+
+/// Random bytes in a control API token.
+const TOKEN_BYTES: usize = 32;
+
+/// Longest request line accepted, newline included.
+const MAX_REQUEST_LINE: usize = 1024 * 1024;
+
+/// How long a new connection has to send its first request with the token.
+const AUTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Metadata written by the daemon to `{slots_dir}/.daemon.json`, readable only
+/// by its owner since `token` grants full control of the slots.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DaemonInfo {
+    pub port: u16,
+    pub pid: u32,
+    /// Every request must carry this; anyone else on the machine can reach
+    /// the port but not read the file.
+    pub token: String,
+}
+
+/// A request as sent on the wire, with the caller's token alongside.
+#[derive(Serialize)]
+struct Authenticated<'a> {
+    token: &'a str,
+    #[serde(flatten)]
+    request: &'a Request,
+}
+
+/// Just the token of an incoming line, checked before the request is parsed.
+#[derive(Deserialize)]
+struct Token {
+    #[serde(default)]
+    token: String,
+}
+
+/// A control API request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "kebab-case")]
+pub enum Request {
+    List,
+    Get {
+        name: String,
+    },
+    Create {
+        name: String,
+        source: String,
+        #[serde(default)]
+        branch: Option<String>,
+        #[serde(default)]
+        prompt: Option<String>,
+        #[serde(default)]
+        strategy: Option<CloneStrategy>,
+    },
+    RetryCreation {
+        name: String,
+    },
+    Start {
+        name: String,
+    },
+    Stop {
+        name: String,
+    },
+    SpawnAgent {
+        name: String,
+        #[serde(default)]
+        prompt: Option<String>,
+        #[serde(default)]
+        allowed_tools: Option<String>,
+        #[serde(default)]
+        max_turns: Option<u32>,
+    },
+    GitStatus {
+        name: String,
+    },
+    Diff {
+        name: String,
+        #[serde(default)]
+        target: DiffTarget,
+    },
+    Rebase {
+        name: String,
+    },
+    RebaseContinue {
+        name: String,
+    },
+    RebaseSkip {
+        name: String,
+    },
+    RebaseAbort {
+        name: String,
+    },
+    SendConflictToAgent {
+        name: String,
+    },
+    SendProblemsToAgent {
+        name: String,
+    },
+    Push {
+        name: String,
+    },
+    CreatePullRequest {
+        name: String,
+    },
+    RefreshPullRequest {
+        name: String,
+    },
+    Destroy {
+        name: String,
+    },
+    Reconcile,
+    Resolve {
+        discrepancy: Discrepancy,
+        action: ReconcileAction,
+    },
+    /// Stream log lines and/or slot snapshots on this connection.
+    Subscribe {
+        #[serde(default)]
+        logs: bool,
+        #[serde(default)]
+        events: bool,
+    },
+}
+
+/// Reply to a request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Response {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Response {
+    fn success(result: Value) -> Self {
+        Self {
+            ok: true,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    fn failure(error: String) -> Self {
+        Self {
+            ok: false,
+            result: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// A message streamed to subscribers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum Event {
+    /// A line of Aspire, setup, pre-push, or agent output.
+    Log(LogLine),
+    /// The full slot list, sent on subscribe and whenever any slot changes.
+    Slots { slots: Vec<Slot> },
+}
+
+/// Bind the control port on localhost and record it, with a fresh token, in
+/// `.daemon.json`. Fails if a daemon is already serving `slots_dir`.
+pub async fn bind(slots_dir: &Path) -> Result<(TcpListener, String)> {
+    if let Some(info) = read_info(slots_dir).await {
+        if DaemonClient::connect(slots_dir).await.is_some() {
+            return Err(OrchestratorError::Daemon(format!(
+                "already running for {} (pid {})",
+                slots_dir.display(),
+                info.pid
+            )));
+        }
+    }
+
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|e| OrchestratorError::Daemon(format!("bind: {e}")))?;
+    let info = DaemonInfo {
+        port: listener.local_addr()?.port(),
+        pid: std::process::id(),
+        token: generate_token()?,
+    };
+    tokio::fs::create_dir_all(slots_dir).await?;
+    write_info(&slots_dir.join(INFO_FILE), &info).await?;
+    Ok((listener, info.token))
+}
+
+fn generate_token() -> Result<String> {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    getrandom::fill(&mut bytes)
+        .map_err(|e| OrchestratorError::Daemon(format!("generate token: {e}")))?;
+    Ok(bytes.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Write `.daemon.json` as a new file, owner read/write only on Unix, so the
+/// token is never readable by other users, even briefly.
+async fn write_info(path: &Path, info: &DaemonInfo) -> Result<()> {
+    let _ = tokio::fs::remove_file(path).await;
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path).await?;
+    file.write_all(&serde_json::to_vec(info)?).await?;
+    file.flush().await?;
+    Ok(())
+}
+
+/// Compare tokens without stopping at the first differing byte.
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Remove `.daemon.json` when the daemon shuts down.
+pub async fn remove_info(slots_dir: &Path) {
+    let _ = tokio::fs::remove_file(slots_dir.join(INFO_FILE)).await;
+}
+
+/// Serve the control API until accepting fails, to requests carrying
+/// `token`. `log_rx` is the receiver paired with the sender `sm` was created
+/// with.
+pub async fn serve(
+    sm: Arc<SlotManager>,
+    mut log_rx: mpsc::UnboundedReceiver<LogLine>,
+    listener: TcpListener,
+    token: String,
+) -> Result<()> {
+    let (log_tx, _) = broadcast::channel::<LogLine>(LOG_BUFFER);
+    let forward_tx = log_tx.clone();
+    tokio::spawn(async move {
+        while let Some(line) = log_rx.recv().await {
+            let _ = forward_tx.send(line);
+        }
+    });
+
+    let initial = sm.get_slots().await;
+    let mut last_snapshot = serde_json::to_value(&initial).ok();
+    let (slots_tx, slots_rx) = watch::channel(initial);
+    let token: Arc<str> = token.into();
+    let watched = Arc::clone(&sm);
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(SLOT_POLL_INTERVAL).await;
+            if let Err(e) = watched.reload_if_changed().await {
+                tracing::warn!("reload state: {e}");
+            }
+            let slots = watched.get_slots().await;
+            let snapshot = serde_json::to_value(&slots).ok();
+            if snapshot != last_snapshot {
+                last_snapshot = snapshot;
+                if slots_tx.send(slots).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    loop {
+        let (stream, _) = listener
+            .accept()
+            .await
+            .map_err(|e| OrchestratorError::Daemon(format!("accept: {e}")))?;
+        let sm = Arc::clone(&sm);
+        let log_tx = log_tx.clone();
+        let slots_rx = slots_rx.clone();
+        let token = Arc::clone(&token);
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &sm, &token, log_tx, slots_rx).await {
+                tracing::debug!("control connection closed: {e}");
+            }
+        });
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    sm: &SlotManager,
+    token: &str,
+    log_tx: broadcast::Sender<LogLine>,
+    slots_rx: watch::Receiver<Vec<Slot>>,
+) -> Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+    let mut authenticated = false;
+    loop {
+        let line = if authenticated {
+            read_request_line(&mut reader).await
+        } else {
+            match tokio::time::timeout(AUTH_TIMEOUT, read_request_line(&mut reader)).await {
+                Ok(line) => line,
+                Err(_) => return Ok(()),
+            }
+        };
+        let line = match line {
+            Ok(Some(line)) => line,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                write_line(&mut write, &Response::failure(e.to_string())).await?;
+                return Ok(());
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let authorized = serde_json::from_str::<Token>(&line)
+            .is_ok_and(|given| token_matches(&given.token, token));
+        if !authorized {
+            write_line(&mut write, &Response::failure("unauthorized".into())).await?;
+            return Ok(());
+        }
+        authenticated = true;
+        let request = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => request,
+            Err(e) => {
+                write_line(
+                    &mut write,
+                    &Response::failure(format!("invalid request: {e}")),
+                )
+                .await?;
+                continue;
+            }
+        };
+        if let Request::Subscribe { logs, events } = request {
+            write_line(&mut write, &Response::success(Value::Null)).await?;
+            let logs = logs.then(|| log_tx.subscribe());
+            let slots = events.then_some(slots_rx);
+            return stream_events(reader, write, logs, slots).await;
+        }
+        let response = match dispatch(sm, request).await {
+            Ok(result) => Response::success(result),
+            Err(e) => Response::failure(e.to_string()),
+        };
+        write_line(&mut write, &response).await?;
+    }
+}
+
+/// Read one request line of at most [`MAX_REQUEST_LINE`] bytes, or `None` at
+/// EOF.
+async fn read_request_line<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<String>> {
+    let mut line = Vec::new();
+    let read = (&mut *reader)
+        .take(MAX_REQUEST_LINE as u64)
+        .read_until(b'\n', &mut line)
+        .await?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if !line.ends_with(b"\n") {
+        return Err(OrchestratorError::Daemon("request line too long".into()));
+    }
+    let line = String::from_utf8(line)
+        .map_err(|_| OrchestratorError::Daemon("request is not UTF-8".into()))?;
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+async fn dispatch(sm: &SlotManager, request: Request) -> Result<Value> {
+    let result = match request {
+        Request::List => to_value(sm.get_slots().await)?,
+        Request::Get { name } => to_value(sm.get_slot(&name).await)?,
+        Request::Create {
+            name,
+            source,
+            branch,
+            prompt,
+            strategy,
+        } => to_value(
+            sm.create_slot(
+                &name,
+                &source,
+                branch.as_deref(),
+                prompt.as_deref(),
+                strategy,
+            )
+            .await?,
+        )?,
+        Request::RetryCreation { name } => to_value(sm.retry_creation(&name).await?)?,
+        Request::Start { name } => to_value(sm.start_aspire(&name).await?)?,
+        Request::Stop { name } => to_value(sm.stop_aspire(&name).await?)?,
+        Request::SpawnAgent {
+            name,
+            prompt,
+            allowed_tools,
+            max_turns,
+        } => to_value(
+            sm.spawn_agent(
+                &name,
+                prompt.as_deref(),
+                allowed_tools.as_deref(),
+                max_turns,
+            )
+            .await?,
+        )?,
+        Request::GitStatus { name } => to_value(sm.git_status(&name).await?)?,
+        Request::Diff { name, target } => to_value(sm.diff(&name, target).await?)?,
+        Request::Rebase { name } => to_value(sm.rebase(&name).await?)?,
+        Request::RebaseContinue { name } => to_value(sm.rebase_continue(&name).await?)?,
+        Request::RebaseSkip { name } => to_value(sm.rebase_skip(&name).await?)?,
+        Request::RebaseAbort { name } => to_value(sm.rebase_abort(&name).await?)?,
+        Request::SendConflictToAgent { name } => to_value(sm.send_conflict_to_agent(&name).await?)?,
+        Request::SendProblemsToAgent { name } => to_value(sm.send_problems_to_agent(&name).await?)?,
+        Request::Push { name } => to_value(sm.git_push(&name).await?)?,
+        Request::CreatePullRequest { name } => to_value(sm.create_pull_request(&name).await?)?,
+        Request::RefreshPullRequest { name } => to_value(sm.refresh_pull_request(&name).await?)?,
+        Request::Destroy { name } => to_value(sm.destroy_slot(&name).await?)?,
+        Request::Reconcile => to_value(sm.reconcile().await)?,
+        Request::Resolve {
+            discrepancy,
+            action,
+        } => to_value(sm.resolve_discrepancy(&discrepancy, action).await?)?,
+        // Handled by the connection before dispatch
+        Request::Subscribe { .. } => Value::Null,
+    };
+    Ok(result)
+}
+
+/// Forward log lines and slot snapshots until the client goes away.
+async fn stream_events(
+    mut reader: BufReader<OwnedReadHalf>,
+    mut write: OwnedWriteHalf,
+    mut logs: Option<broadcast::Receiver<LogLine>>,
+    mut slots: Option<watch::Receiver<Vec<Slot>>>,
+) -> Result<()> {
+    if logs.is_none() && slots.is_none() {
+        return Ok(());
+    }
+    if let Some(ref mut rx) = slots {
+        let snapshot = rx.borrow_and_update().clone();
+        write_line(&mut write, &Event::Slots { slots: snapshot }).await?;
+    }
+
+    loop {
+        let event = tokio::select! {
+            line = recv_log(&mut logs) => match line {
+                Some(line) => Event::Log(line),
+                None => return Ok(()),
+            },
+            snapshot = recv_slots(&mut slots) => match snapshot {
+                Some(slots) => Event::Slots { slots },
+                None => return Ok(()),
+            },
+            input = read_request_line(&mut reader) => match input {
+                // Subscribers don't send anything else; EOF means they left
+                Ok(Some(_)) => continue,
+                _ => return Ok(()),
+            },
+        };
+        write_line(&mut write, &event).await?;
+    }
+}
+
+async fn recv_log(logs: &mut Option<broadcast::Receiver<LogLine>>) -> Option<LogLine> {
+    let Some(rx) = logs else {
+        return std::future::pending().await;
+    };
+    loop {
+        match rx.recv().await {
+            Ok(line) => return Some(line),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::debug!("log subscriber lagged, skipped {skipped} line(s)");
+            }
+            Err(broadcast::error::RecvError::Closed) => return None,
+        }
+    }
+}
+
+async fn recv_slots(slots: &mut Option<watch::Receiver<Vec<Slot>>>) -> Option<Vec<Slot>> {
+    let Some(rx) = slots else {
+        return std::future::pending().await;
+    };
+    rx.changed().await.ok()?;
+    let snapshot = rx.borrow_and_update().clone();
+    Some(snapshot)
+}
+
+/// A client of a running daemon. Each request opens its own connection, so
+/// one client can be shared by concurrent tasks.
+#[derive(Debug, Clone)]
+pub struct DaemonClient {
+    port: u16,
+    token: String,
+}
+
+impl DaemonClient {
+    /// Connect to the daemon serving `slots_dir`, if one is running.
+    pub async fn connect(slots_dir: &Path) -> Option<DaemonClient> {
+        let info = read_info(slots_dir).await?;
+        if !is_pid_alive(info.pid) {
+            return None;
+        }
+        TcpStream::connect(("127.0.0.1", info.port)).await.ok()?;
+        Some(DaemonClient {
+            port: info.port,
+            token: info.token,
+        })
+    }
+
+    /// Send one request and wait for its result. Errors reported by the
+    /// daemon come back as [`OrchestratorError::Remote`].
+    pub async fn call(&self, request: &Request) -> Result<Value> {
+        let (mut lines, mut write) = self.open().await?;
+        self.send(&mut write, request).await?;
+        let response = read_response(&mut lines).await?;
+        match response {
+            Response {
+                ok: true, result, ..
+            } => Ok(result.unwrap_or(Value::Null)),
+            Response { error, .. } => Err(OrchestratorError::Remote(
+                error.unwrap_or_else(|| "request failed".into()),
+            )),
+        }
+    }
+
+    /// Open a stream of log lines and/or slot snapshots.
+    pub async fn subscribe(&self, logs: bool, events: bool) -> Result<Subscription> {
+        let (mut lines, mut write) = self.open().await?;
+        self.send(&mut write, &Request::Subscribe { logs, events })
+            .await?;
+        let response = read_response(&mut lines).await?;
+        if !response.ok {
+            return Err(OrchestratorError::Remote(
+                response.error.unwrap_or_else(|| "subscribe failed".into()),
+            ));
+        }
+        Ok(Subscription {
+            lines,
+            _write: write,
+        })
+    }
+
+    async fn send(&self, write: &mut OwnedWriteHalf, request: &Request) -> Result<()> {
+        let authenticated = Authenticated {
+            token: &self.token,
+            request,
+        };
+        write_line(write, &authenticated).await
+    }
+
+    async fn open(&self) -> Result<(Lines<BufReader<OwnedReadHalf>>, OwnedWriteHalf)> {
+        let stream = TcpStream::connect(("127.0.0.1", self.port))
+            .await
+            .map_err(|e| OrchestratorError::Daemon(format!("connect: {e}")))?;
+        let (read, write) = stream.into_split();
+        Ok((BufReader::new(read).lines(), write))
+    }
+}
+
+/// An open event stream from the daemon.
+pub struct Subscription {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    /// Held open: the daemon ends the stream when this side closes.
+    _write: OwnedWriteHalf,
+}
+
+impl Subscription {
+    /// The next event, or `None` once the daemon has closed the stream.
+    pub async fn next(&mut self) -> Result<Option<Event>> {
+        let line = self
+            .lines
+            .next_line()
+            .await
+            .map_err(|e| OrchestratorError::Daemon(format!("read: {e}")))?;
+        match line {
+            Some(line) => Ok(Some(serde_json::from_str(&line)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+async fn read_info(slots_dir: &Path) -> Option<DaemonInfo> {
+    let content = tokio::fs::read_to_string(slots_dir.join(INFO_FILE))
+        .await
+        .ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+async fn read_response(lines: &mut Lines<BufReader<OwnedReadHalf>>) -> Result<Response> {
+    let line = lines
+        .next_line()
+        .await
+        .map_err(|e| OrchestratorError::Daemon(format!("read: {e}")))?
+        .ok_or_else(|| OrchestratorError::Daemon("connection closed".into()))?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+async fn write_line(write: &mut OwnedWriteHalf, value: &impl Serialize) -> Result<()> {
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+    write.write_all(&line).await?;
+    write.flush().await?;
+    Ok(())
+}
+
+fn to_value(value: impl Serialize) -> Result<Value> {
+    Ok(serde_json::to_value(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::log_tailer::LogSource;
+
+    async fn start_daemon(dir: &Path) -> (DaemonClient, mpsc::UnboundedSender<LogLine>) {
+        let (log_tx, log_rx) = mpsc::unbounded_channel();
+        let sm = Arc::new(SlotManager::new(dir.to_path_buf(), log_tx.clone()));
+        let (listener, token) = bind(dir).await.unwrap();
+        tokio::spawn(serve(sm, log_rx, listener, token));
+        let client = DaemonClient::connect(dir).await.expect("daemon reachable");
+        (client, log_tx)
+    }
+
+    #[test]
+    fn parses_requests_with_omitted_params() {
+        let list: Request = serde_json::from_str(r#"{"method":"list"}"#).unwrap();
+        assert_eq!(list, Request::List);
+
+        let create: Request = serde_json::from_str(
+            r#"{"method":"create","params":{"name":"a","source":"/src","strategy":"worktree"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            create,
+            Request::Create {
+                name: "a".into(),
+                source: "/src".into(),
+                branch: None,
+                prompt: None,
+                strategy: Some(CloneStrategy::Worktree),
+            }
+        );
+
+        let json = serde_json::to_value(Request::SpawnAgent {
+            name: "a".into(),
+            prompt: Some("hi".into()),
+            allowed_tools: None,
+            max_turns: None,
+        })
+        .unwrap();
+        assert_eq!(json["method"], "spawn-agent");
+        assert_eq!(json["params"]["prompt"], "hi");
+    }
+
+    #[tokio::test]
+    async fn serves_requests_and_reports_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let (client, _log_tx) = start_daemon(dir.path()).await;
+
+        assert_eq!(
+            client.call(&Request::List).await.unwrap(),
+            serde_json::json!([])
+        );
+        assert_eq!(
+            client
+                .call(&Request::Get {
+                    name: "nope".into()
+                })
+                .await
+                .unwrap(),
+            Value::Null
+        );
+        let err = client
+            .call(&Request::Start {
+                name: "nope".into(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "slot 'nope' not found");
+
+        // A second daemon for the same directory is refused
+        assert!(matches!(
+            bind(dir.path()).await,
+            Err(OrchestratorError::Daemon(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn requests_without_the_token_are_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let (client, _log_tx) = start_daemon(dir.path()).await;
+
+        for line in [
+            r#"{"method":"list"}"#,
+            r#"{"token":"guess","method":"list"}"#,
+        ] {
+            let stream = TcpStream::connect(("127.0.0.1", client.port))
+                .await
+                .unwrap();
+            let (read, mut write) = stream.into_split();
+            let mut lines = BufReader::new(read).lines();
+            write
+                .write_all(format!("{line}\n").as_bytes())
+                .await
+                .unwrap();
+            let response = read_response(&mut lines).await.unwrap();
+            assert_eq!(response.error.as_deref(), Some("unauthorized"));
+            // The connection is closed rather than left open for retries
+            assert!(lines.next_line().await.unwrap().is_none());
+        }
+
+        // A line that never ends is cut off instead of buffered
+        let stream = TcpStream::connect(("127.0.0.1", client.port))
+            .await
+            .unwrap();
+        let (read, mut write) = stream.into_split();
+        let mut lines = BufReader::new(read).lines();
+        let writer = tokio::spawn(async move {
+            let chunk = vec![b'x'; 64 * 1024];
+            while write.write_all(&chunk).await.is_ok() {}
+        });
+        let response = read_response(&mut lines).await.unwrap();
+        assert_eq!(
+            response.error.as_deref(),
+            Some("daemon error: request line too long")
+        );
+        assert!(lines.next_line().await.unwrap().is_none());
+        writer.abort();
+
+        assert_eq!(
+            client.call(&Request::List).await.unwrap(),
+            serde_json::json!([])
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(dir.path().join(INFO_FILE))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[tokio::test]
+    async fn idle_connections_are_dropped_before_authenticating() {
+        let dir = tempfile::tempdir().unwrap();
+        let (client, _log_tx) = start_daemon(dir.path()).await;
+
+        let stream = TcpStream::connect(("127.0.0.1", client.port))
+            .await
+            .unwrap();
+        let mut lines = BufReader::new(stream).lines();
+        let closed = tokio::time::timeout(AUTH_TIMEOUT * 2, lines.next_line()).await;
+        assert!(closed.expect("connection closed").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn streams_slot_snapshots_and_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        let (client, log_tx) = start_daemon(dir.path()).await;
+
+        let mut subscription = client.subscribe(true, true).await.unwrap();
+        match subscription.next().await.unwrap() {
+            Some(Event::Slots { slots }) => assert!(slots.is_empty()),
+            other => panic!("expected initial slots, got {other:?}"),
+        }
+
+        log_tx
+            .send(LogLine::new("a", LogSource::Aspire, "hello"))
+            .unwrap();
+        match subscription.next().await.unwrap() {
+            Some(Event::Log(line)) => {
+                assert_eq!(line.slot_name, "a");
+                assert_eq!(line.line, "hello");
+            }
+            other => panic!("expected a log line, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,292 @@
+#[cfg(any(test, feature = "test-util"))]
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+#[cfg(any(test, feature = "test-util"))]
+use std::sync::Mutex;
+
+use chrono::Utc;
+
+use crate::error::{OrchestratorError, Result};
+use crate::models::{CheckState, PrState, PullRequest, ReviewState};
+
+/// Boxed future returned by [`ForgeProvider`] methods.
+pub type ForgeFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// A code host that can open pull requests and report their review/CI state.
+pub trait ForgeProvider: Send + Sync {
+    /// Open a pull request from `head` into `base`. `head` must already be pushed.
+    fn create_pull_request<'a>(
+        &'a self,
+        repo_path: &'a Path,
+        head: &'a str,
+        base: &'a str,
+    ) -> ForgeFuture<'a, PullRequest>;
+
+    /// Fetch the current state of pull request `number`.
+    fn pull_request_status<'a>(
+        &'a self,
+        repo_path: &'a Path,
+        number: u64,
+    ) -> ForgeFuture<'a, PullRequest>;
+}
+
+/// GitHub via the `gh` CLI, using whatever account `gh auth` is logged in as.
+#[derive(Debug, Default)]
+pub struct GhForge;
+
+const GH_VIEW_FIELDS: &str = "number,url,state,isDraft,reviewDecision,statusCheckRollup";
+
+impl ForgeProvider for GhForge {
+    fn create_pull_request<'a>(
+        &'a self,
+        repo_path: &'a Path,
+        head: &'a str,
+        base: &'a str,
+    ) -> ForgeFuture<'a, PullRequest> {
+        Box::pin(async move {
+            let stdout = run_gh(
+                &["pr", "create", "--head", head, "--base", base, "--fill"],
+                repo_path,
+            )
+            .await?;
+            // `gh pr create` prints the new PR's URL as its last line
+            let url = stdout
+                .lines()
+                .rev()
+                .map(str::trim)
+                .find(|l| l.starts_with("http"))
+                .ok_or_else(|| {
+                    OrchestratorError::Forge(format!("gh pr create printed no URL: {stdout}"))
+                })?;
+            let json = run_gh(&["pr", "view", url, "--json", GH_VIEW_FIELDS], repo_path).await?;
+            parse_gh_pr_view(&json)
+        })
+    }
+
+    fn pull_request_status<'a>(
+        &'a self,
+        repo_path: &'a Path,
+        number: u64,
+    ) -> ForgeFuture<'a, PullRequest> {
+        Box::pin(async move {
+            let number = number.to_string();
+            let json = run_gh(
+                &["pr", "view", &number, "--json", GH_VIEW_FIELDS],
+                repo_path,
+            )
+            .await?;
+            parse_gh_pr_view(&json)
+        })
+    }
+}
+
+async fn run_gh(args: &[&str], cwd: &Path) -> Result<String> {
+    let output = tokio::process::Command::new("gh")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .await
+        .map_err(|e| OrchestratorError::Forge(format!("gh CLI not available: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        tracing::warn!("gh {} failed: {stderr}", args.join(" "));
+        return Err(OrchestratorError::Forge(stderr.trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Parse `gh pr view --json number,url,state,isDraft,reviewDecision,statusCheckRollup`.
+fn parse_gh_pr_view(json: &str) -> Result<PullRequest> {
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct GhPr {
+        number: u64,
+        url: String,
+        state: String,
+        #[serde(default)]
+        is_draft: bool,
+        #[serde(default)]
+        review_decision: Option<String>,
+        #[serde(default)]
+        status_check_rollup: Option<Vec<GhCheck>>,
+    }
+
+    /// Either a `CheckRun` (status + conclusion) or a `StatusContext` (state).
+    #[derive(serde::Deserialize)]
+    struct GhCheck {
+        #[serde(default)]
+        status: Option<String>,
+        #[serde(default)]
+        conclusion: Option<String>,
+        #[serde(default)]
+        state: Option<String>,
+    }
+
+    let pr: GhPr = serde_json::from_str(json)
+        .map_err(|e| OrchestratorError::Forge(format!("cannot parse gh output: {e}")))?;
+
+    let state = match pr.state.as_str() {
+        "MERGED" => PrState::Merged,
+        "CLOSED" => PrState::Closed,
+        _ if pr.is_draft => PrState::Draft,
+        _ => PrState::Open,
+    };
+    let review = match pr.review_decision.as_deref() {
+        Some("APPROVED") => ReviewState::Approved,
+        Some("CHANGES_REQUESTED") => ReviewState::ChangesRequested,
+        Some("REVIEW_REQUIRED") => ReviewState::ReviewRequired,
+        _ => ReviewState::None,
+    };
+
+    let mut checks = CheckState::None;
+    for check in pr.status_check_rollup.unwrap_or_default() {
+        let result = match (check.state.as_deref(), check.status.as_deref()) {
+            (Some("SUCCESS"), _) => CheckState::Passing,
+            (Some("PENDING" | "EXPECTED"), _) => CheckState::Pending,
+            (Some(_), _) => CheckState::Failing,
+            (None, Some("COMPLETED")) => match check.conclusion.as_deref() {
+                Some("SUCCESS" | "NEUTRAL" | "SKIPPED") => CheckState::Passing,
+                _ => CheckState::Failing,
+            },
+            (None, _) => CheckState::Pending,
+        };
+        checks = worse_check(checks, result);
+    }
+
+    Ok(PullRequest {
+        number: pr.number,
+        url: pr.url,
+        state,
+        review,
+        checks,
+        checked_at: Some(Utc::now()),
+    })
+}
+
+/// Combine two check results: any failure wins, then pending, then passing.
+fn worse_check(a: CheckState, b: CheckState) -> CheckState {
+    let rank = |c| match c {
+        CheckState::None => 0,
+        CheckState::Passing => 1,
+        CheckState::Pending => 2,
+        CheckState::Failing => 3,
+    };
+    if rank(b) > rank(a) {
+        b
+    } else {
+        a
+    }
+}
+
+/// In-memory forge for tests: numbers PRs sequentially and reports whatever
+/// state was last set with [`FakeForge::set_status`]. Other crates get it
+/// with the `test-util` feature.
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug, Default)]
+pub struct FakeForge {
+    pulls: Mutex<HashMap<u64, PullRequest>>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl FakeForge {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the review/CI state reported for PR `number`.
+    pub fn set_status(&self, number: u64, state: PrState, review: ReviewState, checks: CheckState) {
+        if let Some(pr) = self.pulls.lock().unwrap().get_mut(&number) {
+            pr.state = state;
+            pr.review = review;
+            pr.checks = checks;
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl ForgeProvider for FakeForge {
+    fn create_pull_request<'a>(
+        &'a self,
+        _repo_path: &'a Path,
+        head: &'a str,
+        _base: &'a str,
+    ) -> ForgeFuture<'a, PullRequest> {
+        Box::pin(async move {
+            let mut pulls = self.pulls.lock().unwrap();
+            let number = pulls.len() as u64 + 1;
+            let pr = PullRequest {
+                number,
+                url: format!("https://forge.test/pull/{number}?head={head}"),
+                state: PrState::Open,
+                review: ReviewState::ReviewRequired,
+                checks: CheckState::Pending,
+                checked_at: Some(Utc::now()),
+            };
+            pulls.insert(number, pr.clone());
+            Ok(pr)
+        })
+    }
+
+    fn pull_request_status<'a>(
+        &'a self,
+        _repo_path: &'a Path,
+        number: u64,
+    ) -> ForgeFuture<'a, PullRequest> {
+        Box::pin(async move {
+            let mut pr = self
+                .pulls
+                .lock()
+                .unwrap()
+                .get(&number)
+                .cloned()
+                .ok_or_else(|| OrchestratorError::Forge(format!("no pull request #{number}")))?;
+            pr.checked_at = Some(Utc::now());
+            Ok(pr)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_gh_view_rolls_up_checks() {
+        let json = r#"{
+            "number": 42,
+            "url": "https://github.com/o/r/pull/42",
+            "state": "OPEN",
+            "isDraft": false,
+            "reviewDecision": "CHANGES_REQUESTED",
+            "statusCheckRollup": [
+                {"__typename": "CheckRun", "status": "COMPLETED", "conclusion": "SUCCESS"},
+                {"__typename": "CheckRun", "status": "IN_PROGRESS", "conclusion": ""},
+                {"__typename": "StatusContext", "state": "SUCCESS"}
+            ]
+        }"#;
+        let pr = parse_gh_pr_view(json).unwrap();
+        assert_eq!(pr.number, 42);
+        assert_eq!(pr.state, PrState::Open);
+        assert_eq!(pr.review, ReviewState::ChangesRequested);
+        assert_eq!(pr.checks, CheckState::Pending);
+    }
+
+    #[test]
+    fn parse_gh_view_draft_without_checks() {
+        let json = r#"{"number":7,"url":"u","state":"OPEN","isDraft":true,
+            "reviewDecision":"","statusCheckRollup":[]}"#;
+        let pr = parse_gh_pr_view(json).unwrap();
+        assert_eq!(pr.state, PrState::Draft);
+        assert_eq!(pr.review, ReviewState::None);
+        assert_eq!(pr.checks, CheckState::None);
+
+        let failed = r#"{"number":7,"url":"u","state":"MERGED","statusCheckRollup":[
+            {"status":"COMPLETED","conclusion":"FAILURE"},{"state":"PENDING"}]}"#;
+        let pr = parse_gh_pr_view(failed).unwrap();
+        assert_eq!(pr.state, PrState::Merged);
+        assert_eq!(pr.checks, CheckState::Failing);
+    }
+}
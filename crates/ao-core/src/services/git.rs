@@ -1,10 +1,23 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, Utc};
 use tokio::process::Command;
 
 use crate::error::{OrchestratorError, Result};
+use crate::models::{
+    AheadBehind, CommitSummary, DiffHunk, DiffLine, DiffLineKind, FileChange, FileDiff,
+    GitOperation, GitStatus, RebaseOutcome,
+};
 
 async fn run_git(args: &[&str], working_directory: Option<&Path>) -> Result<String> {
+    Ok(run_git_raw(args, working_directory)
+        .await?
+        .trim()
+        .to_string())
+}
+
+/// Like `run_git`, but keeps leading/trailing whitespace (needed for diff output).
+async fn run_git_raw(args: &[&str], working_directory: Option<&Path>) -> Result<String> {
     let mut cmd = Command::new("git");
     cmd.args(args);
     if let Some(dir) = working_directory {
@@ -23,7 +36,7 @@ async fn run_git(args: &[&str], working_directory: Option<&Path>) -> Result<Stri
             output.status.code().unwrap_or(-1)
         )));
     }
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
 /// Clone a repository. `source` can be a local path or a remote URL —
@@ -34,6 +47,50 @@ pub async fn clone_repo(source: &str, target_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Clone borrowing objects from a local repo via `--reference`.
+pub async fn clone_repo_reference(
+    source: &str,
+    reference: &Path,
+    target_path: &Path,
+) -> Result<()> {
+    let target = target_path.to_string_lossy();
+    let reference = reference.to_string_lossy();
+    run_git(&["clone", "--reference", &reference, source, &target], None).await?;
+    Ok(())
+}
+
+/// Clone a local repo with `--shared`, pointing at its object store instead of copying it.
+pub async fn clone_repo_shared(source: &str, target_path: &Path) -> Result<()> {
+    let target = target_path.to_string_lossy();
+    run_git(&["clone", "--shared", source, &target], None).await?;
+    Ok(())
+}
+
+/// Add a worktree of `repo_path` at `worktree_path` with `branch` checked out,
+/// creating the branch from the current HEAD if `create_new` is set.
+pub async fn worktree_add(
+    repo_path: &Path,
+    worktree_path: &Path,
+    branch: &str,
+    create_new: bool,
+) -> Result<()> {
+    let path = worktree_path.to_string_lossy();
+    if create_new {
+        run_git(&["worktree", "add", "-b", branch, &path], Some(repo_path)).await?;
+    } else {
+        run_git(&["worktree", "add", &path, branch], Some(repo_path)).await?;
+    }
+    Ok(())
+}
+
+/// Remove a worktree (discarding local changes) and prune stale entries.
+pub async fn worktree_remove(repo_path: &Path, worktree_path: &Path) -> Result<()> {
+    let path = worktree_path.to_string_lossy();
+    let removed = run_git(&["worktree", "remove", "--force", &path], Some(repo_path)).await;
+    run_git(&["worktree", "prune"], Some(repo_path)).await?;
+    removed.map(|_| ())
+}
+
 pub async fn checkout(repo_path: &Path, branch: &str, create_new: bool) -> Result<()> {
     if create_new {
         run_git(&["checkout", "-b", branch], Some(repo_path)).await?;
@@ -48,25 +105,164 @@ pub async fn fetch(repo_path: &Path) -> Result<()> {
     Ok(())
 }
 
-pub async fn rebase(repo_path: &Path, target_branch: &str) -> Result<()> {
+/// Rebase onto `origin/<target_branch>`. A rebase that stops on conflicts is
+/// left in progress and reported as `Conflicted` rather than as an error.
+pub async fn rebase(repo_path: &Path, target_branch: &str) -> Result<RebaseOutcome> {
     let target = format!("origin/{target_branch}");
-    run_git(&["rebase", &target], Some(repo_path)).await?;
+    let result = run_git(&["rebase", &target], Some(repo_path)).await;
+    rebase_outcome(repo_path, result).await
+}
+
+/// Continue a stopped rebase after conflicts were resolved and staged.
+pub async fn rebase_continue(repo_path: &Path) -> Result<RebaseOutcome> {
+    // Keep the original commit messages instead of opening an editor
+    let result = run_git(
+        &["-c", "core.editor=true", "rebase", "--continue"],
+        Some(repo_path),
+    )
+    .await;
+    rebase_outcome(repo_path, result).await
+}
+
+/// Drop the commit that stopped the rebase and carry on with the rest.
+pub async fn rebase_skip(repo_path: &Path) -> Result<RebaseOutcome> {
+    let result = run_git(&["rebase", "--skip"], Some(repo_path)).await;
+    rebase_outcome(repo_path, result).await
+}
+
+/// Abort a stopped rebase, restoring the branch to where it was.
+pub async fn rebase_abort(repo_path: &Path) -> Result<()> {
+    run_git(&["rebase", "--abort"], Some(repo_path)).await?;
     Ok(())
 }
 
-pub async fn push(repo_path: &Path, branch: &str, set_upstream: bool) -> Result<()> {
+/// Paths with unresolved merge conflicts.
+pub async fn conflicted_files(repo_path: &Path) -> Result<Vec<String>> {
+    let output = run_git(
+        &[
+            "-c",
+            "core.quotePath=false",
+            "diff",
+            "--name-only",
+            "--diff-filter=U",
+        ],
+        Some(repo_path),
+    )
+    .await?;
+    Ok(output
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Map the result of a rebase command: if a rebase is still in progress
+/// afterwards, it stopped on conflicts.
+async fn rebase_outcome(repo_path: &Path, result: Result<String>) -> Result<RebaseOutcome> {
+    if in_progress_operation(repo_path).await == Some(GitOperation::Rebase) {
+        return Ok(RebaseOutcome::Conflicted(
+            conflicted_files(repo_path).await?,
+        ));
+    }
+    result.map(|_| RebaseOutcome::Completed)
+}
+
+/// Push `branch` to `origin`. `force_with_lease` overwrites the remote branch
+/// only if it still matches our remote-tracking ref (e.g. after a rebase).
+pub async fn push(
+    repo_path: &Path,
+    branch: &str,
+    set_upstream: bool,
+    force_with_lease: bool,
+) -> Result<()> {
+    let mut args = vec!["push"];
     if set_upstream {
-        run_git(&["push", "-u", "origin", branch], Some(repo_path)).await?;
-    } else {
-        run_git(&["push", branch], Some(repo_path)).await?;
+        args.push("-u");
+    }
+    if force_with_lease {
+        args.push("--force-with-lease");
     }
+    args.extend(["origin", branch]);
+    run_git(&args, Some(repo_path)).await?;
     Ok(())
 }
 
+/// Whether the branch's upstream is no longer contained in `HEAD`, e.g. after
+/// a rebase, so pushing has to overwrite it. `false` without an upstream.
+pub async fn upstream_rewritten(repo_path: &Path) -> Result<bool> {
+    if run_git(
+        &["rev-parse", "--verify", "--quiet", "@{u}"],
+        Some(repo_path),
+    )
+    .await
+    .is_err()
+    {
+        return Ok(false);
+    }
+    let status = Command::new("git")
+        .args(["merge-base", "--is-ancestor", "@{u}", "HEAD"])
+        .current_dir(repo_path)
+        .status()
+        .await
+        .map_err(|e| OrchestratorError::Git(format!("failed to run git: {e}")))?;
+    match status.code() {
+        Some(0) => Ok(false),
+        Some(1) => Ok(true),
+        _ => Err(OrchestratorError::Git(format!(
+            "git merge-base --is-ancestor @{{u}} HEAD failed ({status})"
+        ))),
+    }
+}
+
 pub async fn get_current_branch(repo_path: &Path) -> Result<String> {
     run_git(&["rev-parse", "--abbrev-ref", "HEAD"], Some(repo_path)).await
 }
 
+/// The branch `origin/HEAD` points at, i.e. the remote's default branch.
+/// Falls back to `main` or `master` when `origin/HEAD` isn't set, as in a
+/// worktree of a repo that was never cloned.
+pub async fn default_branch(repo_path: &Path) -> Result<String> {
+    if let Ok(head) = run_git(
+        &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"],
+        Some(repo_path),
+    )
+    .await
+    {
+        if let Some(branch) = head.strip_prefix("origin/") {
+            return Ok(branch.to_string());
+        }
+    }
+    for candidate in ["main", "master"] {
+        if branch_exists_or_tracked(repo_path, candidate).await? {
+            return Ok(candidate.to_string());
+        }
+    }
+    Err(OrchestratorError::Git(format!(
+        "no default branch found in {}",
+        repo_path.display()
+    )))
+}
+
+/// URL of `remote` in a working copy.
+pub async fn remote_url(repo_path: &Path, remote: &str) -> Result<String> {
+    run_git(&["remote", "get-url", remote], Some(repo_path)).await
+}
+
+/// Absolute path of the repository a worktree shares its objects with
+/// (the working copy itself for a normal clone).
+pub async fn main_worktree(repo_path: &Path) -> Result<PathBuf> {
+    let common_dir = run_git(
+        &["rev-parse", "--path-format=absolute", "--git-common-dir"],
+        Some(repo_path),
+    )
+    .await?;
+    let common_dir = PathBuf::from(common_dir);
+    Ok(common_dir
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or(common_dir))
+}
+
 pub async fn list_branches(repo_path: &Path) -> Result<Vec<String>> {
     let output = run_git(
         &["branch", "-a", "--format=%(refname:short)"],
@@ -92,3 +288,484 @@ pub async fn branch_exists(repo_path: &Path, branch: &str) -> Result<bool> {
         Err(_) => Ok(false),
     }
 }
+
+/// Whether `branch` exists locally or as `origin/<branch>`. Checking out a
+/// branch that only exists on origin creates a local branch tracking it.
+pub async fn branch_exists_or_tracked(repo_path: &Path, branch: &str) -> Result<bool> {
+    Ok(branch_exists(repo_path, branch).await?
+        || branch_exists(repo_path, &format!("origin/{branch}")).await?)
+}
+
+/// Check `name` against git's branch naming rules (see
+/// `git check-ref-format`), without running git.
+pub fn check_branch_name(name: &str) -> std::result::Result<(), String> {
+    if name.is_empty() {
+        return Err("branch name is required".into());
+    }
+    if name.starts_with('-') || name.starts_with('/') || name.ends_with('/') {
+        return Err("can't start with '-' or start or end with '/'".into());
+    }
+    if name.ends_with('.') || name.ends_with(".lock") {
+        return Err("can't end with '.' or '.lock'".into());
+    }
+    if name.contains("..") || name.contains("//") || name.contains("@{") || name == "@" {
+        return Err("can't contain '..', '//' or '@{'".into());
+    }
+    if name.split('/').any(|part| part.starts_with('.')) {
+        return Err("no part can start with '.'".into());
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| c.is_ascii_control() || c.is_whitespace() || "~^:?*[\\".contains(*c))
+    {
+        return Err(format!("'{c}' is not allowed"));
+    }
+    Ok(())
+}
+
+/// Find the commit where HEAD forked from `other`.
+pub async fn merge_base(repo_path: &Path, other: &str) -> Result<String> {
+    run_git(&["merge-base", "HEAD", other], Some(repo_path)).await
+}
+
+/// Diff the working tree (including staged changes) against `base_ref`.
+pub async fn diff(repo_path: &Path, base_ref: &str) -> Result<Vec<FileDiff>> {
+    let output = run_git_raw(
+        &[
+            "-c",
+            "core.quotePath=false",
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            "-M",
+            base_ref,
+        ],
+        Some(repo_path),
+    )
+    .await?;
+    Ok(parse_unified_diff(&output))
+}
+
+/// Parse `git diff` unified output into per-file hunks.
+fn parse_unified_diff(output: &str) -> Vec<FileDiff> {
+    let mut files: Vec<FileDiff> = Vec::new();
+    let mut old_lineno = 0;
+    let mut new_lineno = 0;
+
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            let path = header
+                .rsplit_once(" b/")
+                .map(|(_, b)| b.to_string())
+                .unwrap_or_else(|| header.to_string());
+            files.push(FileDiff {
+                path,
+                old_path: None,
+                change: FileChange::Modified,
+                binary: false,
+                hunks: Vec::new(),
+            });
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+
+        if let Some(hunk) = file.hunks.last_mut() {
+            let (kind, old, new) = match line.as_bytes().first() {
+                Some(b' ') => (DiffLineKind::Context, Some(old_lineno), Some(new_lineno)),
+                Some(b'+') => (DiffLineKind::Added, None, Some(new_lineno)),
+                Some(b'-') => (DiffLineKind::Removed, Some(old_lineno), None),
+                // "\ No newline at end of file"
+                Some(b'\\') => continue,
+                _ => (DiffLineKind::Context, None, None),
+            };
+            if old.is_some() || new.is_some() {
+                old_lineno += u32::from(old.is_some());
+                new_lineno += u32::from(new.is_some());
+                hunk.lines.push(DiffLine {
+                    kind,
+                    content: line[1..].to_string(),
+                    old_lineno: old,
+                    new_lineno: new,
+                });
+                continue;
+            }
+        }
+
+        if line.starts_with("@@") {
+            if let Some(hunk) = parse_hunk_header(line) {
+                old_lineno = hunk.old_start;
+                new_lineno = hunk.new_start;
+                file.hunks.push(hunk);
+            }
+        } else if line.starts_with("new file mode") {
+            file.change = FileChange::Added;
+        } else if line.starts_with("deleted file mode") {
+            file.change = FileChange::Deleted;
+        } else if let Some(from) = line.strip_prefix("rename from ") {
+            file.change = FileChange::Renamed;
+            file.old_path = Some(from.to_string());
+        } else if let Some(to) = line.strip_prefix("rename to ") {
+            file.path = to.to_string();
+        } else if line.starts_with("Binary files ") {
+            file.binary = true;
+        } else if let Some(path) = line.strip_prefix("+++ b/") {
+            file.path = path.to_string();
+        }
+    }
+    files
+}
+
+/// Parse `@@ -a,b +c,d @@ ...`; omitted counts default to 1.
+fn parse_hunk_header(line: &str) -> Option<DiffHunk> {
+    let ranges = line.strip_prefix("@@ ")?.split(" @@").next()?;
+    let (old, new) = ranges.split_once(' ')?;
+    let parse_range = |r: &str| -> Option<(u32, u32)> {
+        match r.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((r.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_lines) = parse_range(old.strip_prefix('-')?)?;
+    let (new_start, new_lines) = parse_range(new.strip_prefix('+')?)?;
+    Some(DiffHunk {
+        header: line.to_string(),
+        old_start,
+        old_lines,
+        new_start,
+        new_lines,
+        lines: Vec::new(),
+    })
+}
+
+/// Add `patterns` to the repo's `info/exclude` so git ignores them without
+/// touching the tracked `.gitignore`. Patterns already listed are skipped.
+pub async fn exclude(repo_path: &Path, patterns: &[&str]) -> Result<()> {
+    let exclude_path = repo_path.join(
+        run_git(
+            &["rev-parse", "--git-path", "info/exclude"],
+            Some(repo_path),
+        )
+        .await?,
+    );
+    let existing = match tokio::fs::read_to_string(&exclude_path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let missing: Vec<&str> = patterns
+        .iter()
+        .copied()
+        .filter(|p| !existing.lines().any(|line| line.trim() == *p))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let mut content = existing;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for pattern in missing {
+        content.push_str(pattern);
+        content.push('\n');
+    }
+    if let Some(parent) = exclude_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&exclude_path, content).await?;
+    Ok(())
+}
+
+/// Collect a structured status summary for a clone.
+/// `base_branch` is compared as `origin/<base_branch>`.
+pub async fn status(repo_path: &Path, base_branch: &str) -> Result<GitStatus> {
+    let porcelain = run_git(&["status", "--porcelain=v2", "--branch"], Some(repo_path)).await?;
+    let mut status = parse_status_porcelain(&porcelain);
+
+    let base_ref = format!("origin/{base_branch}");
+    let range = format!("HEAD...{base_ref}");
+    if let Ok(counts) = run_git(
+        &["rev-list", "--left-right", "--count", &range],
+        Some(repo_path),
+    )
+    .await
+    {
+        status.vs_base = parse_left_right_count(&counts);
+    }
+
+    if let Ok(log) = run_git(
+        &["log", "-1", "--format=%h%x1f%s%x1f%an%x1f%cI"],
+        Some(repo_path),
+    )
+    .await
+    {
+        status.last_commit = parse_last_commit(&log);
+    }
+
+    status.operation = in_progress_operation(repo_path).await;
+    Ok(status)
+}
+
+/// Parse `git status --porcelain=v2 --branch` output.
+fn parse_status_porcelain(output: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    for line in output.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            status.branch = head.to_string();
+        } else if let Some(upstream) = line.strip_prefix("# branch.upstream ") {
+            status.upstream = Some(upstream.to_string());
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            let mut parts = ab.split_whitespace();
+            let ahead = parts
+                .next()
+                .and_then(|a| a.trim_start_matches('+').parse().ok());
+            let behind = parts
+                .next()
+                .and_then(|b| b.trim_start_matches('-').parse().ok());
+            if let (Some(ahead), Some(behind)) = (ahead, behind) {
+                status.vs_upstream = Some(AheadBehind { ahead, behind });
+            }
+        } else if line.starts_with("1 ") || line.starts_with("2 ") {
+            let xy = line.as_bytes().get(2..4).unwrap_or(b"..");
+            if xy[0] != b'.' {
+                status.staged += 1;
+            }
+            if xy[1] != b'.' {
+                status.unstaged += 1;
+            }
+        } else if line.starts_with("u ") {
+            status.conflicted += 1;
+        } else if line.starts_with("? ") {
+            status.untracked += 1;
+        }
+    }
+    status
+}
+
+/// Parse `git rev-list --left-right --count A...B` output ("<left>\t<right>").
+fn parse_left_right_count(output: &str) -> Option<AheadBehind> {
+    let mut parts = output.split_whitespace();
+    let ahead = parts.next()?.parse().ok()?;
+    let behind = parts.next()?.parse().ok()?;
+    Some(AheadBehind { ahead, behind })
+}
+
+/// Parse `git log -1 --format=%h%x1f%s%x1f%an%x1f%cI` output.
+fn parse_last_commit(output: &str) -> Option<CommitSummary> {
+    let mut fields = output.split('\x1f');
+    let hash = fields.next()?.to_string();
+    let subject = fields.next()?.to_string();
+    let author = fields.next()?.to_string();
+    let time = DateTime::parse_from_rfc3339(fields.next()?.trim())
+        .ok()?
+        .with_timezone(&Utc);
+    Some(CommitSummary {
+        hash,
+        subject,
+        author,
+        time,
+    })
+}
+
+/// Resolve the clone's git directory (handles worktrees and `.git` files).
+pub(crate) async fn git_dir(repo_path: &Path) -> Result<PathBuf> {
+    let dir = run_git(&["rev-parse", "--git-dir"], Some(repo_path)).await?;
+    let dir = PathBuf::from(dir);
+    Ok(if dir.is_absolute() {
+        dir
+    } else {
+        repo_path.join(dir)
+    })
+}
+
+/// Detect a rebase, merge, or cherry-pick left in progress.
+pub async fn in_progress_operation(repo_path: &Path) -> Option<GitOperation> {
+    let git_dir = git_dir(repo_path).await.ok()?;
+    if git_dir.join("rebase-merge").exists() || git_dir.join("rebase-apply").exists() {
+        Some(GitOperation::Rebase)
+    } else if git_dir.join("MERGE_HEAD").exists() {
+        Some(GitOperation::Merge)
+    } else if git_dir.join("CHERRY_PICK_HEAD").exists() {
+        Some(GitOperation::CherryPick)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn branch_names_follow_git_rules() {
+        for ok in ["feature/login", "fix-123", "user/a.b"] {
+            assert_eq!(check_branch_name(ok), Ok(()), "{ok}");
+        }
+        for bad in ["", "-x", "a..b", "a b", "a/", "x.lock", "a/.b", "a:b", "@"] {
+            assert!(check_branch_name(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn parse_porcelain_counts_changes() {
+        let output = "\
+# branch.oid 1234567890abcdef
+# branch.head feature/x
+# branch.upstream origin/feature/x
+# branch.ab +2 -1
+1 M. N... 100644 100644 100644 abc def src/staged.rs
+1 .M N... 100644 100644 100644 abc def src/modified.rs
+1 MM N... 100644 100644 100644 abc def src/both.rs
+2 R. N... 100644 100644 100644 abc def R100 new.rs\told.rs
+u UU N... 100644 100644 100644 100644 a b c src/conflict.rs
+? notes.txt
+? scratch/";
+        let status = parse_status_porcelain(output);
+        assert_eq!(status.branch, "feature/x");
+        assert_eq!(status.upstream.as_deref(), Some("origin/feature/x"));
+        assert_eq!(
+            status.vs_upstream,
+            Some(AheadBehind {
+                ahead: 2,
+                behind: 1
+            })
+        );
+        assert_eq!(status.staged, 3);
+        assert_eq!(status.unstaged, 2);
+        assert_eq!(status.conflicted, 1);
+        assert_eq!(status.untracked, 2);
+        assert!(status.is_dirty());
+        assert!(status.has_conflicts());
+    }
+
+    #[test]
+    fn parse_porcelain_clean_without_upstream() {
+        let output = "# branch.oid abc\n# branch.head main";
+        let status = parse_status_porcelain(output);
+        assert_eq!(status.branch, "main");
+        assert!(status.upstream.is_none());
+        assert!(status.vs_upstream.is_none());
+        assert!(!status.is_dirty());
+    }
+
+    #[test]
+    fn parse_diff_hunks_and_line_numbers() {
+        let output = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@ mod tests
+ use std::io;
+-fn old() {}
++fn new() {}
++fn extra() {}
+ 
+@@ -10 +11,0 @@
+-removed
+\\ No newline at end of file
+diff --git a/docs/new.md b/docs/new.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/docs/new.md
+@@ -0,0 +1 @@
++hello
+";
+        let files = parse_unified_diff(output);
+        assert_eq!(files.len(), 2);
+
+        let lib = &files[0];
+        assert_eq!(lib.path, "src/lib.rs");
+        assert_eq!(lib.change, FileChange::Modified);
+        assert_eq!(lib.hunks.len(), 2);
+        assert_eq!(lib.additions(), 2);
+        assert_eq!(lib.deletions(), 2);
+
+        let first = &lib.hunks[0];
+        assert_eq!((first.old_start, first.old_lines), (1, 3));
+        assert_eq!((first.new_start, first.new_lines), (1, 4));
+        assert_eq!(first.lines.len(), 5);
+        assert_eq!(first.lines[1].kind, DiffLineKind::Removed);
+        assert_eq!(first.lines[1].old_lineno, Some(2));
+        assert_eq!(first.lines[2].content, "fn new() {}");
+        assert_eq!(first.lines[3].new_lineno, Some(3));
+        assert_eq!(first.lines[4].content, "");
+        assert_eq!(first.lines[4].old_lineno, Some(3));
+        assert_eq!(first.lines[4].new_lineno, Some(4));
+
+        let second = &lib.hunks[1];
+        assert_eq!((second.old_start, second.old_lines), (10, 1));
+        assert_eq!(second.lines.len(), 1);
+
+        let new = &files[1];
+        assert_eq!(new.path, "docs/new.md");
+        assert_eq!(new.change, FileChange::Added);
+        assert_eq!(new.hunks[0].lines[0].new_lineno, Some(1));
+    }
+
+    #[test]
+    fn parse_diff_rename_and_binary() {
+        let output = "\
+diff --git a/old name.rs b/new name.rs
+similarity index 100%
+rename from old name.rs
+rename to new name.rs
+diff --git a/logo.png b/logo.png
+index 1111111..2222222 100644
+Binary files a/logo.png and b/logo.png differ
+";
+        let files = parse_unified_diff(output);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].change, FileChange::Renamed);
+        assert_eq!(files[0].path, "new name.rs");
+        assert_eq!(files[0].old_path.as_deref(), Some("old name.rs"));
+        assert!(files[0].hunks.is_empty());
+        assert_eq!(files[1].path, "logo.png");
+        assert!(files[1].binary);
+    }
+
+    #[test]
+    fn parse_rev_list_counts() {
+        assert_eq!(
+            parse_left_right_count("3\t5"),
+            Some(AheadBehind {
+                ahead: 3,
+                behind: 5
+            })
+        );
+        assert_eq!(parse_left_right_count(""), None);
+    }
+
+    #[test]
+    fn parse_commit_summary() {
+        let commit =
+            parse_last_commit("abc1234\x1fFix login\x1fJane Doe\x1f2025-06-15T10:30:00+02:00")
+                .unwrap();
+        assert_eq!(commit.hash, "abc1234");
+        assert_eq!(commit.subject, "Fix login");
+        assert_eq!(commit.author, "Jane Doe");
+        assert_eq!(commit.time.to_rfc3339(), "2025-06-15T08:30:00+00:00");
+        assert!(parse_last_commit("").is_none());
+    }
+
+    #[tokio::test]
+    async fn excluded_orchestrator_files_leave_the_clone_clean() {
+        let dir = tempfile::tempdir().unwrap();
+        run_git(&["init", "-q"], Some(dir.path())).await.unwrap();
+        std::fs::write(dir.path().join(".agent-host.json"), "{}").unwrap();
+        std::fs::write(dir.path().join(".aspire-orchestrator-agent.log"), "x").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "x").unwrap();
+
+        let patterns = crate::models::slot::ORCHESTRATOR_FILES;
+        exclude(dir.path(), patterns).await.unwrap();
+        exclude(dir.path(), patterns).await.unwrap();
+        let excludes = std::fs::read_to_string(dir.path().join(".git/info/exclude")).unwrap();
+        assert_eq!(excludes.matches(".agent-host.json").count(), 1);
+
+        let status = status(dir.path(), "main").await.unwrap();
+        assert_eq!(status.untracked, 1);
+    }
+}
@@ -0,0 +1,548 @@
+//! Recognises the log formats .NET and Aspire write, to find a line's real
+//! severity rather than guessing from words like "error" in its text.
+//!
+//! Understood formats:
+//! - Microsoft.Extensions.Logging console output: an `info:`, `warn:`,
+//!   `fail:`, `crit:`, `dbug:` or `trce:` header naming the category and
+//!   event ID, followed by indented message and exception lines.
+//! - JSON console logs, from the .NET JSON formatter or Serilog's compact
+//!   formatter.
+//! - `dotnet build` and MSBuild diagnostics, such as
+//!   `Foo.cs(12,5): error CS1002: ; expected [Foo.csproj]`, and build
+//!   summaries.
+//!
+//! Anything else is plain text. Plain Aspire output is classified by a
+//! leading level marker such as `error:` or `[WRN]`. Plain agent output is
+//! always informational, since an agent talking about errors isn't one.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::log_tailer::LogSource;
+
+/// How serious a log line is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Severity {
+    /// Whether this is at least as serious as `min`.
+    pub fn is_at_least(self, min: Severity) -> bool {
+        self.rank() >= min.rank()
+    }
+
+    fn rank(self) -> u8 {
+        match self {
+            Severity::Debug => 0,
+            Severity::Info => 1,
+            Severity::Warn => 2,
+            Severity::Error => 3,
+        }
+    }
+}
+
+/// The format a line was recognised as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    Plain,
+    /// Microsoft.Extensions.Logging console output.
+    Console,
+    Json,
+    /// A `dotnet build` or MSBuild diagnostic or summary.
+    Build,
+}
+
+/// A compiler or MSBuild diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// The diagnostic ID, e.g. `CS1002` or `MSB3027`.
+    pub code: String,
+    /// The file it was reported in, or the tool (`CSC`) for errors without one.
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    /// The project being built, from the trailing `[...]`.
+    pub project: Option<String>,
+}
+
+/// The structured fields recognised in one log line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedLine {
+    pub severity: Severity,
+    pub format: LogFormat,
+    /// The logger category, e.g. `Microsoft.Hosting.Lifetime`.
+    pub category: Option<String>,
+    pub event_id: Option<i64>,
+    pub diagnostic: Option<Diagnostic>,
+    /// The message without level, category or location prefixes.
+    pub message: String,
+    /// Set for a line that continues the entry above it, such as the
+    /// message under a console log header or an exception's stack trace.
+    pub continuation: bool,
+}
+
+impl ParsedLine {
+    fn plain(severity: Severity, message: &str) -> Self {
+        Self {
+            severity,
+            format: LogFormat::Plain,
+            category: None,
+            event_id: None,
+            diagnostic: None,
+            message: message.to_string(),
+            continuation: false,
+        }
+    }
+
+    /// For a line reporting a finished build, such as `Build succeeded.` or
+    /// `Build FAILED.`, whether the build succeeded.
+    pub fn build_succeeded(&self) -> Option<bool> {
+        if self.format != LogFormat::Build || self.diagnostic.is_some() {
+            return None;
+        }
+        let caps = BUILD_RESULT_RE.captures(&self.message)?;
+        Some(caps[1].eq_ignore_ascii_case("succeeded"))
+    }
+}
+
+static ANSI_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-_]").unwrap()
+});
+
+static CONSOLE_HEADER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:[\d\-/:.,TZ+ ]+\s)?(trce|dbug|info|warn|fail|crit): ([^\s\[]+)\[(-?\d+)\](?:\s+(.*))?$",
+    )
+    .unwrap()
+});
+
+static DIAGNOSTIC_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?:([^\s(:][^(]*?)(?:\((\d+)(?:,(\d+))?(?:,\d+,\d+)?\))?\s*:\s*)?(error|warning) ([A-Za-z]+\d+)\s*:\s*(.*?)(?:\s+\[([^\]]+)\])?\s*$",
+    )
+    .unwrap()
+});
+
+static BUILD_COUNT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(\d+) (Warning|Error)\(s\)\s*$").unwrap());
+
+static BUILD_RESULT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^\s*(?:the )?build (succeeded|failed)\b").unwrap());
+
+static LEVEL_MARKER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^\s*(?:\[(?:[^\]]*\s)?(err|error|ftl|fatal|crit|critical|wrn|warn|warning|inf|info|dbg|debug|vrb|trc|trace)\]|(error|fatal|panic|critical|warning|warn|debug|trace)\s*[:!\]])",
+    )
+    .unwrap()
+});
+
+static EXCEPTION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?:Unhandled exception\b|(?:[A-Za-z_]\w*\.)*[A-Za-z_]\w*Exception(?::|$))")
+        .unwrap()
+});
+
+/// Parses the lines of one stream, such as a slot's Aspire output, in
+/// order. It keeps the entry being read so that continuation lines get the
+/// severity and category of their header.
+#[derive(Debug, Default)]
+pub struct LogParser {
+    /// The last line that started an entry which indented lines continue.
+    open: Option<ParsedLine>,
+}
+
+impl LogParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the next line of the stream.
+    pub fn parse(&mut self, source: LogSource, line: &str) -> ParsedLine {
+        let stripped = ANSI_RE.replace_all(line, "");
+        let text = stripped.as_ref();
+
+        if let Some(open) = &self.open {
+            if text.starts_with(char::is_whitespace)
+                && !text.trim().is_empty()
+                && !DIAGNOSTIC_RE.is_match(text)
+            {
+                return ParsedLine {
+                    message: text.trim().to_string(),
+                    continuation: true,
+                    diagnostic: None,
+                    ..open.clone()
+                };
+            }
+        }
+
+        let parsed = parse_entry(source, text);
+        let opens = match parsed.format {
+            LogFormat::Console => true,
+            // An exception message above its stack trace
+            LogFormat::Plain => parsed.severity.is_at_least(Severity::Warn),
+            LogFormat::Json | LogFormat::Build => false,
+        };
+        self.open = opens.then(|| parsed.clone());
+        parsed
+    }
+}
+
+/// Parse a line on its own, without the lines before it.
+pub fn parse_line(source: LogSource, line: &str) -> ParsedLine {
+    LogParser::new().parse(source, line)
+}
+
+/// Parse a line that starts a new entry.
+fn parse_entry(source: LogSource, text: &str) -> ParsedLine {
+    if let Some(parsed) = parse_diagnostic(text) {
+        return parsed;
+    }
+    if let Some(parsed) = parse_build_summary(text) {
+        return parsed;
+    }
+    if let Some(parsed) = parse_json(text) {
+        return parsed;
+    }
+    if let Some(caps) = CONSOLE_HEADER_RE.captures(text) {
+        return ParsedLine {
+            severity: console_level(&caps[1]),
+            format: LogFormat::Console,
+            category: Some(caps[2].to_string()),
+            event_id: caps[3].parse().ok(),
+            diagnostic: None,
+            message: caps.get(4).map_or("", |m| m.as_str()).to_string(),
+            continuation: false,
+        };
+    }
+
+    let severity = match source {
+        LogSource::Aspire | LogSource::Telemetry => plain_severity(text),
+        LogSource::Agent => Severity::Info,
+    };
+    ParsedLine::plain(severity, text.trim())
+}
+
+fn console_level(level: &str) -> Severity {
+    match level {
+        "fail" | "crit" => Severity::Error,
+        "warn" => Severity::Warn,
+        "info" => Severity::Info,
+        _ => Severity::Debug,
+    }
+}
+
+fn parse_diagnostic(text: &str) -> Option<ParsedLine> {
+    let caps = DIAGNOSTIC_RE.captures(text)?;
+    let severity = match &caps[4] {
+        "error" => Severity::Error,
+        _ => Severity::Warn,
+    };
+    Some(ParsedLine {
+        severity,
+        format: LogFormat::Build,
+        category: None,
+        event_id: None,
+        diagnostic: Some(Diagnostic {
+            code: caps[5].to_string(),
+            file: caps.get(1).map(|m| m.as_str().trim().to_string()),
+            line: caps.get(2).and_then(|m| m.as_str().parse().ok()),
+            column: caps.get(3).and_then(|m| m.as_str().parse().ok()),
+            project: caps.get(7).map(|m| m.as_str().to_string()),
+        }),
+        message: caps[6].to_string(),
+        continuation: false,
+    })
+}
+
+/// `Build succeeded.`, `Build FAILED.` (or `dotnet run`'s `The build
+/// failed.`), and the `N Error(s)` counts under them. A count of zero is good news, not an error.
+fn parse_build_summary(text: &str) -> Option<ParsedLine> {
+    let severity = if let Some(caps) = BUILD_COUNT_RE.captures(text) {
+        match (&caps[1], &caps[2]) {
+            ("0", _) => Severity::Info,
+            (_, "Error") => Severity::Error,
+            _ => Severity::Warn,
+        }
+    } else {
+        let caps = BUILD_RESULT_RE.captures(text)?;
+        if caps[1].eq_ignore_ascii_case("failed") {
+            Severity::Error
+        } else {
+            Severity::Info
+        }
+    };
+    Some(ParsedLine {
+        format: LogFormat::Build,
+        ..ParsedLine::plain(severity, text.trim())
+    })
+}
+
+/// A JSON object with a recognisable level field.
+fn parse_json(text: &str) -> Option<ParsedLine> {
+    let text = text.trim();
+    if !(text.starts_with('{') && text.ends_with('}')) {
+        return None;
+    }
+    let Value::Object(fields) = serde_json::from_str(text).ok()? else {
+        return None;
+    };
+    let string = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| fields.get(*k).and_then(Value::as_str))
+            .map(str::to_string)
+    };
+
+    let severity = match string(&["LogLevel", "logLevel", "Level", "level", "@l", "severity"]) {
+        Some(level) => json_level(&level)?,
+        // Serilog's compact format leaves out the level of information events
+        None if fields.contains_key("@t") => Severity::Info,
+        None => return None,
+    };
+    let event_id = fields
+        .get("EventId")
+        .and_then(|id| id.as_i64().or_else(|| id.get("Id")?.as_i64()));
+    Some(ParsedLine {
+        severity,
+        format: LogFormat::Json,
+        category: string(&["Category", "category", "SourceContext", "logger"]),
+        event_id,
+        diagnostic: None,
+        message: string(&["Message", "message", "msg", "@m", "@mt"]).unwrap_or_default(),
+        continuation: false,
+    })
+}
+
+fn json_level(level: &str) -> Option<Severity> {
+    Some(match level.to_ascii_lowercase().as_str() {
+        "critical" | "crit" | "fatal" | "error" | "fail" => Severity::Error,
+        "warning" | "warn" => Severity::Warn,
+        "information" | "info" => Severity::Info,
+        "debug" | "dbug" | "trace" | "trce" | "verbose" => Severity::Debug,
+        _ => return None,
+    })
+}
+
+/// The severity of plain process output, from a level marker at its start.
+fn plain_severity(text: &str) -> Severity {
+    if EXCEPTION_RE.is_match(text) {
+        return Severity::Error;
+    }
+    let Some(caps) = LEVEL_MARKER_RE.captures(text) else {
+        return Severity::Info;
+    };
+    let marker = caps.get(1).or(caps.get(2)).map_or("", |m| m.as_str());
+    match marker.to_ascii_lowercase().as_str() {
+        "err" | "error" | "ftl" | "fatal" | "crit" | "critical" | "panic" => Severity::Error,
+        "wrn" | "warn" | "warning" => Severity::Warn,
+        "dbg" | "debug" | "vrb" | "trc" | "trace" => Severity::Debug,
+        _ => Severity::Info,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn severity(source: LogSource, line: &str) -> Severity {
+        parse_line(source, line).severity
+    }
+
+    #[test]
+    fn console_entries_carry_level_to_their_lines() {
+        let mut parser = LogParser::new();
+        let mut parse = |line| parser.parse(LogSource::Aspire, line);
+
+        let header = parse("fail: Microsoft.AspNetCore.Server.Kestrel[13]");
+        assert_eq!(header.severity, Severity::Error);
+        assert_eq!(header.format, LogFormat::Console);
+        assert_eq!(
+            header.category.as_deref(),
+            Some("Microsoft.AspNetCore.Server.Kestrel")
+        );
+        assert_eq!(header.event_id, Some(13));
+
+        let message = parse("      Connection id \"0HN1\": An unhandled exception was thrown");
+        assert!(message.continuation);
+        assert_eq!(message.severity, Severity::Error);
+        assert_eq!(message.category, header.category);
+        assert_eq!(
+            message.message,
+            "Connection id \"0HN1\": An unhandled exception was thrown"
+        );
+        assert!(parse("   at Program.<Main>$(String[] args)").continuation);
+
+        let next =
+            parse("info: Microsoft.Hosting.Lifetime[14] Now listening on: http://localhost:5000");
+        assert!(!next.continuation);
+        assert_eq!(next.severity, Severity::Info);
+        assert_eq!(next.message, "Now listening on: http://localhost:5000");
+
+        // Unindented text ends the entry
+        let after = parse("Building...");
+        assert!(!after.continuation);
+        assert_eq!(after.format, LogFormat::Plain);
+    }
+
+    #[test]
+    fn build_output_is_classified_by_its_diagnostics() {
+        let parsed = parse_line(
+            LogSource::Agent,
+            "/src/Api/Program.cs(12,5): error CS1002: ; expected [/src/Api/Api.csproj]",
+        );
+        assert_eq!(parsed.severity, Severity::Error);
+        assert_eq!(parsed.message, "; expected");
+        assert_eq!(
+            parsed.diagnostic,
+            Some(Diagnostic {
+                code: "CS1002".into(),
+                file: Some("/src/Api/Program.cs".into()),
+                line: Some(12),
+                column: Some(5),
+                project: Some("/src/Api/Api.csproj".into()),
+            })
+        );
+
+        let warning = parse_line(
+            LogSource::Aspire,
+            "  CSC : warning CS8618: Non-nullable property 'Name' must contain a non-null value",
+        );
+        assert_eq!(warning.severity, Severity::Warn);
+        assert_eq!(warning.diagnostic.unwrap().file.as_deref(), Some("CSC"));
+
+        assert_eq!(
+            severity(LogSource::Aspire, "    0 Error(s)"),
+            Severity::Info
+        );
+        assert_eq!(
+            severity(LogSource::Aspire, "    2 Warning(s)"),
+            Severity::Warn
+        );
+        assert_eq!(
+            severity(LogSource::Aspire, "    1 Error(s)"),
+            Severity::Error
+        );
+        assert_eq!(
+            severity(LogSource::Aspire, "Build FAILED."),
+            Severity::Error
+        );
+        assert_eq!(
+            severity(LogSource::Aspire, "Build succeeded with 0 errors."),
+            Severity::Info
+        );
+
+        let outcome = |line| parse_line(LogSource::Aspire, line).build_succeeded();
+        assert_eq!(outcome("Build succeeded."), Some(true));
+        assert_eq!(outcome("Build FAILED."), Some(false));
+        assert_eq!(
+            outcome("The build failed. Fix the build errors and run again."),
+            Some(false)
+        );
+        assert_eq!(outcome("    0 Error(s)"), None);
+        assert_eq!(outcome("Building the project..."), None);
+    }
+
+    #[test]
+    fn json_logs_use_their_level_field() {
+        let parsed = parse_line(
+            LogSource::Aspire,
+            r#"{"EventId":3,"LogLevel":"Warning","Category":"Api.Orders","Message":"Slow query"}"#,
+        );
+        assert_eq!(parsed.severity, Severity::Warn);
+        assert_eq!(parsed.format, LogFormat::Json);
+        assert_eq!(parsed.category.as_deref(), Some("Api.Orders"));
+        assert_eq!(parsed.event_id, Some(3));
+        assert_eq!(parsed.message, "Slow query");
+
+        let compact = r#"{"@t":"2026-03-14T09:00:00Z","@mt":"Order {Id} has no errors","Id":7}"#;
+        assert_eq!(severity(LogSource::Aspire, compact), Severity::Info);
+        assert_eq!(
+            severity(
+                LogSource::Aspire,
+                r#"{"@t":"2026-03-14T09:00:00Z","@l":"Fatal"}"#
+            ),
+            Severity::Error
+        );
+        // Not a log event, so plain text
+        assert_eq!(
+            parse_line(LogSource::Aspire, r#"{"errors": 0}"#).format,
+            LogFormat::Plain
+        );
+    }
+
+    #[test]
+    fn plain_text_needs_a_level_marker() {
+        assert_eq!(
+            severity(LogSource::Aspire, "error: port 5000 in use"),
+            Severity::Error
+        );
+        assert_eq!(
+            severity(LogSource::Aspire, "[10:00:01 WRN] Retrying"),
+            Severity::Warn
+        );
+        assert_eq!(
+            severity(
+                LogSource::Aspire,
+                "Unhandled exception. System.InvalidOperationException: boom"
+            ),
+            Severity::Error
+        );
+        assert_eq!(
+            severity(LogSource::Aspire, "Restored 3 projects with no errors"),
+            Severity::Info
+        );
+        assert_eq!(
+            severity(LogSource::Aspire, "Error handling middleware registered"),
+            Severity::Info
+        );
+        // Agents discussing errors aren't reporting them
+        assert_eq!(
+            severity(LogSource::Agent, "error: I'll fix the failing test next"),
+            Severity::Info
+        );
+    }
+
+    #[test]
+    fn exception_stack_traces_follow_their_message() {
+        let mut parser = LogParser::new();
+        parser.parse(
+            LogSource::Aspire,
+            "System.IO.IOException: The file is locked",
+        );
+        let frame = parser.parse(LogSource::Aspire, "   at System.IO.File.Open()");
+        assert!(frame.continuation);
+        assert_eq!(frame.severity, Severity::Error);
+
+        parser.parse(LogSource::Aspire, "Starting");
+        assert!(
+            !parser
+                .parse(LogSource::Aspire, "  indented info")
+                .continuation
+        );
+    }
+
+    #[test]
+    fn escape_sequences_are_ignored() {
+        let parsed = parse_line(
+            LogSource::Agent,
+            "\x1b[41m\x1b[30mfail\x1b[39m\x1b[22m\x1b[49m: Api.Program[0]",
+        );
+        assert_eq!(parsed.severity, Severity::Error);
+        assert_eq!(parsed.category.as_deref(), Some("Api.Program"));
+    }
+
+    #[test]
+    fn severity_ordering() {
+        assert!(Severity::Error.is_at_least(Severity::Warn));
+        assert!(Severity::Warn.is_at_least(Severity::Warn));
+        assert!(!Severity::Info.is_at_least(Severity::Warn));
+        assert!(Severity::Debug.is_at_least(Severity::Debug));
+    }
+}
@@ -0,0 +1,658 @@
+//! Persistent, searchable history of every slot's log lines.
+//!
+//! Lines are appended to JSON-lines segment files under `.slots/logs/`. A
+//! segment is closed once it reaches [`SEGMENT_BYTES`], and an index of its
+//! time range and slots is written beside it so searches can skip it. Old
+//! segments are deleted according to a [`RetentionPolicy`].
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+pub use super::log_parser::Severity;
+use super::log_parser::{LogParser, ParsedLine};
+use super::log_tailer::{LogLine, LogSource};
+use crate::error::{OrchestratorError, Result};
+
+/// Size at which the current segment is closed and a new one started.
+pub const SEGMENT_BYTES: u64 = 4 * 1024 * 1024;
+
+const SEGMENT_EXT: &str = "jsonl";
+const INDEX_EXT: &str = "idx.json";
+
+/// One stored log line. Short field names keep segment files compact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogRecord {
+    #[serde(rename = "t", with = "chrono::serde::ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    #[serde(rename = "s")]
+    pub slot_name: String,
+    #[serde(rename = "o")]
+    pub source: LogSource,
+    #[serde(rename = "v")]
+    pub severity: Severity,
+    /// The logger category, for lines in a .NET log format.
+    #[serde(rename = "c", default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(rename = "x")]
+    pub text: String,
+}
+
+impl LogRecord {
+    /// A record of `line`, with the fields `parsed` from it.
+    pub fn from_line(line: &LogLine, parsed: ParsedLine) -> Self {
+        Self {
+            timestamp: line.timestamp,
+            slot_name: line.slot_name.clone(),
+            source: line.source,
+            severity: parsed.severity,
+            category: parsed.category,
+            text: line.line.clone(),
+        }
+    }
+}
+
+/// How much log history to keep. The oldest segments are deleted first.
+#[derive(Debug, Clone, Copy)]
+pub struct RetentionPolicy {
+    /// Total size of all segments.
+    pub max_bytes: u64,
+    /// Segments whose newest line is older than this are deleted.
+    pub max_age: Duration,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_bytes: 256 * 1024 * 1024,
+            max_age: Duration::days(14),
+        }
+    }
+}
+
+/// What a history search looks for.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogQuery {
+    /// Case-insensitive text the line or its category must contain; empty
+    /// matches every line.
+    pub text: String,
+    /// Slots to include; empty includes all of them, deleted ones too.
+    pub slots: Vec<String>,
+    pub source: Option<LogSource>,
+    /// The least severe lines to include; `None` includes all.
+    pub min_severity: Option<Severity>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl LogQuery {
+    fn matches(&self, record: &LogRecord, needle: &str) -> bool {
+        (self.slots.is_empty() || self.slots.contains(&record.slot_name))
+            && self.source.is_none_or(|s| s == record.source)
+            && self
+                .min_severity
+                .is_none_or(|v| record.severity.is_at_least(v))
+            && self.since.is_none_or(|t| record.timestamp >= t)
+            && self.until.is_none_or(|t| record.timestamp <= t)
+            && (needle.is_empty()
+                || record.text.to_lowercase().contains(needle)
+                || record
+                    .category
+                    .as_ref()
+                    .is_some_and(|c| c.to_lowercase().contains(needle)))
+    }
+
+    /// Whether a segment with this index may hold matching lines.
+    fn may_match(&self, index: &SegmentIndex) -> bool {
+        self.since
+            .is_none_or(|t| index.last >= t.timestamp_millis())
+            && self
+                .until
+                .is_none_or(|t| index.first <= t.timestamp_millis())
+            && (self.slots.is_empty() || self.slots.iter().any(|s| index.slots.contains(s)))
+    }
+}
+
+/// Where a search continues to find older lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogCursor {
+    segment: u64,
+    /// Byte offset of the oldest line already returned.
+    offset: u64,
+}
+
+/// One page of search results.
+#[derive(Debug, Clone, Default)]
+pub struct LogPage {
+    /// Matching lines, oldest first.
+    pub records: Vec<LogRecord>,
+    /// Set when older matches may remain.
+    pub next: Option<LogCursor>,
+}
+
+/// Summary of a closed segment, stored beside it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SegmentIndex {
+    /// Capture times of the first and last lines, in Unix milliseconds.
+    first: i64,
+    last: i64,
+    count: u64,
+    slots: BTreeSet<String>,
+}
+
+impl SegmentIndex {
+    fn add(&mut self, record: &LogRecord) {
+        let t = record.timestamp.timestamp_millis();
+        if self.count == 0 {
+            self.first = t;
+        }
+        self.first = self.first.min(t);
+        self.last = self.last.max(t);
+        self.count += 1;
+        if !self.slots.contains(&record.slot_name) {
+            self.slots.insert(record.slot_name.clone());
+        }
+    }
+}
+
+/// A workspace's log history in `.slots/logs/`.
+#[derive(Debug, Clone)]
+pub struct LogStore {
+    dir: PathBuf,
+    retention: RetentionPolicy,
+}
+
+impl LogStore {
+    pub fn new(slots_directory: &Path) -> Self {
+        Self {
+            dir: slots_directory.join("logs"),
+            retention: RetentionPolicy::default(),
+        }
+    }
+
+    pub fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Store every line sent to the returned sender, then pass it on to
+    /// `tx`. Writing happens on its own thread; if the store can't be written,
+    /// lines are still passed on. Only the process holding the workspace lock
+    /// should send lines.
+    pub fn record(self, tx: mpsc::UnboundedSender<LogLine>) -> mpsc::UnboundedSender<LogLine> {
+        let (record_tx, mut record_rx) = mpsc::unbounded_channel::<LogLine>();
+        let forward_tx = tx.clone();
+        let spawned = std::thread::Builder::new()
+            .name("log-store".into())
+            .spawn(move || {
+                // Opened on the first line, so a process that lost the
+                // workspace lock never touches the owner's segments
+                let mut writer: Option<LogWriter> = None;
+                let mut disabled = false;
+                let mut parsers: HashMap<(String, LogSource), LogParser> = HashMap::new();
+                while let Some(line) = record_rx.blocking_recv() {
+                    let mut next = Some(line);
+                    // Flush once per burst rather than once per line
+                    while let Some(line) = next {
+                        let parsed = parsers
+                            .entry((line.slot_name.clone(), line.source))
+                            .or_default()
+                            .parse(line.source, &line.line);
+                        let record = LogRecord::from_line(&line, parsed);
+                        let _ = forward_tx.send(line);
+                        if writer.is_none() && !disabled {
+                            match self.writer() {
+                                Ok(w) => writer = Some(w),
+                                Err(e) => {
+                                    tracing::warn!("log history disabled: {e}");
+                                    disabled = true;
+                                }
+                            }
+                        }
+                        if let Some(w) = writer.as_mut() {
+                            if let Err(e) = w.append(&record) {
+                                tracing::warn!("log history write failed, disabling: {e}");
+                                writer = None;
+                                disabled = true;
+                            }
+                        }
+                        next = record_rx.try_recv().ok();
+                    }
+                    if let Some(w) = writer.as_mut() {
+                        let _ = w.flush();
+                    }
+                }
+            });
+        match spawned {
+            Ok(_) => record_tx,
+            Err(e) => {
+                tracing::warn!("log history disabled: {e}");
+                // Nothing reads `record_tx`; hand back the original sender
+                drop(record_tx);
+                tx
+            }
+        }
+    }
+
+    /// Open for appending. Each writer starts a new segment, after indexing
+    /// segments left unindexed by a crash and applying retention.
+    pub fn writer(&self) -> std::io::Result<LogWriter> {
+        std::fs::create_dir_all(&self.dir)?;
+        for id in self.segment_ids()? {
+            if !self.index_path(id).exists() {
+                let index = self.build_index(id)?;
+                self.write_index(id, &index)?;
+            }
+        }
+        let writer = LogWriter {
+            store: self.clone(),
+            current: None,
+        };
+        writer.enforce_retention()?;
+        Ok(writer)
+    }
+
+    /// Find lines matching `query`, newest first, `limit` at a time. Pass
+    /// the previous page's `next` to continue further back.
+    pub fn search(
+        &self,
+        query: &LogQuery,
+        before: Option<LogCursor>,
+        limit: usize,
+    ) -> Result<LogPage> {
+        let needle = query.text.to_lowercase();
+        let mut ids = self
+            .segment_ids()
+            .map_err(|e| OrchestratorError::State(format!("reading log history: {e}")))?;
+        ids.reverse();
+
+        let mut page = LogPage::default();
+        for id in ids {
+            let end = match before {
+                Some(cursor) if id > cursor.segment => continue,
+                Some(cursor) if id == cursor.segment => cursor.offset,
+                _ => u64::MAX,
+            };
+            if let Some(index) = self.read_index(id) {
+                if !query.may_match(&index) {
+                    continue;
+                }
+            }
+            let content = match std::fs::read(self.segment_path(id)) {
+                Ok(content) => content,
+                // Deleted by retention since it was listed
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => {
+                    return Err(OrchestratorError::State(format!(
+                        "reading log history: {e}"
+                    )))
+                }
+            };
+            let end = (end.min(content.len() as u64)) as usize;
+
+            for (offset, line) in lines_backwards(&content[..end]) {
+                // A line still being written doesn't parse yet
+                let Ok(record) = serde_json::from_slice::<LogRecord>(line) else {
+                    continue;
+                };
+                if !query.matches(&record, &needle) {
+                    continue;
+                }
+                if page.records.len() == limit {
+                    page.next = Some(LogCursor {
+                        segment: id,
+                        offset: (offset + line.len() + 1) as u64,
+                    });
+                    page.records.reverse();
+                    return Ok(page);
+                }
+                page.records.push(record);
+            }
+        }
+        page.records.reverse();
+        Ok(page)
+    }
+
+    /// Segment ids, oldest first. An id is the segment's start time in Unix
+    /// milliseconds.
+    fn segment_ids(&self) -> std::io::Result<Vec<u64>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut ids: Vec<u64> = entries
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name();
+                let stem = name.to_str()?.strip_suffix(&format!(".{SEGMENT_EXT}"))?;
+                stem.parse().ok()
+            })
+            .collect();
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    fn segment_path(&self, id: u64) -> PathBuf {
+        self.dir.join(format!("{id:013}.{SEGMENT_EXT}"))
+    }
+
+    fn index_path(&self, id: u64) -> PathBuf {
+        self.dir.join(format!("{id:013}.{INDEX_EXT}"))
+    }
+
+    fn read_index(&self, id: u64) -> Option<SegmentIndex> {
+        let content = std::fs::read(self.index_path(id)).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    fn write_index(&self, id: u64, index: &SegmentIndex) -> std::io::Result<()> {
+        let json = serde_json::to_vec(index).map_err(std::io::Error::other)?;
+        std::fs::write(self.index_path(id), json)
+    }
+
+    fn build_index(&self, id: u64) -> std::io::Result<SegmentIndex> {
+        let content = std::fs::read(self.segment_path(id))?;
+        let mut index = SegmentIndex::default();
+        for line in content.split(|&b| b == b'\n') {
+            if let Ok(record) = serde_json::from_slice::<LogRecord>(line) {
+                index.add(&record);
+            }
+        }
+        Ok(index)
+    }
+}
+
+/// The lines of `content` from last to first, with their byte offsets.
+fn lines_backwards(content: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    let mut end = content.len();
+    std::iter::from_fn(move || {
+        while end > 0 {
+            let start = content[..end - 1]
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(0, |i| i + 1);
+            let line = &content[start..end];
+            let line = line.strip_suffix(b"\n").unwrap_or(line);
+            end = start;
+            if !line.is_empty() {
+                return Some((start, line));
+            }
+        }
+        None
+    })
+}
+
+/// Appends records to the log history. Dropping it closes the current
+/// segment.
+pub struct LogWriter {
+    store: LogStore,
+    current: Option<OpenSegment>,
+}
+
+struct OpenSegment {
+    id: u64,
+    file: BufWriter<File>,
+    bytes: u64,
+    index: SegmentIndex,
+}
+
+impl LogWriter {
+    pub fn append(&mut self, record: &LogRecord) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(record).map_err(std::io::Error::other)?;
+        line.push(b'\n');
+
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|s| s.bytes + line.len() as u64 > SEGMENT_BYTES)
+        {
+            self.close_segment()?;
+            self.enforce_retention()?;
+        }
+        let segment = match &mut self.current {
+            Some(segment) => segment,
+            None => self.current.insert(self.open_segment()?),
+        };
+        segment.file.write_all(&line)?;
+        segment.bytes += line.len() as u64;
+        segment.index.add(record);
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.current {
+            Some(segment) => segment.file.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn open_segment(&self) -> std::io::Result<OpenSegment> {
+        let now = Utc::now().timestamp_millis().max(0) as u64;
+        // Segment ids must increase even if two open in the same millisecond
+        let latest = self.store.segment_ids()?.last().copied().unwrap_or(0);
+        let id = now.max(latest + 1);
+        let file = std::fs::OpenOptions::new()
+            .create_new(true)
+            .append(true)
+            .open(self.store.segment_path(id))?;
+        Ok(OpenSegment {
+            id,
+            file: BufWriter::new(file),
+            bytes: 0,
+            index: SegmentIndex::default(),
+        })
+    }
+
+    fn close_segment(&mut self) -> std::io::Result<()> {
+        if let Some(mut segment) = self.current.take() {
+            segment.file.flush()?;
+            self.store.write_index(segment.id, &segment.index)?;
+        }
+        Ok(())
+    }
+
+    /// Delete the oldest closed segments until the history fits the policy.
+    fn enforce_retention(&self) -> std::io::Result<()> {
+        let store = &self.store;
+        let current = self.current.as_ref().map(|s| s.id);
+        let cutoff = (Utc::now() - store.retention.max_age).timestamp_millis();
+
+        let mut segments: Vec<(u64, u64)> = Vec::new();
+        for id in store.segment_ids()? {
+            let bytes = std::fs::metadata(store.segment_path(id)).map_or(0, |m| m.len());
+            segments.push((id, bytes));
+        }
+        let mut total: u64 = segments.iter().map(|(_, bytes)| bytes).sum();
+
+        for (id, bytes) in segments {
+            if Some(id) == current {
+                break;
+            }
+            let expired = store.read_index(id).is_some_and(|i| i.last < cutoff);
+            if total <= store.retention.max_bytes && !expired {
+                break;
+            }
+            std::fs::remove_file(store.segment_path(id))?;
+            let _ = std::fs::remove_file(store.index_path(id));
+            total -= bytes;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for LogWriter {
+    fn drop(&mut self) {
+        if let Err(e) = self.close_segment() {
+            tracing::warn!("closing log history segment: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(slot: &str, minutes_ago: i64, text: &str) -> LogRecord {
+        LogRecord {
+            timestamp: Utc::now() - Duration::minutes(minutes_ago),
+            slot_name: slot.into(),
+            source: LogSource::Agent,
+            severity: Severity::Info,
+            category: None,
+            text: text.into(),
+        }
+    }
+
+    fn texts(page: &LogPage) -> Vec<&str> {
+        page.records.iter().map(|r| r.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_search_pages_backwards_with_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LogStore::new(dir.path());
+        {
+            let mut writer = store.writer().unwrap();
+            for i in 0..5 {
+                writer
+                    .append(&record("api", 50 - i, &format!("api line {i}")))
+                    .unwrap();
+                writer
+                    .append(&record("web", 50 - i, &format!("web line {i}")))
+                    .unwrap();
+            }
+            writer.append(&record("api", 1, "Build FAILED")).unwrap();
+        }
+        // A second run writes a new segment
+        {
+            let mut writer = store.writer().unwrap();
+            writer.append(&record("api", 0, "api restarted")).unwrap();
+        }
+
+        let query = LogQuery {
+            slots: vec!["api".into()],
+            ..Default::default()
+        };
+        let page = store.search(&query, None, 4).unwrap();
+        assert_eq!(
+            texts(&page),
+            ["api line 3", "api line 4", "Build FAILED", "api restarted"]
+        );
+        let older = store.search(&query, page.next, 4).unwrap();
+        assert_eq!(texts(&older), ["api line 0", "api line 1", "api line 2"]);
+        assert!(older.next.is_none());
+
+        let query = LogQuery {
+            text: "failed".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            texts(&store.search(&query, None, 10).unwrap()),
+            ["Build FAILED"]
+        );
+
+        let query = LogQuery {
+            since: Some(Utc::now() - Duration::seconds(47 * 60 + 30)),
+            until: Some(Utc::now() - Duration::seconds(45 * 60 + 30)),
+            slots: vec!["web".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            texts(&store.search(&query, None, 10).unwrap()),
+            ["web line 3", "web line 4"]
+        );
+    }
+
+    #[test]
+    fn test_retention_deletes_oldest_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LogStore::new(dir.path()).with_retention(RetentionPolicy {
+            max_bytes: 1,
+            max_age: Duration::days(1),
+        });
+        for text in ["first run", "second run", "third run"] {
+            let mut writer = store.writer().unwrap();
+            writer.append(&record("api", 0, text)).unwrap();
+        }
+        // Everything over the limit goes, but never the segment being written
+        let mut writer = store.writer().unwrap();
+        writer.append(&record("api", 0, "fourth run")).unwrap();
+        writer.flush().unwrap();
+        let page = store.search(&LogQuery::default(), None, 10).unwrap();
+        assert_eq!(texts(&page), ["fourth run"]);
+
+        drop(writer);
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = LogStore::new(dir.path());
+        for (minutes_ago, text) in [(3 * 24 * 60, "last week"), (0, "today")] {
+            let mut writer = store.writer().unwrap();
+            writer.append(&record("api", minutes_ago, text)).unwrap();
+        }
+        let store = store.with_retention(RetentionPolicy {
+            max_bytes: u64::MAX,
+            max_age: Duration::days(2),
+        });
+        drop(store.writer().unwrap());
+        let page = store.search(&LogQuery::default(), None, 10).unwrap();
+        assert_eq!(texts(&page), ["today"]);
+    }
+
+    #[test]
+    fn test_unindexed_segment_is_indexed_on_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LogStore::new(dir.path());
+        let mut writer = store.writer().unwrap();
+        writer.append(&record("api", 0, "before crash")).unwrap();
+        writer.flush().unwrap();
+        // Simulate a crash: the segment is never closed
+        std::mem::forget(writer);
+
+        drop(store.writer().unwrap());
+        let id = store.segment_ids().unwrap()[0];
+        let index = store.read_index(id).unwrap();
+        assert_eq!(index.count, 1);
+        assert!(index.slots.contains("api"));
+    }
+
+    #[tokio::test]
+    async fn test_record_stores_and_forwards_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LogStore::new(dir.path());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let record_tx = store.clone().record(tx);
+        record_tx
+            .send(LogLine::new("api", LogSource::Aspire, "error: port in use"))
+            .unwrap();
+
+        let forwarded = rx.recv().await.unwrap();
+        assert_eq!(forwarded.line, "error: port in use");
+        drop(record_tx);
+        // The writer thread flushes before forwarding the next burst; wait
+        // for it to finish
+        for _ in 0..50 {
+            if !store
+                .search(&LogQuery::default(), None, 1)
+                .unwrap()
+                .records
+                .is_empty()
+            {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let page = store.search(&LogQuery::default(), None, 10).unwrap();
+        assert_eq!(page.records.len(), 1);
+        assert_eq!(page.records[0].severity, Severity::Error);
+        assert_eq!(page.records[0].source, LogSource::Aspire);
+    }
+}
@@ -1,49 +1,134 @@
-use std::io::{Read, Seek, SeekFrom};
+use std::fs::{File, Metadata};
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, SecondsFormat, Utc};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
-use tokio::time::{interval, Duration};
+use tokio::time::{interval, Duration, MissedTickBehavior};
 
-/// The source of a log line: agent output or Aspire process output.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The source of a log line: agent output, Aspire process output, or
+/// OpenTelemetry data sent to the slot's OTLP receiver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum LogSource {
     Agent,
     Aspire,
+    Telemetry,
 }
 
 /// A log line event with the slot name, source, and the line text.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct LogLine {
     pub slot_name: String,
     pub source: LogSource,
     pub line: String,
+    /// When the line was captured from its process or file. Lines from a
+    /// daemon that predates timestamps are stamped on arrival.
+    #[serde(default = "Utc::now")]
+    pub timestamp: DateTime<Utc>,
+}
+
+impl LogLine {
+    /// A line captured now.
+    pub fn new(slot_name: impl Into<String>, source: LogSource, line: impl Into<String>) -> Self {
+        Self {
+            slot_name: slot_name.into(),
+            source,
+            line: line.into(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Format a line as written to a slot's log file, prefixed by its capture
+/// time.
+pub fn format_timestamped(timestamp: DateTime<Utc>, line: &str) -> String {
+    format!(
+        "{} {line}",
+        timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)
+    )
 }
 
-/// Start an async file tailer that polls for new content and sends lines over a channel.
+/// Split the capture time written by [`format_timestamped`] off a log file
+/// line. Lines without one are returned whole.
+pub fn split_timestamp(line: &str) -> (Option<DateTime<Utc>>, &str) {
+    // Cheap check first, as most lines of a raw log won't have one
+    if !line.as_bytes().first().is_some_and(u8::is_ascii_digit) {
+        return (None, line);
+    }
+    let Some((prefix, text)) = line.split_once(' ') else {
+        return (None, line);
+    };
+    match DateTime::parse_from_rfc3339(prefix) {
+        Ok(timestamp) => (Some(timestamp.with_timezone(&Utc)), text),
+        Err(_) => (None, line),
+    }
+}
+
+/// How often to check a file that couldn't be watched.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How often to check a watched file anyway, for filesystems that don't
+/// report every change (network shares, some container mounts).
+const WATCHED_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Start an async file tailer that sends each complete line over a channel.
 ///
-/// Returns a `JoinHandle` that can be aborted to stop tailing.
+/// The file's directory is watched for changes, falling back to polling when
+/// that isn't possible. The file may not exist yet. When it is truncated or
+/// replaced (log rotation), tailing starts over from the beginning of the new
+/// content. Returns a `JoinHandle` that can be aborted to stop tailing.
 pub fn start_tailing(
     file_path: PathBuf,
     slot_name: String,
     source: LogSource,
     tx: mpsc::UnboundedSender<LogLine>,
 ) -> tokio::task::JoinHandle<()> {
-    tokio::spawn(async move {
-        let mut last_position: u64 = 0;
+    spawn_tail(Tail::new(file_path, slot_name, source), tx)
+}
 
-        // Wait for file to exist
-        while !file_path.exists() {
-            tokio::time::sleep(Duration::from_millis(500)).await;
-        }
+/// Like [`start_tailing`], but for a file an earlier tailer (in a previous
+/// run) already sent: only lines finished after its last complete line are
+/// sent, so they aren't recorded twice.
+pub fn resume_tailing(
+    file_path: PathBuf,
+    slot_name: String,
+    source: LogSource,
+    tx: mpsc::UnboundedSender<LogLine>,
+) -> tokio::task::JoinHandle<()> {
+    let mut tail = Tail::new(file_path, slot_name, source);
+    if let Err(e) = tail.skip_sent() {
+        tracing::debug!(path = %tail.path.display(), "log tail resume failed: {e}");
+    }
+    spawn_tail(tail, tx)
+}
+
+fn spawn_tail(mut tail: Tail, tx: mpsc::UnboundedSender<LogLine>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let (wake_tx, mut wake_rx) = mpsc::unbounded_channel();
+        // Dropping the watcher stops it, so it lives as long as the task
+        let watcher = watch_file(&tail.path, wake_tx);
+        let mut tick = interval(if watcher.is_some() {
+            WATCHED_POLL_INTERVAL
+        } else {
+            POLL_INTERVAL
+        });
+        tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
-        let mut tick = interval(Duration::from_millis(250));
         loop {
-            tick.tick().await;
+            tokio::select! {
+                _ = tick.tick() => {}
+                Some(()) = wake_rx.recv() => {
+                    // One read covers a burst of writes
+                    while wake_rx.try_recv().is_ok() {}
+                }
+            }
 
-            if let Ok(new_pos) =
-                read_new_content(&file_path, last_position, &slot_name, source, &tx)
-            {
-                last_position = new_pos;
+            if let Err(e) = tail.poll(&tx) {
+                tracing::debug!(path = %tail.path.display(), "log tail read failed: {e}");
             }
 
             if tx.is_closed() {
@@ -53,42 +138,236 @@ pub fn start_tailing(
     })
 }
 
-fn read_new_content(
-    path: &Path,
-    last_position: u64,
-    slot_name: &str,
+/// Watch the directory holding `path` and signal `wake` whenever an entry
+/// with its file name changes. Watching the directory rather than the file
+/// sees the file being created, deleted or renamed over. Returns `None` when
+/// no watcher can be set up.
+fn watch_file(path: &Path, wake: mpsc::UnboundedSender<()>) -> Option<RecommendedWatcher> {
+    let name = path.file_name()?.to_os_string();
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let relevant = match event {
+            Ok(event) => event
+                .paths
+                .iter()
+                .any(|p| p.file_name() == Some(name.as_os_str())),
+            // Events may have been lost; let the tailer look for itself
+            Err(_) => true,
+        };
+        if relevant {
+            let _ = wake.send(());
+        }
+    })
+    .map_err(|e| tracing::debug!("file watcher unavailable, polling: {e}"))
+    .ok()?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .map_err(|e| tracing::debug!(dir = %dir.display(), "cannot watch, polling: {e}"))
+        .ok()?;
+    Some(watcher)
+}
+
+/// How much of a file to read at a time.
+const READ_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Longest line kept whole; longer ones are split.
+const MAX_LINE_BYTES: usize = 64 * 1024;
+
+/// The largest index up to `max` that doesn't fall inside a UTF-8 sequence,
+/// so a split line doesn't cut a character in two. Falls back to `max` for
+/// bytes that aren't UTF-8 anyway.
+fn char_boundary(bytes: &[u8], max: usize) -> usize {
+    let is_continuation = |b: u8| b & 0b1100_0000 == 0b1000_0000;
+    (max.saturating_sub(3)..=max)
+        .rev()
+        .find(|&i| i < bytes.len() && !is_continuation(bytes[i]))
+        .filter(|&i| i > 0)
+        .unwrap_or(max)
+}
+
+/// Identifies a file independently of its path, to notice rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileId(u64, u64);
+
+impl FileId {
+    #[cfg(unix)]
+    fn of(metadata: &Metadata) -> Option<Self> {
+        use std::os::unix::fs::MetadataExt;
+        Some(Self(metadata.dev(), metadata.ino()))
+    }
+
+    // Stable Rust has no file index on Windows; creation time tells a
+    // recreated file apart well enough.
+    #[cfg(not(unix))]
+    fn of(metadata: &Metadata) -> Option<Self> {
+        let created = metadata.created().ok()?;
+        let since_epoch = created.duration_since(std::time::UNIX_EPOCH).ok()?;
+        Some(Self(
+            since_epoch.as_secs(),
+            since_epoch.subsec_nanos().into(),
+        ))
+    }
+}
+
+/// The read state of one tailed file.
+struct Tail {
+    path: PathBuf,
+    slot_name: String,
     source: LogSource,
-    tx: &mpsc::UnboundedSender<LogLine>,
-) -> std::io::Result<u64> {
-    let mut file = std::fs::OpenOptions::new().read(true).open(path)?;
+    /// The file being read. It stays open so that lines written to it just
+    /// before it was rotated are still read.
+    file: Option<(File, Option<FileId>)>,
+    position: u64,
+    /// Bytes after the last line break, held until their line is finished.
+    /// Kept undecoded so a character or escape sequence split across writes
+    /// is put back together.
+    partial: Vec<u8>,
+}
 
-    let metadata = file.metadata()?;
-    if metadata.len() <= last_position {
-        return Ok(last_position);
+impl Tail {
+    fn new(path: PathBuf, slot_name: String, source: LogSource) -> Self {
+        Self {
+            path,
+            slot_name,
+            source,
+            file: None,
+            position: 0,
+            partial: Vec::new(),
+        }
+    }
+
+    /// Start after the file's last line break. Everything before it was sent
+    /// by an earlier tailer; an unfinished last line wasn't, unless it had
+    /// grown past [`MAX_LINE_BYTES`] and been sent in pieces.
+    fn skip_sent(&mut self) -> std::io::Result<()> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let metadata = file.metadata()?;
+        let len = metadata.len();
+        let start = len.saturating_sub(MAX_LINE_BYTES as u64);
+        let mut last = Vec::new();
+        file.seek(SeekFrom::Start(start))?;
+        (&mut file).take(len - start).read_to_end(&mut last)?;
+        self.position = match last.iter().rposition(|&b| b == b'\n') {
+            Some(i) => start + i as u64 + 1,
+            None if start == 0 => 0,
+            None => len,
+        };
+        self.file = Some((file, FileId::of(&metadata)));
+        Ok(())
+    }
+
+    /// Send any lines completed since the last call.
+    fn poll(&mut self, tx: &mpsc::UnboundedSender<LogLine>) -> std::io::Result<()> {
+        self.read_to_end(tx)?;
+
+        let metadata = match std::fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            // Not created yet, or rotated away and not yet replaced
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        match &self.file {
+            Some((_, id)) if *id == FileId::of(&metadata) => {
+                if metadata.len() >= self.position {
+                    return Ok(());
+                }
+                // Truncated in place, e.g. cleared before an agent restarts
+                self.flush_partial(tx);
+                self.position = 0;
+            }
+            _ => {
+                // First open, or a new file has taken the name
+                self.flush_partial(tx);
+                let file = File::open(&self.path)?;
+                let id = FileId::of(&file.metadata()?);
+                self.file = Some((file, id));
+                self.position = 0;
+            }
+        }
+        self.read_to_end(tx)
+    }
+
+    fn read_to_end(&mut self, tx: &mpsc::UnboundedSender<LogLine>) -> std::io::Result<()> {
+        if let Some((file, _)) = self.file.as_mut() {
+            file.seek(SeekFrom::Start(self.position))?;
+        }
+        // Read in chunks so catching up on a large file doesn't hold it all
+        let mut chunk = vec![0; READ_CHUNK_BYTES];
+        loop {
+            let Some((file, _)) = self.file.as_mut() else {
+                return Ok(());
+            };
+            let n = file.read(&mut chunk)?;
+            if n == 0 {
+                return Ok(());
+            }
+            self.position += n as u64;
+            self.push(&chunk[..n], tx);
+        }
     }
 
-    file.seek(SeekFrom::Start(last_position))?;
-    let mut content = String::new();
-    file.read_to_string(&mut content)?;
-    let new_position = file.stream_position()?;
+    /// Add newly read bytes, sending each line they complete.
+    fn push(&mut self, bytes: &[u8], tx: &mpsc::UnboundedSender<LogLine>) {
+        for piece in bytes.split_inclusive(|&b| b == b'\n') {
+            let (text, ends_line) = match piece.strip_suffix(b"\n") {
+                Some(text) => (text, true),
+                None => (piece, false),
+            };
+            self.partial.extend_from_slice(text);
 
-    for line in content.lines() {
+            // A line that never ends (a progress bar redrawn with `\r`, say)
+            // is sent in pieces rather than buffered without limit
+            while self.partial.len() > MAX_LINE_BYTES {
+                let cut = char_boundary(&self.partial, MAX_LINE_BYTES);
+                let rest = self.partial.split_off(cut);
+                let line = std::mem::replace(&mut self.partial, rest);
+                self.send(&line, tx);
+            }
+
+            if ends_line {
+                let line = std::mem::take(&mut self.partial);
+                self.send(&line, tx);
+            }
+        }
+    }
+
+    /// Send an unfinished last line whose file is going away.
+    fn flush_partial(&mut self, tx: &mpsc::UnboundedSender<LogLine>) {
+        let partial = std::mem::take(&mut self.partial);
+        self.send(&partial, tx);
+    }
+
+    /// Decode and send one line. Invalid UTF-8 becomes U+FFFD rather than
+    /// losing the line.
+    fn send(&self, line: &[u8], tx: &mpsc::UnboundedSender<LogLine>) {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
         if !line.is_empty() {
+            let line = String::from_utf8_lossy(line);
+            // Keep the capture time of a line written with one
+            let (timestamp, text) = split_timestamp(&line);
             let _ = tx.send(LogLine {
-                slot_name: slot_name.to_string(),
-                source,
-                line: line.to_string(),
+                slot_name: self.slot_name.clone(),
+                source: self.source,
+                line: text.to_string(),
+                timestamp: timestamp.unwrap_or_else(Utc::now),
             });
         }
     }
-
-    Ok(new_position)
 }
 
 /// Read the last N lines of a file (non-async, for initial load).
 pub fn read_tail(file_path: &Path, line_count: usize) -> String {
-    match std::fs::read_to_string(file_path) {
-        Ok(content) => {
+    match std::fs::read(file_path) {
+        Ok(bytes) => {
+            let content = String::from_utf8_lossy(&bytes);
             let lines: Vec<&str> = content.lines().collect();
             let start = lines.len().saturating_sub(line_count);
             lines[start..].join("\n")
@@ -96,3 +375,206 @@ pub fn read_tail(file_path: &Path, line_count: usize) -> String {
         Err(_) => String::new(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn drain(rx: &mut mpsc::UnboundedReceiver<LogLine>) -> Vec<String> {
+        let mut lines = Vec::new();
+        while let Ok(line) = rx.try_recv() {
+            lines.push(line.line);
+        }
+        lines
+    }
+
+    fn append(path: &Path, text: impl AsRef<[u8]>) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(text.as_ref()).unwrap();
+    }
+
+    async fn next(rx: &mut mpsc::UnboundedReceiver<LogLine>) -> String {
+        tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("tailer sent nothing")
+            .unwrap()
+            .line
+    }
+
+    fn tail_in(dir: &Path) -> (Tail, PathBuf) {
+        let path = dir.join("agent.log");
+        let tail = Tail::new(path.clone(), "slot".into(), LogSource::Agent);
+        (tail, path)
+    }
+
+    #[test]
+    fn partial_lines_wait_for_their_line_break() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut tail, path) = tail_in(dir.path());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        tail.poll(&tx).unwrap();
+        assert!(drain(&mut rx).is_empty());
+
+        append(&path, "one\r\ntw");
+        tail.poll(&tx).unwrap();
+        assert_eq!(drain(&mut rx), ["one"]);
+
+        append(&path, "o\n\nthree\n");
+        tail.poll(&tx).unwrap();
+        assert_eq!(drain(&mut rx), ["two", "three"]);
+    }
+
+    #[test]
+    fn characters_and_escapes_split_across_writes_are_rejoined() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut tail, path) = tail_in(dir.path());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let text = "h\u{e9}llo \u{1F680} \x1b[32mgreen\x1b[0m\n".as_bytes();
+        // Mid-emoji, then mid-escape sequence
+        for piece in [&text[..10], &text[10..17], &text[17..]] {
+            append(&path, piece);
+            tail.poll(&tx).unwrap();
+        }
+        assert_eq!(
+            drain(&mut rx),
+            ["h\u{e9}llo \u{1F680} \x1b[32mgreen\x1b[0m"]
+        );
+    }
+
+    #[test]
+    fn resuming_skips_lines_already_sent() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut tail, path) = tail_in(dir.path());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        append(&path, "one\ntwo\npart");
+        tail.skip_sent().unwrap();
+        tail.poll(&tx).unwrap();
+        assert!(drain(&mut rx).is_empty());
+
+        // The unfinished line was never sent, so it comes through whole
+        append(&path, "ial\nthree\n");
+        tail.poll(&tx).unwrap();
+        assert_eq!(drain(&mut rx), ["partial", "three"]);
+
+        let (mut missing, _) = tail_in(&dir.path().join("nowhere"));
+        missing.skip_sent().unwrap();
+        assert_eq!(missing.position, 0);
+    }
+
+    #[test]
+    fn invalid_utf8_is_replaced_not_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut tail, path) = tail_in(dir.path());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        append(&path, b"bad \xff byte\nnext\n");
+        tail.poll(&tx).unwrap();
+        assert_eq!(drain(&mut rx), ["bad \u{FFFD} byte", "next"]);
+    }
+
+    #[test]
+    fn written_capture_times_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut tail, path) = tail_in(dir.path());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let captured = DateTime::parse_from_rfc3339("2026-03-14T09:26:53.589Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let before = Utc::now();
+        append(
+            &path,
+            format!(
+                "{}\n2026 was a year\n",
+                format_timestamped(captured, "info: ready")
+            ),
+        );
+        tail.poll(&tx).unwrap();
+
+        let first = rx.try_recv().unwrap();
+        assert_eq!(first.line, "info: ready");
+        assert_eq!(first.timestamp, captured);
+        let second = rx.try_recv().unwrap();
+        assert_eq!(second.line, "2026 was a year");
+        assert!(second.timestamp >= before);
+    }
+
+    #[test]
+    fn overlong_lines_are_split_on_character_boundaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut tail, path) = tail_in(dir.path());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        // Three-byte characters don't divide MAX_LINE_BYTES evenly
+        let long = "\u{20AC}".repeat(MAX_LINE_BYTES / 3 + 10);
+        append(&path, format!("{long}\n"));
+        tail.poll(&tx).unwrap();
+        let lines = drain(&mut rx);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines.concat(), long);
+    }
+
+    #[test]
+    fn truncated_file_is_read_from_the_start() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut tail, path) = tail_in(dir.path());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        append(&path, "first run line one\nfirst run line two\n");
+        tail.poll(&tx).unwrap();
+        assert_eq!(drain(&mut rx).len(), 2);
+
+        std::fs::write(&path, "").unwrap();
+        append(&path, "second\n");
+        tail.poll(&tx).unwrap();
+        assert_eq!(drain(&mut rx), ["second"]);
+    }
+
+    #[test]
+    fn rotated_file_is_drained_then_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut tail, path) = tail_in(dir.path());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        append(&path, "old 1\n");
+        tail.poll(&tx).unwrap();
+        assert_eq!(drain(&mut rx), ["old 1"]);
+
+        append(&path, "old 2\nold partial");
+        std::fs::rename(&path, dir.path().join("agent.log.1")).unwrap();
+        tail.poll(&tx).unwrap();
+        assert_eq!(drain(&mut rx), ["old 2"]);
+
+        append(&path, "new 1\n");
+        tail.poll(&tx).unwrap();
+        assert_eq!(drain(&mut rx), ["old partial", "new 1"]);
+    }
+
+    #[tokio::test]
+    async fn start_tailing_follows_writes_and_truncation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aspire.log");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let handle = start_tailing(path.clone(), "slot".into(), LogSource::Aspire, tx);
+
+        // The file doesn't exist when tailing starts
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        append(&path, "starting\n");
+        assert_eq!(next(&mut rx).await, "starting");
+
+        std::fs::write(&path, "").unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        append(&path, "restarted\n");
+        assert_eq!(next(&mut rx).await, "restarted");
+
+        handle.abort();
+    }
+}
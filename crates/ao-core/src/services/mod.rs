@@ -3,10 +3,21 @@ pub mod agent_host;
 pub mod aspire;
 pub mod blueprint;
 pub mod config_loader;
+pub mod daemon;
 pub mod discovery;
+pub mod forge;
 pub mod git;
+pub mod log_parser;
+pub mod log_store;
 pub mod log_tailer;
+pub mod orchestrator;
+pub mod otlp;
 pub mod ports;
+pub mod problems;
+pub mod prompt_library;
+pub mod prompt_template;
 pub mod repo_finder;
+pub mod service_manifest;
 pub mod slot_manager;
 pub mod state;
+pub mod workspace_lock;
@@ -0,0 +1,337 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use tokio::sync::mpsc;
+
+use super::daemon::{DaemonClient, Event, Request};
+use super::log_store::LogStore;
+use super::log_tailer::LogLine;
+use super::slot_manager::SlotManager;
+use crate::error::Result;
+use crate::models::{
+    CloneStrategy, DiffTarget, Discrepancy, FileDiff, GitStatus, PullRequest, RebaseOutcome,
+    ReconcileAction, Slot,
+};
+
+/// Slot operations for a front end: run by an in-process `SlotManager`, or
+/// forwarded to a daemon that owns one.
+pub enum Orchestrator {
+    Local(Arc<SlotManager>),
+    Remote {
+        client: DaemonClient,
+        slots_directory: PathBuf,
+    },
+}
+
+impl Orchestrator {
+    /// Attach to the daemon serving `slots_directory` if one is running,
+    /// otherwise load its slots into an in-process `SlotManager`, which takes
+    /// the workspace lock and records log history. Log lines arrive on
+    /// `log_tx` either way.
+    pub async fn connect_or_load(
+        slots_directory: PathBuf,
+        log_tx: mpsc::UnboundedSender<LogLine>,
+    ) -> Result<Self> {
+        if let Some(client) = DaemonClient::connect(&slots_directory).await {
+            let mut subscription = client.subscribe(true, false).await?;
+            tokio::spawn(async move {
+                while let Ok(Some(event)) = subscription.next().await {
+                    if let Event::Log(line) = event {
+                        if log_tx.send(line).is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+            return Ok(Orchestrator::Remote {
+                client,
+                slots_directory,
+            });
+        }
+
+        let log_tx = LogStore::new(&slots_directory).record(log_tx);
+        let sm = SlotManager::new(slots_directory, log_tx);
+        sm.lock_workspace()?;
+        sm.load_state().await?;
+        Ok(Orchestrator::Local(Arc::new(sm)))
+    }
+
+    pub fn is_remote(&self) -> bool {
+        matches!(self, Orchestrator::Remote { .. })
+    }
+
+    /// The in-process manager, unless attached to a daemon.
+    pub fn local(&self) -> Option<&Arc<SlotManager>> {
+        match self {
+            Orchestrator::Local(sm) => Some(sm),
+            Orchestrator::Remote { .. } => None,
+        }
+    }
+
+    /// Snapshot of all slots. Empty if the daemon can't be reached.
+    pub async fn get_slots(&self) -> Vec<Slot> {
+        match self {
+            Orchestrator::Local(sm) => sm.get_slots().await,
+            Orchestrator::Remote { client, .. } => match call(client, Request::List).await {
+                Ok(slots) => slots,
+                Err(e) => {
+                    tracing::warn!("list slots from daemon: {e}");
+                    Vec::new()
+                }
+            },
+        }
+    }
+
+    pub async fn get_slot(&self, name: &str) -> Option<Slot> {
+        match self {
+            Orchestrator::Local(sm) => sm.get_slot(name).await,
+            Orchestrator::Remote { client, .. } => call(client, Request::Get { name: name.into() })
+                .await
+                .ok()
+                .flatten(),
+        }
+    }
+
+    pub async fn create_slot(
+        &self,
+        name: &str,
+        source: &str,
+        branch: Option<&str>,
+        prompt: Option<&str>,
+        clone_strategy: Option<CloneStrategy>,
+    ) -> Result<Slot> {
+        match self {
+            Orchestrator::Local(sm) => {
+                sm.create_slot(name, source, branch, prompt, clone_strategy)
+                    .await
+            }
+            Orchestrator::Remote { client, .. } => {
+                call(
+                    client,
+                    Request::Create {
+                        name: name.into(),
+                        source: source.into(),
+                        branch: branch.map(str::to_string),
+                        prompt: prompt.map(str::to_string),
+                        strategy: clone_strategy,
+                    },
+                )
+                .await
+            }
+        }
+    }
+
+    pub async fn retry_creation(&self, name: &str) -> Result<Slot> {
+        match self {
+            Orchestrator::Local(sm) => sm.retry_creation(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::RetryCreation { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn start_aspire(&self, name: &str) -> Result<()> {
+        match self {
+            Orchestrator::Local(sm) => sm.start_aspire(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::Start { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn stop_aspire(&self, name: &str) -> Result<()> {
+        match self {
+            Orchestrator::Local(sm) => sm.stop_aspire(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::Stop { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn spawn_agent(
+        &self,
+        name: &str,
+        prompt: Option<&str>,
+        allowed_tools: Option<&str>,
+        max_turns: Option<u32>,
+    ) -> Result<()> {
+        match self {
+            Orchestrator::Local(sm) => sm.spawn_agent(name, prompt, allowed_tools, max_turns).await,
+            Orchestrator::Remote { client, .. } => {
+                call(
+                    client,
+                    Request::SpawnAgent {
+                        name: name.into(),
+                        prompt: prompt.map(str::to_string),
+                        allowed_tools: allowed_tools.map(str::to_string),
+                        max_turns,
+                    },
+                )
+                .await
+            }
+        }
+    }
+
+    pub async fn git_status(&self, name: &str) -> Result<GitStatus> {
+        match self {
+            Orchestrator::Local(sm) => sm.git_status(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::GitStatus { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn diff(&self, name: &str, target: DiffTarget) -> Result<Vec<FileDiff>> {
+        match self {
+            Orchestrator::Local(sm) => sm.diff(name, target).await,
+            Orchestrator::Remote { client, .. } => {
+                call(
+                    client,
+                    Request::Diff {
+                        name: name.into(),
+                        target,
+                    },
+                )
+                .await
+            }
+        }
+    }
+
+    pub async fn rebase(&self, name: &str) -> Result<RebaseOutcome> {
+        match self {
+            Orchestrator::Local(sm) => sm.rebase(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::Rebase { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn rebase_continue(&self, name: &str) -> Result<RebaseOutcome> {
+        match self {
+            Orchestrator::Local(sm) => sm.rebase_continue(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::RebaseContinue { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn rebase_skip(&self, name: &str) -> Result<RebaseOutcome> {
+        match self {
+            Orchestrator::Local(sm) => sm.rebase_skip(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::RebaseSkip { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn rebase_abort(&self, name: &str) -> Result<()> {
+        match self {
+            Orchestrator::Local(sm) => sm.rebase_abort(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::RebaseAbort { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn send_conflict_to_agent(&self, name: &str) -> Result<bool> {
+        match self {
+            Orchestrator::Local(sm) => sm.send_conflict_to_agent(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::SendConflictToAgent { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn send_problems_to_agent(&self, name: &str) -> Result<bool> {
+        match self {
+            Orchestrator::Local(sm) => sm.send_problems_to_agent(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::SendProblemsToAgent { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn git_push(&self, name: &str) -> Result<()> {
+        match self {
+            Orchestrator::Local(sm) => sm.git_push(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::Push { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn create_pull_request(&self, name: &str) -> Result<PullRequest> {
+        match self {
+            Orchestrator::Local(sm) => sm.create_pull_request(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::CreatePullRequest { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn refresh_pull_request(&self, name: &str) -> Result<Option<PullRequest>> {
+        match self {
+            Orchestrator::Local(sm) => sm.refresh_pull_request(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::RefreshPullRequest { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn destroy_slot(&self, name: &str) -> Result<()> {
+        match self {
+            Orchestrator::Local(sm) => sm.destroy_slot(name).await,
+            Orchestrator::Remote { client, .. } => {
+                call(client, Request::Destroy { name: name.into() }).await
+            }
+        }
+    }
+
+    pub async fn reconcile(&self) -> Result<Vec<Discrepancy>> {
+        match self {
+            Orchestrator::Local(sm) => Ok(sm.reconcile().await),
+            Orchestrator::Remote { client, .. } => call(client, Request::Reconcile).await,
+        }
+    }
+
+    pub async fn resolve_discrepancy(
+        &self,
+        discrepancy: &Discrepancy,
+        action: ReconcileAction,
+    ) -> Result<()> {
+        match self {
+            Orchestrator::Local(sm) => sm.resolve_discrepancy(discrepancy, action).await,
+            Orchestrator::Remote { client, .. } => {
+                call(
+                    client,
+                    Request::Resolve {
+                        discrepancy: discrepancy.clone(),
+                        action,
+                    },
+                )
+                .await
+            }
+        }
+    }
+
+    /// Returns the workspace root (parent of the slots directory).
+    pub fn workspace_root(&self) -> &Path {
+        let slots_directory = self.slots_directory();
+        slots_directory.parent().unwrap_or(slots_directory)
+    }
+
+    /// Returns the slots directory path.
+    pub fn slots_directory(&self) -> &Path {
+        match self {
+            Orchestrator::Local(sm) => sm.slots_directory(),
+            Orchestrator::Remote {
+                slots_directory, ..
+            } => slots_directory,
+        }
+    }
+}
+
+async fn call<T: DeserializeOwned>(client: &DaemonClient, request: Request) -> Result<T> {
+    Ok(serde_json::from_value(client.call(&request).await?)?)
+}
@@ -0,0 +1,1325 @@
+//! A lightweight OTLP/HTTP receiver, one per slot, for the logs and traces
+//! that Aspire services export with OpenTelemetry.
+//!
+//! It accepts `POST /v1/logs` and `/v1/traces` (and ignores `/v1/metrics`)
+//! encoded as protobuf, the .NET exporter's `http/protobuf` protocol, or as
+//! OTLP JSON. Compressed bodies are refused. Log records and failed spans
+//! are written to the slot's log as `LogSource::Telemetry` lines in the
+//! console log format, so the log parser reads their level and category.
+//! Counts and recent failed spans are kept on the slot's
+//! [`TelemetrySummary`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, RwLock};
+
+use crate::error::{OrchestratorError, Result};
+use crate::models::{ErrorSpan, Slot, TelemetrySummary};
+
+use super::log_parser::Severity;
+use super::log_tailer::{LogLine, LogSource};
+
+/// Name of the port allocation the receiver listens on.
+pub const OTLP_PORT_NAME: &str = "OTLP_PORT";
+
+/// Largest request body accepted.
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+/// Longest request line or header accepted.
+const MAX_HEADER_LINE: usize = 8 * 1024;
+
+/// Most headers accepted on one request.
+const MAX_HEADERS: usize = 100;
+
+/// The endpoint to export to for a receiver on `port`.
+pub fn endpoint(port: u16) -> String {
+    format!("http://127.0.0.1:{port}")
+}
+
+/// Environment variables pointing an OpenTelemetry SDK at the receiver.
+/// Aspire replaces these with its dashboard's endpoint for its resources.
+pub fn exporter_env(port: u16) -> Vec<(String, String)> {
+    vec![
+        ("OTEL_EXPORTER_OTLP_ENDPOINT".into(), endpoint(port)),
+        ("OTEL_EXPORTER_OTLP_PROTOCOL".into(), "http/protobuf".into()),
+    ]
+}
+
+/// Per-signal variables sending logs and traces to the receiver. SDKs prefer
+/// them over the endpoint Aspire gives each resource, and resources inherit
+/// them from the AppHost, so those signals leave the dashboard.
+pub fn resource_exporter_env(port: u16) -> Vec<(String, String)> {
+    let mut vars = Vec::new();
+    for (signal, path) in [("LOGS", "/v1/logs"), ("TRACES", "/v1/traces")] {
+        vars.push((
+            format!("OTEL_EXPORTER_OTLP_{signal}_ENDPOINT"),
+            format!("{}{path}", endpoint(port)),
+        ));
+        vars.push((
+            format!("OTEL_EXPORTER_OTLP_{signal}_PROTOCOL"),
+            "http/protobuf".into(),
+        ));
+    }
+    vars
+}
+
+/// An exception recorded on a log record or span.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OtlpException {
+    pub kind: Option<String>,
+    pub message: Option<String>,
+    pub stacktrace: Option<String>,
+}
+
+/// A log record received over OTLP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtlpLog {
+    pub time: DateTime<Utc>,
+    /// The `service.name` of the resource that sent it.
+    pub service: Option<String>,
+    /// The instrumentation scope; the logger category for .NET logs.
+    pub scope: Option<String>,
+    pub severity: Severity,
+    pub event_id: Option<i64>,
+    pub message: String,
+    pub exception: Option<OtlpException>,
+}
+
+/// A span received over OTLP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtlpSpan {
+    /// When the span ended.
+    pub time: DateTime<Utc>,
+    pub service: Option<String>,
+    pub scope: Option<String>,
+    pub name: String,
+    pub trace_id: String,
+    /// Set when the span's status is error, to its status message.
+    pub error: Option<String>,
+    pub exception: Option<OtlpException>,
+}
+
+impl OtlpSpan {
+    pub fn failed(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// The decoded contents of one export request.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Export {
+    pub logs: Vec<OtlpLog>,
+    pub spans: Vec<OtlpSpan>,
+}
+
+/// Which signal an export request carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    Logs,
+    Traces,
+}
+
+/// Decode an OTLP/HTTP protobuf export request body.
+pub fn decode_protobuf(signal: Signal, body: &[u8]) -> Result<Export> {
+    let mut export = Export::default();
+    for field in fields(body) {
+        let (number, value) = field?;
+        match (signal, number) {
+            (Signal::Logs, 1) => proto::resource_logs(value.bytes()?, &mut export.logs)?,
+            (Signal::Traces, 1) => proto::resource_spans(value.bytes()?, &mut export.spans)?,
+            _ => {}
+        }
+    }
+    Ok(export)
+}
+
+/// Decode an OTLP/HTTP JSON export request body.
+pub fn decode_json(signal: Signal, body: &[u8]) -> Result<Export> {
+    let root: Value = serde_json::from_slice(body)
+        .map_err(|e| OrchestratorError::Telemetry(format!("invalid JSON: {e}")))?;
+    let mut export = Export::default();
+    match signal {
+        Signal::Logs => json::resource_logs(&root, &mut export.logs),
+        Signal::Traces => json::resource_spans(&root, &mut export.spans),
+    }
+    Ok(export)
+}
+
+// ─── Shared Record Building ─────────────────────────────────────────────
+
+/// A log record's fields before its resource and scope are applied.
+#[derive(Default)]
+struct RawLog {
+    time_nanos: u64,
+    observed_nanos: u64,
+    severity_number: i64,
+    severity_text: Option<String>,
+    body: Option<String>,
+    attributes: Vec<(String, String)>,
+}
+
+impl RawLog {
+    fn finish(self, service: Option<&str>, scope: Option<&str>) -> OtlpLog {
+        let attribute = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|k| self.attributes.iter().find(|(key, _)| key == k))
+                .map(|(_, v)| v.clone())
+        };
+        OtlpLog {
+            time: time_from_nanos(self.time_nanos)
+                .or_else(|| time_from_nanos(self.observed_nanos))
+                .unwrap_or_else(Utc::now),
+            service: service.map(str::to_string),
+            scope: scope.map(str::to_string),
+            severity: severity(self.severity_number, self.severity_text.as_deref()),
+            event_id: attribute(&["event.id", "logrecord.event.id", "EventId"])
+                .and_then(|id| id.parse().ok()),
+            message: self.body.clone().unwrap_or_default(),
+            exception: exception(&self.attributes),
+        }
+    }
+}
+
+/// A span's fields before its resource and scope are applied.
+#[derive(Default)]
+struct RawSpan {
+    trace_id: String,
+    name: String,
+    end_nanos: u64,
+    status_code: i64,
+    status_message: Option<String>,
+    /// Attributes of the span's `exception` events.
+    exceptions: Vec<Vec<(String, String)>>,
+}
+
+impl RawSpan {
+    fn finish(self, service: Option<&str>, scope: Option<&str>) -> OtlpSpan {
+        let exception = self.exceptions.iter().find_map(|a| exception(a));
+        // STATUS_CODE_ERROR
+        let error = (self.status_code == 2).then(|| {
+            self.status_message
+                .filter(|m| !m.is_empty())
+                .or_else(|| exception.as_ref().and_then(|e| e.message.clone()))
+                .unwrap_or_default()
+        });
+        OtlpSpan {
+            time: time_from_nanos(self.end_nanos).unwrap_or_else(Utc::now),
+            service: service.map(str::to_string),
+            scope: scope.map(str::to_string),
+            name: self.name,
+            trace_id: self.trace_id,
+            error,
+            exception,
+        }
+    }
+}
+
+fn time_from_nanos(nanos: u64) -> Option<DateTime<Utc>> {
+    (nanos > 0).then(|| DateTime::from_timestamp_nanos(nanos.min(i64::MAX as u64) as i64))
+}
+
+/// The severity of a log record, from its `SeverityNumber` or else its text.
+fn severity(number: i64, text: Option<&str>) -> Severity {
+    match number {
+        1..=8 => Severity::Debug,
+        9..=12 => Severity::Info,
+        13..=16 => Severity::Warn,
+        17.. => Severity::Error,
+        _ => match text.map(str::to_ascii_lowercase).as_deref() {
+            Some("error" | "fatal" | "critical") => Severity::Error,
+            Some("warn" | "warning") => Severity::Warn,
+            Some("debug" | "trace") => Severity::Debug,
+            _ => Severity::Info,
+        },
+    }
+}
+
+/// The `exception.*` semantic convention attributes, if any are set.
+fn exception(attributes: &[(String, String)]) -> Option<OtlpException> {
+    let get = |key: &str| {
+        attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+    };
+    let exception = OtlpException {
+        kind: get("exception.type"),
+        message: get("exception.message"),
+        stacktrace: get("exception.stacktrace"),
+    };
+    (exception != OtlpException::default()).then_some(exception)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// ─── Protobuf ───────────────────────────────────────────────────────────
+
+/// A protobuf field value, as far as the wire format tells.
+enum Wire<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32,
+}
+
+impl<'a> Wire<'a> {
+    fn bytes(&self) -> Result<&'a [u8]> {
+        match self {
+            Wire::Bytes(b) => Ok(b),
+            _ => Err(malformed("expected a length-delimited field")),
+        }
+    }
+
+    fn string(&self) -> Result<String> {
+        Ok(String::from_utf8_lossy(self.bytes()?).into_owned())
+    }
+
+    fn uint(&self) -> u64 {
+        match self {
+            Wire::Varint(v) | Wire::Fixed64(v) => *v,
+            _ => 0,
+        }
+    }
+}
+
+fn malformed(what: &str) -> OrchestratorError {
+    OrchestratorError::Telemetry(format!("malformed protobuf: {what}"))
+}
+
+/// The fields of a protobuf message, in wire order.
+fn fields(mut buf: &[u8]) -> impl Iterator<Item = Result<(u32, Wire<'_>)>> {
+    std::iter::from_fn(move || {
+        if buf.is_empty() {
+            return None;
+        }
+        let field = next_field(&mut buf);
+        if field.is_err() {
+            buf = &[];
+        }
+        Some(field)
+    })
+}
+
+fn next_field<'a>(buf: &mut &'a [u8]) -> Result<(u32, Wire<'a>)> {
+    let key = varint(buf)?;
+    let number = (key >> 3) as u32;
+    let value = match key & 7 {
+        0 => Wire::Varint(varint(buf)?),
+        1 => Wire::Fixed64(u64::from_le_bytes(take(buf, 8)?.try_into().unwrap())),
+        2 => {
+            let len = varint(buf)? as usize;
+            Wire::Bytes(take(buf, len)?)
+        }
+        5 => {
+            take(buf, 4)?;
+            Wire::Fixed32
+        }
+        wire_type => return Err(malformed(&format!("wire type {wire_type}"))),
+    };
+    Ok((number, value))
+}
+
+fn varint(buf: &mut &[u8]) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = buf.split_first().ok_or_else(|| malformed("truncated"))?;
+        *buf = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(malformed("varint too long"))
+}
+
+fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if buf.len() < len {
+        return Err(malformed("truncated"));
+    }
+    let (head, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(head)
+}
+
+/// Decoders for the messages of `opentelemetry/proto`, by field number.
+mod proto {
+    use super::*;
+
+    pub(super) fn resource_logs(buf: &[u8], out: &mut Vec<OtlpLog>) -> Result<()> {
+        let mut service = None;
+        let mut scopes = Vec::new();
+        for field in fields(buf) {
+            match field? {
+                (1, v) => service = resource_service(v.bytes()?)?,
+                (2, v) => scopes.push(v.bytes()?),
+                _ => {}
+            }
+        }
+        for scope_logs in scopes {
+            let mut scope = None;
+            let mut records = Vec::new();
+            for field in fields(scope_logs) {
+                match field? {
+                    (1, v) => scope = scope_name(v.bytes()?)?,
+                    (2, v) => records.push(log_record(v.bytes()?)?),
+                    _ => {}
+                }
+            }
+            out.extend(
+                records
+                    .into_iter()
+                    .map(|r| r.finish(service.as_deref(), scope.as_deref())),
+            );
+        }
+        Ok(())
+    }
+
+    pub(super) fn resource_spans(buf: &[u8], out: &mut Vec<OtlpSpan>) -> Result<()> {
+        let mut service = None;
+        let mut scopes = Vec::new();
+        for field in fields(buf) {
+            match field? {
+                (1, v) => service = resource_service(v.bytes()?)?,
+                (2, v) => scopes.push(v.bytes()?),
+                _ => {}
+            }
+        }
+        for scope_spans in scopes {
+            let mut scope = None;
+            let mut spans = Vec::new();
+            for field in fields(scope_spans) {
+                match field? {
+                    (1, v) => scope = scope_name(v.bytes()?)?,
+                    (2, v) => spans.push(span(v.bytes()?)?),
+                    _ => {}
+                }
+            }
+            out.extend(
+                spans
+                    .into_iter()
+                    .map(|s| s.finish(service.as_deref(), scope.as_deref())),
+            );
+        }
+        Ok(())
+    }
+
+    /// `service.name` from a `Resource`.
+    fn resource_service(buf: &[u8]) -> Result<Option<String>> {
+        for field in fields(buf) {
+            if let (1, v) = field? {
+                let (key, value) = key_value(v.bytes()?)?;
+                if key == "service.name" {
+                    return Ok(Some(value));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// The name of an `InstrumentationScope`.
+    fn scope_name(buf: &[u8]) -> Result<Option<String>> {
+        for field in fields(buf) {
+            if let (1, v) = field? {
+                return Ok(Some(v.string()?).filter(|n| !n.is_empty()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn log_record(buf: &[u8]) -> Result<RawLog> {
+        let mut log = RawLog::default();
+        for field in fields(buf) {
+            match field? {
+                (1, v) => log.time_nanos = v.uint(),
+                (2, v) => log.severity_number = v.uint() as i64,
+                (3, v) => log.severity_text = Some(v.string()?),
+                (5, v) => log.body = any_value(v.bytes()?)?,
+                (6, v) => log.attributes.push(key_value(v.bytes()?)?),
+                (11, v) => log.observed_nanos = v.uint(),
+                _ => {}
+            }
+        }
+        Ok(log)
+    }
+
+    fn span(buf: &[u8]) -> Result<RawSpan> {
+        let mut span = RawSpan::default();
+        for field in fields(buf) {
+            match field? {
+                (1, v) => span.trace_id = hex(v.bytes()?),
+                (5, v) => span.name = v.string()?,
+                (8, v) => span.end_nanos = v.uint(),
+                (11, v) => {
+                    let mut name = String::new();
+                    let mut attributes = Vec::new();
+                    for field in fields(v.bytes()?) {
+                        match field? {
+                            (2, v) => name = v.string()?,
+                            (3, v) => attributes.push(key_value(v.bytes()?)?),
+                            _ => {}
+                        }
+                    }
+                    if name == "exception" {
+                        span.exceptions.push(attributes);
+                    }
+                }
+                (15, v) => {
+                    for field in fields(v.bytes()?) {
+                        match field? {
+                            (2, v) => span.status_message = Some(v.string()?),
+                            (3, v) => span.status_code = v.uint() as i64,
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(span)
+    }
+
+    /// A `KeyValue`, with its value as text.
+    fn key_value(buf: &[u8]) -> Result<(String, String)> {
+        let mut key = String::new();
+        let mut value = None;
+        for field in fields(buf) {
+            match field? {
+                (1, v) => key = v.string()?,
+                (2, v) => value = any_value(v.bytes()?)?,
+                _ => {}
+            }
+        }
+        Ok((key, value.unwrap_or_default()))
+    }
+
+    /// An `AnyValue` as text. Arrays and maps are left out.
+    fn any_value(buf: &[u8]) -> Result<Option<String>> {
+        // The value is a oneof, so only one field is set
+        let Some(field) = fields(buf).next() else {
+            return Ok(None);
+        };
+        Ok(match field? {
+            (1, v) => Some(v.string()?),
+            (2, v) => Some((v.uint() != 0).to_string()),
+            (3, v) => Some((v.uint() as i64).to_string()),
+            (4, v) => Some(f64::from_bits(v.uint()).to_string()),
+            (7, v) => Some(hex(v.bytes()?)),
+            _ => None,
+        })
+    }
+}
+
+// ─── JSON ───────────────────────────────────────────────────────────────
+
+/// Decoders for the OTLP JSON encoding, which uses lowerCamelCase field
+/// names, hex trace IDs, and strings for 64-bit integers.
+mod json {
+    use super::*;
+
+    fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+        value
+            .get(key)
+            .and_then(Value::as_array)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn string(value: &Value, key: &str) -> Option<String> {
+        value.get(key).and_then(Value::as_str).map(str::to_string)
+    }
+
+    /// An integer written as a number or a string.
+    fn uint(value: &Value, key: &str) -> u64 {
+        match value.get(key) {
+            Some(Value::Number(n)) => n.as_u64().unwrap_or(0),
+            Some(Value::String(s)) => s.parse().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    pub(super) fn resource_logs(root: &Value, out: &mut Vec<OtlpLog>) {
+        for resource_logs in array(root, "resourceLogs") {
+            let service = resource_service(resource_logs);
+            for scope_logs in array(resource_logs, "scopeLogs") {
+                let scope = scope_name(scope_logs);
+                for record in array(scope_logs, "logRecords") {
+                    let log = RawLog {
+                        time_nanos: uint(record, "timeUnixNano"),
+                        observed_nanos: uint(record, "observedTimeUnixNano"),
+                        severity_number: uint(record, "severityNumber") as i64,
+                        severity_text: string(record, "severityText"),
+                        body: record.get("body").and_then(any_value),
+                        attributes: attributes(record),
+                    };
+                    out.push(log.finish(service.as_deref(), scope.as_deref()));
+                }
+            }
+        }
+    }
+
+    pub(super) fn resource_spans(root: &Value, out: &mut Vec<OtlpSpan>) {
+        for resource_spans in array(root, "resourceSpans") {
+            let service = resource_service(resource_spans);
+            for scope_spans in array(resource_spans, "scopeSpans") {
+                let scope = scope_name(scope_spans);
+                for span in array(scope_spans, "spans") {
+                    let status = span.get("status").unwrap_or(&Value::Null);
+                    let status_code = match status.get("code") {
+                        Some(Value::String(code)) if code == "STATUS_CODE_ERROR" => 2,
+                        Some(_) => uint(status, "code") as i64,
+                        None => 0,
+                    };
+                    let raw = RawSpan {
+                        trace_id: string(span, "traceId").unwrap_or_default(),
+                        name: string(span, "name").unwrap_or_default(),
+                        end_nanos: uint(span, "endTimeUnixNano"),
+                        status_code,
+                        status_message: string(status, "message"),
+                        exceptions: array(span, "events")
+                            .iter()
+                            .filter(|e| e.get("name").and_then(Value::as_str) == Some("exception"))
+                            .map(attributes)
+                            .collect(),
+                    };
+                    out.push(raw.finish(service.as_deref(), scope.as_deref()));
+                }
+            }
+        }
+    }
+
+    fn resource_service(value: &Value) -> Option<String> {
+        let resource = value.get("resource")?;
+        attributes(resource)
+            .into_iter()
+            .find(|(k, _)| k == "service.name")
+            .map(|(_, v)| v)
+    }
+
+    fn scope_name(value: &Value) -> Option<String> {
+        value
+            .get("scope")
+            .and_then(|s| string(s, "name"))
+            .filter(|n| !n.is_empty())
+    }
+
+    fn attributes(value: &Value) -> Vec<(String, String)> {
+        array(value, "attributes")
+            .iter()
+            .filter_map(|kv| {
+                let key = string(kv, "key")?;
+                let value = kv.get("value").and_then(any_value).unwrap_or_default();
+                Some((key, value))
+            })
+            .collect()
+    }
+
+    fn any_value(value: &Value) -> Option<String> {
+        if let Some(s) = value.get("stringValue").and_then(Value::as_str) {
+            return Some(s.to_string());
+        }
+        ["boolValue", "intValue", "doubleValue"]
+            .iter()
+            .find_map(|key| value.get(*key))
+            .map(|v| match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+    }
+}
+
+// ─── Log Lines ──────────────────────────────────────────────────────────
+
+/// Indent of the message and exception lines under a console log header.
+const INDENT: &str = "      ";
+
+/// A log record as console log lines: a `fail: Category[id]` header, then
+/// its message and exception indented below.
+pub fn log_lines(log: &OtlpLog) -> Vec<String> {
+    let level = match log.severity {
+        Severity::Error => "fail",
+        Severity::Warn => "warn",
+        Severity::Info => "info",
+        Severity::Debug => "dbug",
+    };
+    let category = category(log.scope.as_deref(), log.service.as_deref());
+    let mut lines = vec![format!(
+        "{level}: {category}[{}]",
+        log.event_id.unwrap_or(0)
+    )];
+    lines.extend(log.message.lines().map(|l| format!("{INDENT}{l}")));
+    push_exception(&mut lines, log.exception.as_ref());
+    lines
+}
+
+/// A failed span as console log lines, naming the span and its trace.
+pub fn span_lines(span: &OtlpSpan) -> Vec<String> {
+    let category = category(span.scope.as_deref(), span.service.as_deref());
+    let outcome = match span.error.as_deref() {
+        Some(message) if !message.is_empty() => format!("failed: {message}"),
+        _ => "failed".to_string(),
+    };
+    let mut lines = vec![
+        format!("fail: {category}[0]"),
+        format!("{INDENT}{} {outcome} (trace {})", span.name, span.trace_id),
+    ];
+    push_exception(&mut lines, span.exception.as_ref());
+    lines
+}
+
+fn category<'a>(scope: Option<&'a str>, service: Option<&'a str>) -> &'a str {
+    scope.or(service).unwrap_or("otlp")
+}
+
+fn push_exception(lines: &mut Vec<String>, exception: Option<&OtlpException>) {
+    let Some(exception) = exception else { return };
+    // A .NET stack trace starts with the type and message
+    if let Some(ref trace) = exception.stacktrace {
+        lines.extend(trace.lines().map(|l| format!("{INDENT}{l}")));
+        return;
+    }
+    let heading = match (&exception.kind, &exception.message) {
+        (Some(kind), Some(message)) => format!("{kind}: {message}"),
+        (Some(text), None) | (None, Some(text)) => text.clone(),
+        (None, None) => return,
+    };
+    lines.push(format!("{INDENT}{heading}"));
+}
+
+// ─── Receiver ───────────────────────────────────────────────────────────
+
+/// Listen for OTLP/HTTP on `port` of the loopback interface.
+pub async fn bind(port: u16) -> Result<TcpListener> {
+    TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| OrchestratorError::Telemetry(format!("bind port {port}: {e}")))
+}
+
+/// Receive OTLP/HTTP exports for `slot_name` on `listener` until the task
+/// is aborted. Log records and failed spans are sent to `log_tx`, and the
+/// slot's [`TelemetrySummary`] in `slots` is kept up to date.
+pub fn serve(
+    listener: TcpListener,
+    slot_name: String,
+    slots: Arc<RwLock<Vec<Slot>>>,
+    log_tx: mpsc::UnboundedSender<LogLine>,
+) -> tokio::task::JoinHandle<()> {
+    let (export_tx, export_rx) = mpsc::unbounded_channel();
+    // Exports are recorded in arrival order by one task, so lines of
+    // concurrent requests don't interleave
+    tokio::spawn(record(slot_name, slots, log_tx, export_rx));
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(handle_connection(stream, export_tx.clone()));
+                }
+                Err(e) => {
+                    tracing::warn!("telemetry receiver accept failed: {e}");
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                }
+            }
+        }
+    })
+}
+
+async fn record(
+    slot_name: String,
+    slots: Arc<RwLock<Vec<Slot>>>,
+    log_tx: mpsc::UnboundedSender<LogLine>,
+    mut exports: mpsc::UnboundedReceiver<Export>,
+) {
+    while let Some(export) = exports.recv().await {
+        let send = |lines: Vec<String>, time: DateTime<Utc>| {
+            for line in lines {
+                let _ = log_tx.send(LogLine {
+                    slot_name: slot_name.clone(),
+                    source: LogSource::Telemetry,
+                    line,
+                    timestamp: time,
+                });
+            }
+        };
+        for log in &export.logs {
+            send(log_lines(log), log.time);
+        }
+        for span in export.spans.iter().filter(|s| s.failed()) {
+            send(span_lines(span), span.time);
+        }
+
+        let mut slots = slots.write().await;
+        let Some(slot) = slots.iter_mut().find(|s| s.name == slot_name) else {
+            continue;
+        };
+        let summary = slot.telemetry.get_or_insert_with(TelemetrySummary::default);
+        summary.logs += export.logs.len() as u64;
+        summary.spans += export.spans.len() as u64;
+        for span in export.spans.into_iter().filter(OtlpSpan::failed) {
+            summary.push_error(ErrorSpan {
+                time: span.time,
+                service: span.service,
+                name: span.name,
+                message: span.error.filter(|m| !m.is_empty()),
+                trace_id: span.trace_id,
+            });
+        }
+    }
+}
+
+/// An HTTP request, read as far as the receiver needs.
+struct Request {
+    method: String,
+    path: String,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+
+    fn keep_alive(&self) -> bool {
+        !self
+            .header("connection")
+            .is_some_and(|c| c.eq_ignore_ascii_case("close"))
+    }
+}
+
+async fn handle_connection(stream: TcpStream, exports: mpsc::UnboundedSender<Export>) {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+    loop {
+        let request = match read_request(&mut reader).await {
+            Ok(Some(request)) => request,
+            Ok(None) => return,
+            Err(e) => {
+                let _ = respond(&mut write, 400, "text/plain", e.to_string().as_bytes()).await;
+                return;
+            }
+        };
+        let (status, content_type, body) = match handle_request(&request) {
+            Ok((export, content_type)) => {
+                if !export.logs.is_empty() || !export.spans.is_empty() {
+                    let _ = exports.send(export);
+                }
+                // An empty Export*ServiceResponse means full success
+                let body: &[u8] = if content_type == "application/json" {
+                    b"{}"
+                } else {
+                    b""
+                };
+                (200, content_type, body.to_vec())
+            }
+            Err((status, message)) => (status, "text/plain", message.into_bytes()),
+        };
+        if respond(&mut write, status, content_type, &body)
+            .await
+            .is_err()
+            || !request.keep_alive()
+        {
+            return;
+        }
+    }
+}
+
+/// Decode an export request, or give the HTTP status and message to refuse it with.
+fn handle_request(request: &Request) -> std::result::Result<(Export, &'static str), (u16, String)> {
+    let signal = match request.path.split('?').next().unwrap_or_default() {
+        "/v1/logs" => Some(Signal::Logs),
+        "/v1/traces" => Some(Signal::Traces),
+        "/v1/metrics" => None,
+        path => return Err((404, format!("no OTLP endpoint at {path}"))),
+    };
+    if request.method != "POST" {
+        return Err((405, "OTLP exports must be POSTed".into()));
+    }
+    if let Some(encoding) = request.header("content-encoding") {
+        if !encoding.eq_ignore_ascii_case("identity") {
+            return Err((415, format!("content encoding '{encoding}' not supported")));
+        }
+    }
+    let content_type = request
+        .header("content-type")
+        .and_then(|t| t.split(';').next())
+        .map(|t| t.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    let (json, response_type) = match content_type.as_str() {
+        "application/x-protobuf" | "application/protobuf" => (false, "application/x-protobuf"),
+        "application/json" => (true, "application/json"),
+        other => return Err((415, format!("content type '{other}' not supported"))),
+    };
+    let Some(signal) = signal else {
+        // Metrics are accepted so exporters don't retry, but not kept
+        return Ok((Export::default(), response_type));
+    };
+    let decoded = if json {
+        decode_json(signal, &request.body)
+    } else {
+        decode_protobuf(signal, &request.body)
+    };
+    decoded
+        .map(|export| (export, response_type))
+        .map_err(|e| (400, e.to_string()))
+}
+
+/// Read the next request on a connection, or `None` once the client closes it.
+async fn read_request<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<Request>> {
+    let Some(request_line) = read_header_line(reader).await? else {
+        return Ok(None);
+    };
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(OrchestratorError::Telemetry(format!(
+            "bad request line '{request_line}'"
+        )));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut headers = HashMap::new();
+    loop {
+        let line = read_header_line(reader)
+            .await?
+            .ok_or_else(|| OrchestratorError::Telemetry("headers cut short".into()))?;
+        if line.is_empty() {
+            break;
+        }
+        if headers.len() == MAX_HEADERS {
+            return Err(OrchestratorError::Telemetry(format!(
+                "more than {MAX_HEADERS} headers"
+            )));
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let chunked = headers
+        .get("transfer-encoding")
+        .is_some_and(|t| t.to_ascii_lowercase().contains("chunked"));
+    let body = if chunked {
+        read_chunked(reader).await?
+    } else {
+        let len: usize = match headers.get("content-length") {
+            Some(len) => len
+                .parse()
+                .map_err(|_| OrchestratorError::Telemetry(format!("bad content length '{len}'")))?,
+            None => 0,
+        };
+        if len > MAX_BODY {
+            return Err(OrchestratorError::Telemetry(format!(
+                "body of {len} bytes is too large"
+            )));
+        }
+        let mut body = vec![0; len];
+        reader.read_exact(&mut body).await?;
+        body
+    };
+    Ok(Some(Request {
+        method,
+        path,
+        headers,
+        body,
+    }))
+}
+
+async fn read_chunked<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let size_line = read_header_line(reader)
+            .await?
+            .ok_or_else(|| OrchestratorError::Telemetry("chunked body cut short".into()))?;
+        let size_hex = size_line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size_hex, 16)
+            .map_err(|_| OrchestratorError::Telemetry(format!("bad chunk size '{size_hex}'")))?;
+        if size == 0 {
+            // Trailers, up to the blank line
+            while read_header_line(reader)
+                .await?
+                .is_some_and(|l| !l.is_empty())
+            {}
+            return Ok(body);
+        }
+        if body
+            .len()
+            .checked_add(size)
+            .is_none_or(|len| len > MAX_BODY)
+        {
+            return Err(OrchestratorError::Telemetry(
+                "chunked body is too large".into(),
+            ));
+        }
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..]).await?;
+        read_header_line(reader).await?;
+    }
+}
+
+/// One CRLF-terminated line, without the terminator, or `None` at end of stream.
+async fn read_header_line<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<String>> {
+    let mut line = Vec::new();
+    let read = (&mut *reader)
+        .take(MAX_HEADER_LINE as u64)
+        .read_until(b'\n', &mut line)
+        .await?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if !line.ends_with(b"\n") {
+        return Err(OrchestratorError::Telemetry("header line too long".into()));
+    }
+    let line = String::from_utf8_lossy(&line);
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+async fn respond<W: AsyncWrite + Unpin>(
+    write: &mut W,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Unsupported Media Type",
+    };
+    let head = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\r\n",
+        body.len()
+    );
+    write.write_all(head.as_bytes()).await?;
+    write.write_all(body).await?;
+    write.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A minimal OTLP client: encodes export requests as protobuf and posts
+    /// them over HTTP/1.1.
+    mod client {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpStream;
+
+        fn varint(out: &mut Vec<u8>, mut value: u64) {
+            while value >= 0x80 {
+                out.push((value as u8) | 0x80);
+                value >>= 7;
+            }
+            out.push(value as u8);
+        }
+
+        pub fn bytes(out: &mut Vec<u8>, field: u32, value: &[u8]) {
+            varint(out, u64::from(field) << 3 | 2);
+            varint(out, value.len() as u64);
+            out.extend_from_slice(value);
+        }
+
+        pub fn uint(out: &mut Vec<u8>, field: u32, value: u64) {
+            varint(out, u64::from(field) << 3);
+            varint(out, value);
+        }
+
+        pub fn fixed64(out: &mut Vec<u8>, field: u32, value: u64) {
+            varint(out, u64::from(field) << 3 | 1);
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+
+        fn message(build: impl FnOnce(&mut Vec<u8>)) -> Vec<u8> {
+            let mut out = Vec::new();
+            build(&mut out);
+            out
+        }
+
+        pub fn string_attribute(key: &str, value: &str) -> Vec<u8> {
+            message(|m| {
+                bytes(m, 1, key.as_bytes());
+                bytes(m, 2, &message(|v| bytes(v, 1, value.as_bytes())));
+            })
+        }
+
+        /// A `Resource` named `service`, with one scope holding `items`
+        /// (log records or spans).
+        pub fn export(service: &str, scope: &str, items: &[Vec<u8>]) -> Vec<u8> {
+            let resource = message(|r| bytes(r, 1, &string_attribute("service.name", service)));
+            let scoped = message(|s| {
+                bytes(s, 1, &message(|i| bytes(i, 1, scope.as_bytes())));
+                for item in items {
+                    bytes(s, 2, item);
+                }
+            });
+            let resource_items = message(|r| {
+                bytes(r, 1, &resource);
+                bytes(r, 2, &scoped);
+            });
+            message(|e| bytes(e, 1, &resource_items))
+        }
+
+        pub fn log_record(
+            nanos: u64,
+            severity: u64,
+            body: &str,
+            attributes: &[Vec<u8>],
+        ) -> Vec<u8> {
+            message(|l| {
+                fixed64(l, 1, nanos);
+                uint(l, 2, severity);
+                bytes(l, 5, &message(|v| bytes(v, 1, body.as_bytes())));
+                for attribute in attributes {
+                    bytes(l, 6, attribute);
+                }
+            })
+        }
+
+        pub fn span(trace_id: &[u8], name: &str, end_nanos: u64, error: Option<&str>) -> Vec<u8> {
+            message(|s| {
+                bytes(s, 1, trace_id);
+                bytes(s, 5, name.as_bytes());
+                fixed64(s, 8, end_nanos);
+                if let Some(status_message) = error {
+                    bytes(
+                        s,
+                        15,
+                        &message(|st| {
+                            bytes(st, 2, status_message.as_bytes());
+                            uint(st, 3, 2);
+                        }),
+                    );
+                }
+            })
+        }
+
+        /// POST `body` and return the response status line.
+        pub async fn post(port: u16, path: &str, content_type: &str, body: &[u8]) -> String {
+            let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+            let head = format!(
+                "POST {path} HTTP/1.1\r\nHost: localhost\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(head.as_bytes()).await.unwrap();
+            stream.write_all(body).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response.lines().next().unwrap_or_default().to_string()
+        }
+    }
+
+    const NANOS: u64 = 1_773_480_600_000_000_000;
+
+    fn failed_order_log() -> Vec<u8> {
+        client::log_record(
+            NANOS,
+            17,
+            "Order 7 could not be saved",
+            &[
+                client::string_attribute("exception.type", "System.InvalidOperationException"),
+                client::string_attribute("exception.message", "Sequence contains no elements"),
+            ],
+        )
+    }
+
+    #[test]
+    fn decodes_protobuf_logs() {
+        let body = client::export("api", "Api.Orders.OrderService", &[failed_order_log()]);
+        let export = decode_protobuf(Signal::Logs, &body).unwrap();
+        assert_eq!(
+            export.logs,
+            [OtlpLog {
+                time: DateTime::from_timestamp_nanos(NANOS as i64),
+                service: Some("api".into()),
+                scope: Some("Api.Orders.OrderService".into()),
+                severity: Severity::Error,
+                event_id: None,
+                message: "Order 7 could not be saved".into(),
+                exception: Some(OtlpException {
+                    kind: Some("System.InvalidOperationException".into()),
+                    message: Some("Sequence contains no elements".into()),
+                    stacktrace: None,
+                }),
+            }]
+        );
+        assert_eq!(
+            log_lines(&export.logs[0]),
+            [
+                "fail: Api.Orders.OrderService[0]",
+                "      Order 7 could not be saved",
+                "      System.InvalidOperationException: Sequence contains no elements",
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_protobuf_spans() {
+        let trace_id = [0xab; 16];
+        let body = client::export(
+            "api",
+            "Microsoft.AspNetCore",
+            &[
+                client::span(&trace_id, "GET /orders", NANOS, None),
+                client::span(
+                    &trace_id,
+                    "POST /orders",
+                    NANOS,
+                    Some("Sequence contains no elements"),
+                ),
+            ],
+        );
+        let export = decode_protobuf(Signal::Traces, &body).unwrap();
+        assert_eq!(export.spans.len(), 2);
+        assert!(!export.spans[0].failed());
+        let failed = &export.spans[1];
+        assert_eq!(failed.trace_id, "ab".repeat(16));
+        assert_eq!(
+            span_lines(failed),
+            [
+                "fail: Microsoft.AspNetCore[0]".to_string(),
+                format!(
+                    "      POST /orders failed: Sequence contains no elements (trace {})",
+                    "ab".repeat(16)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_json_exports() {
+        let logs = br#"{"resourceLogs":[{"resource":{"attributes":[{"key":"service.name","value":{"stringValue":"web"}}]},
+            "scopeLogs":[{"scope":{"name":"Web.Cart"},"logRecords":[{"timeUnixNano":"1773480600000000000",
+            "severityNumber":13,"body":{"stringValue":"Cart is slow"},
+            "attributes":[{"key":"event.id","value":{"intValue":"42"}}]}]}]}]}"#;
+        let export = decode_json(Signal::Logs, logs).unwrap();
+        assert_eq!(export.logs[0].severity, Severity::Warn);
+        assert_eq!(export.logs[0].event_id, Some(42));
+        assert_eq!(log_lines(&export.logs[0])[0], "warn: Web.Cart[42]");
+
+        let traces = br#"{"resourceSpans":[{"scopeSpans":[{"spans":[{"traceId":"5b8efff798038103d269b633813fc60c",
+            "name":"checkout","endTimeUnixNano":"1773480600000000000","status":{"code":2},
+            "events":[{"name":"exception","attributes":[{"key":"exception.message","value":{"stringValue":"boom"}}]}]}]}]}]}"#;
+        let export = decode_json(Signal::Traces, traces).unwrap();
+        assert_eq!(export.spans[0].error.as_deref(), Some("boom"));
+        assert_eq!(export.spans[0].scope, None);
+    }
+
+    #[test]
+    fn truncated_protobuf_is_an_error() {
+        let body = client::export("api", "Api", &[failed_order_log()]);
+        assert!(decode_protobuf(Signal::Logs, &body[..body.len() - 3]).is_err());
+    }
+
+    #[tokio::test]
+    async fn oversized_requests_are_refused() {
+        let mut many_headers = String::from("POST /v1/logs HTTP/1.1\r\n");
+        for i in 0..=MAX_HEADERS {
+            many_headers.push_str(&format!("x-{i}: a\r\n"));
+        }
+        many_headers.push_str("\r\n");
+        let err = read_request(&mut many_headers.as_bytes()).await.err();
+        assert!(err.is_some_and(|e| e.to_string().contains("headers")));
+
+        // A second chunk whose size would overflow the running total
+        let chunked = format!(
+            "POST /v1/logs HTTP/1.1\r\ntransfer-encoding: chunked\r\n\r\n1\r\na\r\n{:x}\r\n",
+            usize::MAX
+        );
+        let err = read_request(&mut chunked.as_bytes()).await.err();
+        assert!(err.is_some_and(|e| e.to_string().contains("too large")));
+    }
+
+    #[tokio::test]
+    async fn receiver_records_exports_on_the_slot() {
+        let slot = Slot::new(
+            "api".into(),
+            "/repo".into(),
+            "feature".into(),
+            "/clone".into(),
+        );
+        let slots = Arc::new(RwLock::new(vec![slot]));
+        let (log_tx, mut log_rx) = mpsc::unbounded_channel();
+        let listener = bind(0).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let receiver = serve(listener, "api".into(), slots.clone(), log_tx);
+
+        let logs = client::export("api", "Api.Orders.OrderService", &[failed_order_log()]);
+        let status = client::post(port, "/v1/logs", "application/x-protobuf", &logs).await;
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        let spans = client::export(
+            "api",
+            "Microsoft.AspNetCore",
+            &[client::span(&[1; 16], "POST /orders", NANOS, Some("boom"))],
+        );
+        let status = client::post(port, "/v1/traces", "application/x-protobuf", &spans).await;
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        let status = client::post(port, "/v1/logs", "text/plain", b"hello").await;
+        assert_eq!(status, "HTTP/1.1 415 Unsupported Media Type");
+        let status = client::post(port, "/v1/logs", "application/x-protobuf", &[0xff]).await;
+        assert_eq!(status, "HTTP/1.1 400 Bad Request");
+
+        let mut lines = Vec::new();
+        for _ in 0..5 {
+            let line = log_rx.recv().await.unwrap();
+            assert_eq!(line.source, LogSource::Telemetry);
+            lines.push(line.line);
+        }
+        assert_eq!(lines[0], "fail: Api.Orders.OrderService[0]");
+        assert!(lines[4].starts_with("      POST /orders failed: boom"));
+
+        let summary = slots.read().await[0].telemetry.clone().unwrap();
+        assert_eq!((summary.logs, summary.spans), (1, 1));
+        assert_eq!(summary.error_spans[0].name, "POST /orders");
+        assert_eq!(summary.error_spans[0].service.as_deref(), Some("api"));
+        receiver.abort();
+    }
+
+    #[tokio::test]
+    async fn captured_resources_export_to_the_receiver() {
+        let slots = Arc::new(RwLock::new(vec![Slot::new(
+            "api".into(),
+            "/repo".into(),
+            "feature".into(),
+            "/clone".into(),
+        )]));
+        let (log_tx, mut log_rx) = mpsc::unbounded_channel();
+        let listener = bind(0).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let receiver = serve(listener, "api".into(), slots, log_tx);
+
+        // Export where the AppHost's environment tells a resource to
+        let allocations = [crate::models::PortAllocation {
+            name: OTLP_PORT_NAME.into(),
+            port,
+        }];
+        let telemetry = crate::models::TelemetryConfig {
+            enabled: true,
+            capture_resources: true,
+        };
+        let vars = crate::services::aspire::build_env_vars(&allocations, &telemetry);
+        let endpoint = vars
+            .iter()
+            .find(|(name, _)| name == "OTEL_EXPORTER_OTLP_LOGS_ENDPOINT")
+            .map(|(_, value)| value.as_str())
+            .unwrap();
+        let (authority, path) = endpoint
+            .strip_prefix("http://")
+            .and_then(|rest| rest.split_once('/'))
+            .unwrap();
+        assert_eq!(authority, format!("127.0.0.1:{port}"));
+
+        let logs = client::export("api", "Api.Orders.OrderService", &[failed_order_log()]);
+        let status = client::post(port, &format!("/{path}"), "application/x-protobuf", &logs).await;
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        let line = log_rx.recv().await.unwrap();
+        assert_eq!(line.line, "fail: Api.Orders.OrderService[0]");
+        receiver.abort();
+    }
+}
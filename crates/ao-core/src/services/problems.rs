@@ -0,0 +1,289 @@
+//! Collects the compiler and MSBuild diagnostics of each slot's builds from
+//! its Aspire and setup command output, so a broken build is listed on the
+//! slot instead of being buried in the log.
+//!
+//! A slot's problems are those of its last build. The list is replaced as a
+//! new build reports diagnostics, and cut down to warnings once a build
+//! succeeds.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, RwLock};
+
+use crate::models::{Problem, ProblemSeverity, Slot};
+
+use super::log_parser::{Diagnostic, LogFormat, LogParser, ParsedLine, Severity};
+use super::log_tailer::{LogLine, LogSource};
+
+/// Follows the builds in one slot's output.
+#[derive(Debug, Default)]
+pub struct ProblemCollector {
+    /// Diagnostics of the build in progress.
+    building: Vec<Problem>,
+    /// The slot's problem list.
+    problems: Vec<Problem>,
+    /// Set after a build result, while MSBuild repeats the build's
+    /// diagnostics in its summary.
+    in_summary: bool,
+}
+
+impl ProblemCollector {
+    /// Start from the problems the slot already has.
+    pub fn new(problems: Vec<Problem>) -> Self {
+        Self {
+            problems,
+            ..Self::default()
+        }
+    }
+
+    /// Take note of the next line of output. Returns the new problem list
+    /// when it changed.
+    pub fn observe(&mut self, parsed: &ParsedLine) -> Option<Vec<Problem>> {
+        if let Some(diagnostic) = &parsed.diagnostic {
+            if self.in_summary {
+                return None;
+            }
+            let problem = to_problem(parsed, diagnostic);
+            if self.building.contains(&problem) {
+                return None;
+            }
+            self.building.push(problem);
+            return self.publish(self.building.clone());
+        }
+        if parsed.format == LogFormat::Plain && parsed.message.is_empty() {
+            return None;
+        }
+        self.in_summary = false;
+
+        let succeeded = match parsed.build_succeeded() {
+            Some(succeeded) => {
+                self.in_summary = true;
+                succeeded
+            }
+            // `dotnet run` says nothing after a good build, but the app
+            // logging shows it built
+            None if parsed.format == LogFormat::Console
+                && !parsed.continuation
+                && (!self.building.is_empty() || self.problems.iter().any(Problem::is_error)) =>
+            {
+                true
+            }
+            None => return None,
+        };
+        let mut problems = std::mem::take(&mut self.building);
+        if succeeded {
+            problems.retain(|p| !p.is_error());
+        }
+        self.publish(problems)
+    }
+
+    fn publish(&mut self, problems: Vec<Problem>) -> Option<Vec<Problem>> {
+        if problems == self.problems {
+            return None;
+        }
+        self.problems = problems;
+        Some(self.problems.clone())
+    }
+}
+
+fn to_problem(parsed: &ParsedLine, diagnostic: &Diagnostic) -> Problem {
+    Problem {
+        severity: if parsed.severity == Severity::Error {
+            ProblemSeverity::Error
+        } else {
+            ProblemSeverity::Warning
+        },
+        code: diagnostic.code.clone(),
+        file: diagnostic.file.clone(),
+        line: diagnostic.line,
+        column: diagnostic.column,
+        message: parsed.message.clone(),
+        project: diagnostic.project.clone(),
+    }
+}
+
+/// Record the problems in Aspire and setup command output sent to the
+/// returned sender on `slots`, then pass each line on to `tx`. Slots are
+/// updated in memory and saved with their next change.
+pub fn collect(
+    slots: Arc<RwLock<Vec<Slot>>>,
+    tx: mpsc::UnboundedSender<LogLine>,
+) -> mpsc::UnboundedSender<LogLine> {
+    let (collect_tx, mut collect_rx) = mpsc::unbounded_channel::<LogLine>();
+    let forward_tx = tx.clone();
+    let spawned = std::thread::Builder::new()
+        .name("problems".into())
+        .spawn(move || {
+            let mut collectors: HashMap<String, (LogParser, ProblemCollector)> = HashMap::new();
+            while let Some(line) = collect_rx.blocking_recv() {
+                if line.source == LogSource::Aspire {
+                    let (parser, collector) =
+                        collectors.entry(line.slot_name.clone()).or_insert_with(|| {
+                            let existing = slots
+                                .blocking_read()
+                                .iter()
+                                .find(|s| s.name == line.slot_name)
+                                .map(|s| s.problems.clone())
+                                .unwrap_or_default();
+                            (LogParser::new(), ProblemCollector::new(existing))
+                        });
+                    let parsed = parser.parse(line.source, &line.line);
+                    if let Some(problems) = collector.observe(&parsed) {
+                        let mut slots = slots.blocking_write();
+                        match slots.iter_mut().find(|s| s.name == line.slot_name) {
+                            Some(slot) => slot.problems = problems,
+                            // Destroyed; a new slot by that name starts over
+                            None => {
+                                collectors.remove(&line.slot_name);
+                            }
+                        }
+                    }
+                }
+                let _ = forward_tx.send(line);
+            }
+        });
+    match spawned {
+        Ok(_) => collect_tx,
+        Err(e) => {
+            tracing::warn!("build problems disabled: {e}");
+            drop(collect_tx);
+            tx
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use super::super::log_parser::parse_line;
+
+    fn run(collector: &mut ProblemCollector, lines: &[&str]) -> Option<Vec<Problem>> {
+        let mut parser = LogParser::new();
+        let mut last = None;
+        for line in lines {
+            if let Some(problems) = collector.observe(&parser.parse(LogSource::Aspire, line)) {
+                last = Some(problems);
+            }
+        }
+        last
+    }
+
+    const FAILED_BUILD: &[&str] = &[
+        "  Determining projects to restore...",
+        "/src/Api/Orders.cs(12,5): error CS1002: ; expected [/src/Api/Api.csproj]",
+        "/src/Api/Orders.cs(30,9): warning CS0168: The variable 'e' is declared but never used [/src/Api/Api.csproj]",
+        "/src/Api/Orders.cs(12,5): error CS1002: ; expected [/src/Api/Api.csproj]",
+        "",
+        "Build FAILED.",
+        "",
+        "/src/Api/Orders.cs(30,9): warning CS0168: The variable 'e' is declared but never used [/src/Api/Api.csproj]",
+        "/src/Api/Orders.cs(12,5): error CS1002: ; expected [/src/Api/Api.csproj]",
+        "    1 Warning(s)",
+        "    1 Error(s)",
+    ];
+
+    #[test]
+    fn failed_build_lists_each_diagnostic_once() {
+        let mut collector = ProblemCollector::default();
+        let problems = run(&mut collector, FAILED_BUILD).unwrap();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(
+            problems[0],
+            Problem {
+                severity: ProblemSeverity::Error,
+                code: "CS1002".into(),
+                file: Some("/src/Api/Orders.cs".into()),
+                line: Some(12),
+                column: Some(5),
+                message: "; expected".into(),
+                project: Some("/src/Api/Api.csproj".into()),
+            }
+        );
+        assert_eq!(
+            problems[0].location().as_deref(),
+            Some("/src/Api/Orders.cs(12,5)")
+        );
+        assert_eq!(problems[1].severity, ProblemSeverity::Warning);
+    }
+
+    #[test]
+    fn successful_rebuild_keeps_only_warnings() {
+        let mut collector = ProblemCollector::default();
+        run(&mut collector, FAILED_BUILD);
+        let problems = run(
+            &mut collector,
+            &[
+                "/src/Api/Orders.cs(30,9): warning CS0168: The variable 'e' is declared but never used [/src/Api/Api.csproj]",
+                "Build succeeded.",
+            ],
+        )
+        .unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].code, "CS0168");
+
+        let cleared = run(&mut collector, &["Build succeeded.", "    0 Warning(s)"]).unwrap();
+        assert!(cleared.is_empty());
+    }
+
+    #[test]
+    fn a_new_build_replaces_the_old_problems() {
+        let mut collector = ProblemCollector::default();
+        run(&mut collector, FAILED_BUILD);
+        let problems = run(
+            &mut collector,
+            &[
+                "/src/Web/Home.razor(3,1): error RZ9980: Unclosed tag 'div' with no matching end tag. [/src/Web/Web.csproj]",
+                "The build failed. Fix the build errors and run again.",
+            ],
+        )
+        .unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].code, "RZ9980");
+    }
+
+    #[test]
+    fn app_logging_after_dotnet_run_clears_errors() {
+        let mut collector = ProblemCollector::default();
+        run(&mut collector, FAILED_BUILD);
+        let cleared = run(
+            &mut collector,
+            &[
+                "info: Aspire.Hosting.DistributedApplication[0]",
+                "      Aspire version: 9.1.0",
+            ],
+        )
+        .unwrap();
+        assert!(cleared.is_empty());
+
+        // Further logging has nothing to clear
+        let line = parse_line(
+            LogSource::Aspire,
+            "info: Aspire.Hosting.DistributedApplication[0]",
+        );
+        assert_eq!(collector.observe(&line), None);
+    }
+
+    #[tokio::test]
+    async fn collected_problems_are_stored_on_the_slot() {
+        let slot = Slot::new(
+            "api".into(),
+            "/repo".into(),
+            "feature".into(),
+            "/clone".into(),
+        );
+        let slots = Arc::new(RwLock::new(vec![slot]));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let collect_tx = collect(slots.clone(), tx);
+        for line in FAILED_BUILD {
+            collect_tx
+                .send(LogLine::new("api", LogSource::Aspire, line.to_string()))
+                .unwrap();
+        }
+        for _ in FAILED_BUILD {
+            rx.recv().await.unwrap();
+        }
+        assert_eq!(slots.read().await[0].problems.len(), 2);
+    }
+}
@@ -0,0 +1,192 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::{OrchestratorError, Result};
+use crate::models::SavedPrompt;
+
+/// Reads saved prompts from the `.slots/prompts/` directory.
+///
+/// A prompt is either a `.yaml` file with a `body` field, or a `.md` file
+/// whose text is the body, optionally preceded by a `---` front matter block
+/// holding the other fields.
+pub struct PromptLibrary {
+    prompts_dir: PathBuf,
+}
+
+impl PromptLibrary {
+    pub fn new(slots_directory: &Path) -> Self {
+        Self {
+            prompts_dir: slots_directory.join("prompts"),
+        }
+    }
+
+    pub fn prompts_dir(&self) -> &Path {
+        &self.prompts_dir
+    }
+
+    /// Load every prompt in the library, sorted by name. Files that fail to
+    /// parse are skipped with a warning so one bad file doesn't hide the rest.
+    pub async fn list(&self) -> Result<Vec<SavedPrompt>> {
+        let dir = &self.prompts_dir;
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut prompts = Vec::new();
+        let mut entries = tokio::fs::read_dir(dir)
+            .await
+            .map_err(|e| OrchestratorError::State(format!("reading prompts dir: {e}")))?;
+
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| OrchestratorError::State(format!("reading prompt entry: {e}")))?
+        {
+            let path = entry.path();
+            if prompt_stem(&path).is_none() {
+                continue;
+            }
+            match self.read(&path).await {
+                Ok(prompt) => prompts.push(prompt),
+                Err(e) => tracing::warn!(path = %path.display(), "skipping prompt: {e}"),
+            }
+        }
+
+        prompts.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(prompts)
+    }
+
+    /// Load a prompt by name.
+    pub async fn load(&self, name: &str) -> Result<SavedPrompt> {
+        for ext in ["md", "yaml", "yml"] {
+            let path = self.prompts_dir.join(format!("{name}.{ext}"));
+            if path.exists() {
+                return self.read(&path).await;
+            }
+        }
+        // The name may come from front matter rather than the file name
+        self.list()
+            .await?
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| OrchestratorError::PromptNotFound(name.to_string()))
+    }
+
+    async fn read(&self, path: &Path) -> Result<SavedPrompt> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| OrchestratorError::State(format!("reading prompt: {e}")))?;
+        let stem = prompt_stem(path).unwrap_or_default();
+        let is_markdown = path.extension().and_then(|e| e.to_str()) == Some("md");
+        parse(stem, &content, is_markdown)
+    }
+}
+
+/// The prompt name implied by a library file, or `None` for other files.
+fn prompt_stem(path: &Path) -> Option<&str> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("md" | "yaml" | "yml") => path.file_stem().and_then(|s| s.to_str()),
+        _ => None,
+    }
+}
+
+/// Parse a prompt file. `stem` names the prompt if the file doesn't.
+pub fn parse(stem: &str, content: &str, is_markdown: bool) -> Result<SavedPrompt> {
+    let mut prompt = if is_markdown {
+        let (front_matter, body) = split_front_matter(content);
+        let mut prompt: SavedPrompt = match front_matter {
+            Some(yaml) if !yaml.trim().is_empty() => {
+                serde_yaml::from_str(yaml).map_err(OrchestratorError::Yaml)?
+            }
+            _ => SavedPrompt::default(),
+        };
+        prompt.body = body.trim().to_string();
+        prompt
+    } else {
+        let mut prompt: SavedPrompt =
+            serde_yaml::from_str(content).map_err(OrchestratorError::Yaml)?;
+        prompt.body = prompt.body.trim_end().to_string();
+        prompt
+    };
+    if prompt.name.is_empty() {
+        prompt.name = stem.to_string();
+    }
+    Ok(prompt)
+}
+
+/// Split a `---` delimited front matter block off the top of a markdown file.
+fn split_front_matter(content: &str) -> (Option<&str>, &str) {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    // An unterminated block is just text
+    (None, content)
+}
+
+/// Find a prompt by name in an already-loaded list.
+pub fn find<'a>(prompts: &'a [SavedPrompt], name: &str) -> Option<&'a SavedPrompt> {
+    prompts.iter().find(|p| p.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_markdown_with_front_matter() {
+        let content = "---\ndescription: Fix what's red\nmax_turns: 20\n---\n\nFix the failing tests on {branch}.\n";
+        let prompt = parse("fix-tests", content, true).unwrap();
+        assert_eq!(prompt.name, "fix-tests");
+        assert_eq!(prompt.description.as_deref(), Some("Fix what's red"));
+        assert_eq!(prompt.max_turns, Some(20));
+        assert_eq!(prompt.body, "Fix the failing tests on {branch}.");
+    }
+
+    #[test]
+    fn test_parse_markdown_without_front_matter() {
+        let prompt = parse("review", "Review and refactor.\n\n---\nThanks", true).unwrap();
+        assert_eq!(prompt.name, "review");
+        assert_eq!(prompt.body, "Review and refactor.\n\n---\nThanks");
+    }
+
+    #[tokio::test]
+    async fn test_library_list_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = PromptLibrary::new(dir.path());
+        assert!(library.list().await.unwrap().is_empty());
+
+        std::fs::create_dir_all(library.prompts_dir()).unwrap();
+        std::fs::write(
+            library.prompts_dir().join("review.yaml"),
+            "name: review-refactor\nbody: |\n  Review {slot_name}.\nallowed_tools: Read,Grep\n",
+        )
+        .unwrap();
+        std::fs::write(library.prompts_dir().join("fix.md"), "Fix the tests.").unwrap();
+        std::fs::write(library.prompts_dir().join("notes.txt"), "ignored").unwrap();
+        std::fs::write(library.prompts_dir().join("broken.yaml"), "body: [").unwrap();
+
+        let prompts = library.list().await.unwrap();
+        let names: Vec<_> = prompts.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["fix", "review-refactor"]);
+        assert_eq!(prompts[1].body, "Review {slot_name}.");
+        assert_eq!(prompts[1].allowed_tools.as_deref(), Some("Read,Grep"));
+
+        assert_eq!(
+            library.load("review-refactor").await.unwrap().name,
+            "review-refactor"
+        );
+        assert_eq!(library.load("fix").await.unwrap().body, "Fix the tests.");
+        let err = library.load("missing").await.unwrap_err();
+        assert!(matches!(err, OrchestratorError::PromptNotFound(_)));
+    }
+}
@@ -0,0 +1,429 @@
+//! A small template engine for agent prompts.
+//!
+//! Syntax:
+//! - `{name}` substitutes a variable. Unknown names are left as-is, so literal
+//!   braces in prompts (JSON snippets, C# code) survive rendering.
+//! - `{#if name}...{else}...{/if}` renders the first branch when `name` is a
+//!   non-empty string or a non-empty list.
+//! - `{#each name}...{/each}` repeats the body once per list item. Item fields
+//!   (e.g. `{name}`, `{url}`) shadow outer variables inside the body.
+
+use std::collections::BTreeMap;
+
+use crate::error::{OrchestratorError, Result};
+use crate::models::Slot;
+
+/// A template variable: either plain text or a list of records for `{#each}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Text(String),
+    List(Vec<BTreeMap<String, String>>),
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Text(s) => !s.is_empty(),
+            Value::List(items) => !items.is_empty(),
+        }
+    }
+}
+
+/// Variables available to a template.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    vars: BTreeMap<String, Value>,
+}
+
+impl TemplateContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a text variable.
+    pub fn set(&mut self, key: &str, value: impl Into<String>) -> &mut Self {
+        self.vars.insert(key.to_string(), Value::Text(value.into()));
+        self
+    }
+
+    /// Set a list variable for use with `{#each}`.
+    pub fn set_list(&mut self, key: &str, items: Vec<BTreeMap<String, String>>) -> &mut Self {
+        self.vars.insert(key.to_string(), Value::List(items));
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.vars.get(key)
+    }
+
+    /// Build the standard context for a slot.
+    ///
+    /// Provides `slot_name`, `branch`, `base_branch`, `repo_path`, `clone_path`,
+    /// `dashboard_url`, `has_urls`, the `services` list (`name`, `url`), the
+    /// `ports` list (`name`, `port`), flat `service.<name>` / `port.<NAME>`
+    /// lookups, and `services_file` (the live services manifest). `slot_index` depends on the slot list and is set by the caller.
+    pub fn for_slot(slot: &Slot) -> Self {
+        let mut ctx = Self::new();
+        ctx.set("slot_name", &slot.name)
+            .set("branch", &slot.branch)
+            .set("base_branch", &slot.base_branch)
+            .set("repo_path", &slot.repo_path)
+            .set("clone_path", &slot.clone_path)
+            .set(
+                "services_file",
+                slot.services_manifest_path().to_string_lossy(),
+            )
+            .set(
+                "dashboard_url",
+                slot.services.dashboard_url.clone().unwrap_or_default(),
+            );
+
+        // Sort services by name so rendered prompts are stable across runs.
+        let services: BTreeMap<&String, &String> = slot.services.service_urls.iter().collect();
+        let mut service_items = Vec::new();
+        for (name, url) in services {
+            ctx.set(&format!("service.{name}"), url.as_str());
+            service_items.push(record(&[("name", name), ("url", url)]));
+        }
+        ctx.set_list("services", service_items);
+
+        let mut port_items = Vec::new();
+        for alloc in &slot.port_allocations {
+            let port = alloc.port.to_string();
+            ctx.set(&format!("port.{}", alloc.name), port.as_str());
+            port_items.push(record(&[("name", &alloc.name), ("port", &port)]));
+        }
+        ctx.set_list("ports", port_items);
+
+        let has_urls =
+            slot.services.dashboard_url.is_some() || !slot.services.service_urls.is_empty();
+        ctx.set("has_urls", if has_urls { "true" } else { "" });
+
+        ctx
+    }
+}
+
+fn record(fields: &[(&str, &str)]) -> BTreeMap<String, String> {
+    fields
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// Render a template against a context.
+pub fn render(template: &str, ctx: &TemplateContext) -> Result<String> {
+    let nodes = parse(template)?;
+    let mut out = String::with_capacity(template.len());
+    render_nodes(&nodes, ctx, &[], &mut out);
+    Ok(out)
+}
+
+// ─── Parsing ───────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Var(String),
+    If {
+        name: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        name: String,
+        body: Vec<Node>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Tag {
+    Var(String),
+    If(String),
+    Each(String),
+    Else,
+    EndIf,
+    EndEach,
+}
+
+/// An open block on the parser stack.
+enum Frame {
+    If {
+        name: String,
+        then: Vec<Node>,
+        in_else: bool,
+    },
+    Each {
+        name: String,
+    },
+}
+
+fn parse(template: &str) -> Result<Vec<Node>> {
+    // Each stack level holds (frame, nodes collected so far at that level).
+    let mut stack: Vec<(Frame, Vec<Node>)> = Vec::new();
+    let mut current: Vec<Node> = Vec::new();
+    let mut text = String::new();
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        text.push_str(&rest[..open]);
+        let after = &rest[open..];
+        let Some((tag, len)) = parse_tag(after) else {
+            // Not a tag — keep the brace as literal text.
+            text.push('{');
+            rest = &after[1..];
+            continue;
+        };
+        rest = &after[len..];
+
+        if !text.is_empty() {
+            current.push(Node::Text(std::mem::take(&mut text)));
+        }
+
+        match tag {
+            Tag::Var(name) => current.push(Node::Var(name)),
+            Tag::If(name) => {
+                let frame = Frame::If {
+                    name,
+                    then: Vec::new(),
+                    in_else: false,
+                };
+                stack.push((frame, std::mem::take(&mut current)));
+            }
+            Tag::Each(name) => {
+                stack.push((Frame::Each { name }, std::mem::take(&mut current)));
+            }
+            Tag::Else => match stack.last_mut() {
+                Some((Frame::If { then, in_else, .. }, _)) if !*in_else => {
+                    *then = std::mem::take(&mut current);
+                    *in_else = true;
+                }
+                _ => return Err(template_error("unexpected {else}")),
+            },
+            Tag::EndIf => match stack.pop() {
+                Some((
+                    Frame::If {
+                        name,
+                        then,
+                        in_else,
+                    },
+                    parent,
+                )) => {
+                    let body = std::mem::replace(&mut current, parent);
+                    let (then, otherwise) = if in_else {
+                        (then, body)
+                    } else {
+                        (body, Vec::new())
+                    };
+                    current.push(Node::If {
+                        name,
+                        then,
+                        otherwise,
+                    });
+                }
+                _ => return Err(template_error("unexpected {/if}")),
+            },
+            Tag::EndEach => match stack.pop() {
+                Some((Frame::Each { name }, parent)) => {
+                    let body = std::mem::replace(&mut current, parent);
+                    current.push(Node::Each { name, body });
+                }
+                _ => return Err(template_error("unexpected {/each}")),
+            },
+        }
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        current.push(Node::Text(text));
+    }
+
+    match stack.last() {
+        None => Ok(current),
+        Some((Frame::If { name, .. }, _)) => {
+            Err(template_error(&format!("unclosed {{#if {name}}}")))
+        }
+        Some((Frame::Each { name }, _)) => {
+            Err(template_error(&format!("unclosed {{#each {name}}}")))
+        }
+    }
+}
+
+/// Try to parse a tag at the start of `s` (which begins with `{`).
+/// Returns the tag and the number of bytes consumed.
+fn parse_tag(s: &str) -> Option<(Tag, usize)> {
+    let close = s.find('}')?;
+    let inner = &s[1..close];
+    let tag = match inner {
+        "else" => Tag::Else,
+        "/if" => Tag::EndIf,
+        "/each" => Tag::EndEach,
+        _ => {
+            if let Some(name) = inner.strip_prefix("#if ") {
+                Tag::If(valid_name(name.trim())?)
+            } else if let Some(name) = inner.strip_prefix("#each ") {
+                Tag::Each(valid_name(name.trim())?)
+            } else {
+                Tag::Var(valid_name(inner)?)
+            }
+        }
+    };
+    Some((tag, close + 1))
+}
+
+fn valid_name(name: &str) -> Option<String> {
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    if !(first.is_ascii_alphabetic() || first == '_') {
+        return None;
+    }
+    if chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')) {
+        Some(name.to_string())
+    } else {
+        None
+    }
+}
+
+fn template_error(msg: &str) -> OrchestratorError {
+    OrchestratorError::Template(msg.to_string())
+}
+
+// ─── Rendering ─────────────────────────────────────────────────────────
+
+fn render_nodes(
+    nodes: &[Node],
+    ctx: &TemplateContext,
+    scopes: &[&BTreeMap<String, String>],
+    out: &mut String,
+) {
+    for node in nodes {
+        match node {
+            Node::Text(t) => out.push_str(t),
+            Node::Var(name) => match lookup(name, ctx, scopes) {
+                Some(Value::Text(v)) => out.push_str(&v),
+                // Unknown names and lists render verbatim.
+                _ => {
+                    out.push('{');
+                    out.push_str(name);
+                    out.push('}');
+                }
+            },
+            Node::If {
+                name,
+                then,
+                otherwise,
+            } => {
+                let truthy = lookup(name, ctx, scopes).is_some_and(|v| v.is_truthy());
+                let branch = if truthy { then } else { otherwise };
+                render_nodes(branch, ctx, scopes, out);
+            }
+            Node::Each { name, body } => {
+                if let Some(Value::List(items)) = ctx.get(name) {
+                    for item in items {
+                        let mut inner: Vec<&BTreeMap<String, String>> = scopes.to_vec();
+                        inner.push(item);
+                        render_nodes(body, ctx, &inner, out);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn lookup(
+    name: &str,
+    ctx: &TemplateContext,
+    scopes: &[&BTreeMap<String, String>],
+) -> Option<Value> {
+    for scope in scopes.iter().rev() {
+        if let Some(v) = scope.get(name) {
+            return Some(Value::Text(v.clone()));
+        }
+    }
+    ctx.get(name).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PortAllocation;
+
+    fn ctx() -> TemplateContext {
+        let mut ctx = TemplateContext::new();
+        ctx.set("slot_name", "api-1")
+            .set("branch", "feature/x")
+            .set("empty", "")
+            .set_list(
+                "services",
+                vec![
+                    record(&[("name", "api"), ("url", "https://localhost:5001")]),
+                    record(&[("name", "web"), ("url", "https://localhost:5002")]),
+                ],
+            );
+        ctx
+    }
+
+    #[test]
+    fn substitutes_variables() {
+        let out = render("Slot {slot_name} on {branch}.", &ctx()).unwrap();
+        assert_eq!(out, "Slot api-1 on feature/x.");
+    }
+
+    #[test]
+    fn unknown_variables_and_literal_braces_pass_through() {
+        let out = render("{unknown} {\"a\": 1} { spaced } {", &ctx()).unwrap();
+        assert_eq!(out, "{unknown} {\"a\": 1} { spaced } {");
+    }
+
+    #[test]
+    fn if_else_branches() {
+        let t = "{#if slot_name}yes{else}no{/if}|{#if empty}yes{else}no{/if}|{#if missing}x{/if}";
+        assert_eq!(render(t, &ctx()).unwrap(), "yes|no|");
+    }
+
+    #[test]
+    fn each_iterates_with_item_scope() {
+        let t = "{#each services}{name}={url} ({slot_name})\n{/each}";
+        let out = render(t, &ctx()).unwrap();
+        assert_eq!(
+            out,
+            "api=https://localhost:5001 (api-1)\nweb=https://localhost:5002 (api-1)\n"
+        );
+    }
+
+    #[test]
+    fn nested_blocks() {
+        let t = "{#if services}{#each services}{#if url}[{name}]{/if}{/each}{/if}";
+        assert_eq!(render(t, &ctx()).unwrap(), "[api][web]");
+    }
+
+    #[test]
+    fn unbalanced_blocks_are_errors() {
+        assert!(render("{#if slot_name}open", &ctx()).is_err());
+        assert!(render("{/each}", &ctx()).is_err());
+        assert!(render("{else}", &ctx()).is_err());
+        assert!(render("{#each services}{/if}", &ctx()).is_err());
+    }
+
+    #[test]
+    fn slot_context_exposes_ports_and_services() {
+        let mut slot = Slot::new(
+            "web-2".into(),
+            "/repo".into(),
+            "feature/y".into(),
+            "/slots/web-2".into(),
+        );
+        slot.port_allocations.push(PortAllocation {
+            name: "API_PORT".into(),
+            port: 5001,
+        });
+        slot.services
+            .service_urls
+            .insert("apiservice".into(), "https://localhost:7001".into());
+        let ctx = TemplateContext::for_slot(&slot);
+        let out = render(
+            "{port.API_PORT} {service.apiservice} {#each ports}{name}:{port}{/each} {has_urls}",
+            &ctx,
+        )
+        .unwrap();
+        assert_eq!(out, "5001 https://localhost:7001 API_PORT:5001 true");
+    }
+}
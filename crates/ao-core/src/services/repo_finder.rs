@@ -44,7 +44,7 @@ async fn scan_filesystem(parent_dir: std::path::PathBuf) -> Vec<RepoCandidate> {
                 }
             }
         }
-        candidates.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        candidates.sort_by_key(|a| a.name.to_lowercase());
         candidates
     })
     .await
@@ -137,10 +137,10 @@ fn merge_candidates(local: Vec<RepoCandidate>, remote: Vec<RepoCandidate>) -> Ve
 
     // Collect locals (now with merged remote URLs) sorted alphabetically
     let mut result: Vec<RepoCandidate> = by_name.into_values().collect();
-    result.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    result.sort_by_key(|a| a.name.to_lowercase());
 
     // Append remote-only, also sorted
-    remote_only.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    remote_only.sort_by_key(|a| a.name.to_lowercase());
     result.extend(remote_only);
 
     result
@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{OrchestratorError, Result};
+use crate::models::Slot;
+
+/// Machine-readable snapshot of a slot's live endpoints, written to
+/// `.aspire-orchestrator-services.json` so running agents can pick up
+/// URL changes without being respawned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceManifest {
+    pub slot_name: String,
+    pub branch: String,
+    pub aspire_running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dashboard_url: Option<String>,
+    pub services: BTreeMap<String, String>,
+    pub ports: BTreeMap<String, u16>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ServiceManifest {
+    pub fn from_slot(slot: &Slot, aspire_running: bool) -> Self {
+        Self {
+            slot_name: slot.name.clone(),
+            branch: slot.branch.clone(),
+            aspire_running,
+            dashboard_url: slot.services.dashboard_url.clone(),
+            services: slot
+                .services
+                .service_urls
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            ports: slot
+                .port_allocations
+                .iter()
+                .map(|p| (p.name.clone(), p.port))
+                .collect(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Render as `KEY=value` lines suitable for `source` or dotenv loaders.
+    pub fn to_env(&self) -> String {
+        let mut lines = vec![format!("AO_SLOT_NAME={}", self.slot_name)];
+        if let Some(ref url) = self.dashboard_url {
+            lines.push(format!("AO_DASHBOARD_URL={url}"));
+        }
+        for (name, url) in &self.services {
+            lines.push(format!("AO_SERVICE_{}_URL={url}", env_key(name)));
+        }
+        for (name, port) in &self.ports {
+            lines.push(format!("{}={port}", env_key(name)));
+        }
+        let mut out = lines.join("\n");
+        out.push('\n');
+        out
+    }
+}
+
+/// Upper-case a name and replace anything that is not valid in an env var key.
+fn env_key(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Write the manifest (and optionally the env file) into the slot's clone.
+/// Files are written to a temp name and renamed so readers never see a partial file.
+pub async fn write(slot: &Slot, aspire_running: bool, write_env: bool) -> Result<()> {
+    let manifest = ServiceManifest::from_slot(slot, aspire_running);
+    let json = serde_json::to_string_pretty(&manifest)?;
+    write_atomic(&slot.services_manifest_path(), json.as_bytes()).await?;
+
+    if write_env {
+        write_atomic(&slot.services_env_path(), manifest.to_env().as_bytes()).await?;
+    }
+    Ok(())
+}
+
+/// Read a previously written manifest.
+pub async fn read(slot: &Slot) -> Result<ServiceManifest> {
+    let content = tokio::fs::read_to_string(slot.services_manifest_path())
+        .await
+        .map_err(|e| OrchestratorError::State(format!("reading services manifest: {e}")))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Distinguishes the temp files of overlapping writes within this process.
+static WRITE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+async fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let tmp = temp_path(path);
+    tokio::fs::write(&tmp, content)
+        .await
+        .map_err(|e| OrchestratorError::State(format!("writing {}: {e}", tmp.display())))?;
+    tokio::fs::rename(&tmp, path)
+        .await
+        .map_err(|e| OrchestratorError::State(format!("renaming {}: {e}", tmp.display())))?;
+    Ok(())
+}
+
+/// `<file name>.<pid>.<n>.tmp` beside `path`. Keeping the whole file name
+/// keeps it under the orchestrator's git exclude pattern, and the suffix
+/// gives each write its own file.
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        WRITE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PortAllocation;
+
+    fn test_slot(clone_path: &Path) -> Slot {
+        let mut slot = Slot::new(
+            "api-1".into(),
+            "/repo".into(),
+            "feature/x".into(),
+            clone_path.to_string_lossy().to_string(),
+        );
+        slot.services.dashboard_url = Some("https://localhost:15234".into());
+        slot.services
+            .service_urls
+            .insert("web-frontend".into(), "https://localhost:5002".into());
+        slot.port_allocations.push(PortAllocation {
+            name: "API_PORT".into(),
+            port: 5001,
+        });
+        slot
+    }
+
+    #[tokio::test]
+    async fn write_and_read_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let slot = test_slot(dir.path());
+        write(&slot, true, false).await.unwrap();
+
+        let manifest = read(&slot).await.unwrap();
+        assert_eq!(manifest.slot_name, "api-1");
+        assert!(manifest.aspire_running);
+        assert_eq!(
+            manifest.services.get("web-frontend").map(String::as_str),
+            Some("https://localhost:5002")
+        );
+        assert_eq!(manifest.ports.get("API_PORT"), Some(&5001));
+        assert!(!slot.services_env_path().exists());
+
+        let json = std::fs::read_to_string(slot.services_manifest_path()).unwrap();
+        assert!(json.contains("\"dashboardUrl\""));
+    }
+
+    #[tokio::test]
+    async fn write_env_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let slot = test_slot(dir.path());
+        write(&slot, true, true).await.unwrap();
+
+        let env = std::fs::read_to_string(slot.services_env_path()).unwrap();
+        assert!(env.contains("AO_SLOT_NAME=api-1\n"));
+        assert!(env.contains("AO_DASHBOARD_URL=https://localhost:15234\n"));
+        assert!(env.contains("AO_SERVICE_WEB_FRONTEND_URL=https://localhost:5002\n"));
+        assert!(env.contains("API_PORT=5001\n"));
+    }
+
+    #[tokio::test]
+    async fn manifest_files_are_ignored_by_git() {
+        let dir = tempfile::tempdir().unwrap();
+        let init = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert!(init.success());
+        // Done once when the slot is provisioned
+        crate::services::git::exclude(dir.path(), crate::models::slot::ORCHESTRATOR_FILES)
+            .await
+            .unwrap();
+        let slot = test_slot(dir.path());
+        write(&slot, true, true).await.unwrap();
+
+        let status = std::process::Command::new("git")
+            .args(["status", "--porcelain"])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&status.stdout), "");
+    }
+
+    #[tokio::test]
+    async fn overlapping_writes_keep_each_file_intact() {
+        let dir = tempfile::tempdir().unwrap();
+        let slot = test_slot(dir.path());
+        let writes: Vec<_> = (0..16)
+            .map(|i| {
+                let slot = slot.clone();
+                tokio::spawn(async move { write(&slot, i % 2 == 0, true).await })
+            })
+            .collect();
+        for handle in writes {
+            handle.await.unwrap().unwrap();
+        }
+
+        let manifest = read(&slot).await.unwrap();
+        assert_eq!(manifest.slot_name, "api-1");
+        let env = std::fs::read_to_string(slot.services_env_path()).unwrap();
+        assert!(env.starts_with("AO_"));
+        let leftovers = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter(|e| {
+                e.as_ref()
+                    .unwrap()
+                    .path()
+                    .to_string_lossy()
+                    .ends_with(".tmp")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+}
@@ -4,19 +4,38 @@ use std::sync::Arc;
 use chrono::Utc;
 use tokio::process::Child;
 use tokio::sync::{mpsc, RwLock};
+use tokio::time::Duration;
 
 use crate::error::{OrchestratorError, Result};
-use crate::models::{AgentStatus, Slot, SlotStatus};
-use crate::services::{agent_host, aspire, config_loader, discovery, git};
-
+use crate::models::slot::{ORCHESTRATOR_FILES, RESERVED_SLOT_NAMES};
+use crate::models::{
+    AgentStatus, CloneStrategy, CreationState, CreationStep, DiffTarget, Discrepancy, FileDiff,
+    GitOperation, GitStatus, OrchestratorConfig, PortAllocation, ProvisioningConfig, PullRequest,
+    RebaseOutcome, RebaseStatus, ReconcileAction, Slot, SlotStatus,
+};
+use crate::services::{agent_host, aspire, config_loader, discovery, git, otlp, service_manifest};
+
+use super::forge::{ForgeProvider, GhForge};
 use super::log_tailer::{LogLine, LogSource};
 use super::ports::PortAllocator;
 use super::state::SlotStateStore;
+use super::workspace_lock::WorkspaceLock;
 
-/// Holds a running Aspire child process and its log receiver.
+/// Holds a running Aspire child process, its log receiver, and its OTLP
+/// receiver when telemetry is enabled.
 struct AspireProcess {
     child: Child,
     _log_rx_task: tokio::task::JoinHandle<()>,
+    telemetry_task: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl AspireProcess {
+    fn abort_tasks(&self) {
+        self._log_rx_task.abort();
+        if let Some(ref task) = self.telemetry_task {
+            task.abort();
+        }
+    }
 }
 
 pub struct SlotManager {
@@ -31,30 +50,78 @@ pub struct SlotManager {
     /// Channel for log lines from file tailers (agent logs).
     agent_tailer_handles:
         Arc<RwLock<std::collections::HashMap<String, tokio::task::JoinHandle<()>>>>,
+    /// Code host used to open and poll pull requests.
+    forge: Arc<dyn ForgeProvider>,
+    /// Held once this manager owns the slots directory.
+    workspace_lock: std::sync::Mutex<Option<WorkspaceLock>>,
 }
 
 impl SlotManager {
     pub fn new(slots_directory: PathBuf, log_tx: mpsc::UnboundedSender<LogLine>) -> Self {
         let state_store = SlotStateStore::new(&slots_directory);
+        let slots = Arc::new(RwLock::new(Vec::new()));
+        let log_tx = super::problems::collect(slots.clone(), log_tx);
         Self {
-            slots: Arc::new(RwLock::new(Vec::new())),
+            slots,
             slots_directory,
             state_store,
             port_allocator: PortAllocator::new(),
             aspire_processes: Arc::new(RwLock::new(std::collections::HashMap::new())),
             log_tx,
             agent_tailer_handles: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            forge: Arc::new(GhForge),
+            workspace_lock: std::sync::Mutex::new(None),
         }
     }
 
+    /// Replace the forge provider (GitHub via `gh` by default).
+    pub fn with_forge(mut self, forge: Arc<dyn ForgeProvider>) -> Self {
+        self.forge = forge;
+        self
+    }
+
+    /// Claim the slots directory for this process until the manager is
+    /// dropped, so a second orchestrator can't write the same `state.json`.
+    pub fn lock_workspace(&self) -> Result<()> {
+        let lock = WorkspaceLock::acquire(&self.slots_directory)?;
+        *self
+            .workspace_lock
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(lock);
+        Ok(())
+    }
+
     /// Load persisted slots from state file.
     pub async fn load_state(&self) -> Result<()> {
-        let loaded = self.state_store.load().await?;
-        let mut slots = self.slots.write().await;
-        *slots = loaded;
+        let mut loaded = self.state_store.load().await?;
+        let resolved = resolve_base_branches(&mut loaded).await;
+        // Clones provisioned before the orchestrator's files were excluded
+        for slot in loaded.iter().filter(|s| s.creation.is_none()) {
+            let _ = git::exclude(Path::new(&slot.clone_path), ORCHESTRATOR_FILES).await;
+        }
+        *self.slots.write().await = loaded;
+        if resolved {
+            self.persist().await?;
+        }
         Ok(())
     }
 
+    /// What loading `state.json` had to do to recover from a damaged file,
+    /// if anything. Reported once.
+    pub fn take_state_recovery_note(&self) -> Option<String> {
+        self.state_store.take_recovery_note()
+    }
+
+    /// Reload `state.json` if something else (a hand edit, or a tool that
+    /// ignores the workspace lock) changed it. Returns whether it reloaded.
+    pub async fn reload_if_changed(&self) -> Result<bool> {
+        if !self.state_store.changed_on_disk() {
+            return Ok(false);
+        }
+        self.load_state().await?;
+        Ok(true)
+    }
+
     /// Reconnect to existing agent host processes, resetting status for missing ones.
     pub async fn reconnect_existing_sessions(&self) -> Result<()> {
         let running = agent_host::list_running(&self.slots_directory)
@@ -64,6 +131,14 @@ impl SlotManager {
 
         let mut slots = self.slots.write().await;
         for slot in slots.iter_mut() {
+            // A creation that was still running when we exited can only be retried
+            if let Some(ref mut creation) = slot.creation {
+                if !creation.failed {
+                    creation.failed = true;
+                    slot.status = SlotStatus::Error;
+                    slot.last_error = Some(format!("{}: interrupted", creation.step));
+                }
+            }
             if !running_set.contains(&slot.name) {
                 // No running agent host — reset agent status
                 if slot.agent_status == AgentStatus::Active
@@ -81,7 +156,8 @@ impl SlotManager {
             if let Some(slot) = self.get_slot(name).await {
                 let log_path = slot.agent_log_path();
                 if log_path.exists() {
-                    let handle = super::log_tailer::start_tailing(
+                    // A previous run already recorded what's in the file
+                    let handle = super::log_tailer::resume_tailing(
                         log_path,
                         name.clone(),
                         LogSource::Agent,
@@ -96,6 +172,174 @@ impl SlotManager {
         Ok(())
     }
 
+    /// Compare the slots with what is on disk: missing working copies, orphan
+    /// directories, agent host files left by exited hosts, and clones on a
+    /// different branch than recorded. Run it before
+    /// [`Self::reconnect_existing_sessions`], which removes stale agent host
+    /// files without reporting them.
+    pub async fn reconcile(&self) -> Vec<Discrepancy> {
+        let slots = self.get_slots().await;
+        let mut found = Vec::new();
+
+        for slot in &slots {
+            // An unfinished creation owns its working copy until retried
+            if slot.creation.is_some() {
+                continue;
+            }
+            let clone_path = Path::new(&slot.clone_path);
+            if !clone_path.join(".git").exists() {
+                found.push(Discrepancy::MissingClone {
+                    slot: slot.name.clone(),
+                    path: slot.clone_path.clone(),
+                });
+                continue;
+            }
+            if let Some(pid) = agent_host::stale_pid(&slot.name, &self.slots_directory).await {
+                found.push(Discrepancy::StaleAgentHost {
+                    slot: slot.name.clone(),
+                    pid,
+                });
+            }
+            let operation = git::in_progress_operation(clone_path).await;
+            if let Err(e) = self.sync_rebase_status(slot, operation.as_ref()).await {
+                tracing::warn!("checking rebase state of {}: {e}", slot.name);
+            }
+            // A rebase detaches HEAD until it finishes
+            if operation != Some(GitOperation::Rebase) {
+                // `HEAD` means detached, e.g. mid-bisect; nothing to compare
+                if let Ok(actual) = git::get_current_branch(clone_path).await {
+                    if actual != "HEAD" && actual != slot.branch {
+                        found.push(Discrepancy::BranchMismatch {
+                            slot: slot.name.clone(),
+                            expected: slot.branch.clone(),
+                            actual,
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Ok(mut entries) = tokio::fs::read_dir(&self.slots_directory).await {
+            let mut orphans = Vec::new();
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().to_string();
+                let owned = slots
+                    .iter()
+                    .any(|s| s.name == name || Path::new(&s.clone_path) == path);
+                if !path.is_dir()
+                    || owned
+                    || name.starts_with('.')
+                    || RESERVED_SLOT_NAMES.contains(&name.as_str())
+                {
+                    continue;
+                }
+                orphans.push(Discrepancy::OrphanDirectory {
+                    name,
+                    is_repo: path.join(".git").exists(),
+                    path: path.to_string_lossy().to_string(),
+                });
+            }
+            orphans.sort_by(|a, b| a.name().cmp(b.name()));
+            found.extend(orphans);
+        }
+
+        found
+    }
+
+    /// Apply one of the actions offered for a discrepancy from [`Self::reconcile`].
+    pub async fn resolve_discrepancy(
+        &self,
+        discrepancy: &Discrepancy,
+        action: ReconcileAction,
+    ) -> Result<()> {
+        if discrepancy.action_label(action).is_none() {
+            return Err(OrchestratorError::Reconcile(format!(
+                "{action:?} does not apply to {}",
+                discrepancy.name()
+            )));
+        }
+        match (discrepancy, action) {
+            (Discrepancy::MissingClone { slot, .. }, ReconcileAction::Remove) => {
+                self.destroy_slot(slot).await
+            }
+            (Discrepancy::MissingClone { slot, .. }, ReconcileAction::Fix) => {
+                self.update_slot(slot, |s| {
+                    let mut creation = CreationState::new(Some(&s.branch), None);
+                    creation.failed = true;
+                    s.creation = Some(creation);
+                    s.status = SlotStatus::Error;
+                })
+                .await?;
+                self.retry_creation(slot).await.map(|_| ())
+            }
+            (Discrepancy::OrphanDirectory { name, path, .. }, ReconcileAction::Adopt) => {
+                self.adopt_directory(name, Path::new(path)).await
+            }
+            (Discrepancy::OrphanDirectory { path, .. }, ReconcileAction::Remove) => {
+                Ok(tokio::fs::remove_dir_all(path).await?)
+            }
+            (Discrepancy::StaleAgentHost { slot, .. }, _) => {
+                agent_host::remove_stale(slot, &self.slots_directory).await?;
+                self.update_slot(slot, |s| {
+                    if s.agent_status != AgentStatus::None {
+                        s.agent_status = AgentStatus::Stopped;
+                    }
+                })
+                .await
+            }
+            (Discrepancy::BranchMismatch { slot, actual, .. }, ReconcileAction::Adopt) => {
+                let actual = actual.clone();
+                self.update_slot(slot, |s| s.branch = actual).await
+            }
+            (Discrepancy::BranchMismatch { slot, expected, .. }, _) => {
+                let clone_path = self
+                    .get_slot(slot)
+                    .await
+                    .map(|s| PathBuf::from(s.clone_path))
+                    .ok_or_else(|| OrchestratorError::SlotNotFound(slot.clone()))?;
+                git::checkout(&clone_path, expected, false).await
+            }
+            _ => unreachable!("checked by action_label"),
+        }
+    }
+
+    /// Register an existing working copy under `.slots/` as a ready slot.
+    async fn adopt_directory(&self, name: &str, clone_path: &Path) -> Result<()> {
+        if self.get_slot(name).await.is_some() {
+            return Err(OrchestratorError::SlotAlreadyExists(name.to_string()));
+        }
+        let branch = git::get_current_branch(clone_path).await?;
+        git::exclude(clone_path, ORCHESTRATOR_FILES).await?;
+        let worktree = clone_path.join(".git").is_file();
+        // The base is what creation would have recorded: the source's
+        // checked-out branch for a worktree, the clone's default otherwise
+        let (repo_path, base_branch) = if worktree {
+            let source = git::main_worktree(clone_path).await?;
+            let base_branch = git::get_current_branch(&source).await?;
+            (source.to_string_lossy().to_string(), base_branch)
+        } else {
+            let repo_path = git::remote_url(clone_path, "origin")
+                .await
+                .unwrap_or_else(|_| clone_path.to_string_lossy().to_string());
+            (repo_path, git::default_branch(clone_path).await?)
+        };
+
+        let mut slot = Slot::new(
+            name.to_string(),
+            repo_path,
+            branch,
+            clone_path.to_string_lossy().to_string(),
+        );
+        slot.base_branch = base_branch;
+        if worktree {
+            slot.clone_strategy = CloneStrategy::Worktree;
+        }
+        slot.status = SlotStatus::Ready;
+        self.slots.write().await.push(slot);
+        self.persist().await
+    }
+
     /// Get a snapshot of all slots (cheap clone for TUI rendering).
     pub async fn get_slots(&self) -> Vec<Slot> {
         self.slots.read().await.clone()
@@ -109,13 +353,20 @@ impl SlotManager {
 
     /// Create a new development slot.
     /// `source` can be a local filesystem path or a remote URL (https://, git@, etc.).
+    /// `clone_strategy` overrides the source repo's `provisioning.strategy`.
+    ///
+    /// The slot is registered before any work starts and each creation step is
+    /// persisted as it completes. If a step fails the slot stays in `Error` with
+    /// the failed step, to be resumed with [`Self::retry_creation`] or destroyed.
     pub async fn create_slot(
         &self,
         name: &str,
         source: &str,
         branch: Option<&str>,
         prompt: Option<&str>,
+        clone_strategy: Option<CloneStrategy>,
     ) -> Result<Slot> {
+        Slot::validate_name(name).map_err(OrchestratorError::InvalidSlotName)?;
         // Validate uniqueness
         {
             let slots = self.slots.read().await;
@@ -125,14 +376,7 @@ impl SlotManager {
         }
 
         let clone_path = self.slots_directory.join(name);
-        let clone_path_str = clone_path.to_string_lossy().to_string();
-
-        let is_remote = source.starts_with("https://")
-            || source.starts_with("http://")
-            || source.starts_with("git@")
-            || source.starts_with("ssh://");
-
-        let source_label = if is_remote {
+        let source_label = if is_remote_source(source) {
             source.to_string()
         } else {
             let repo_path = Path::new(source);
@@ -144,100 +388,269 @@ impl SlotManager {
 
         let mut slot = Slot::new(
             name.to_string(),
-            source_label.clone(),
+            source_label,
             branch.unwrap_or("unknown").to_string(),
-            clone_path_str.clone(),
+            clone_path.to_string_lossy().to_string(),
         );
+        slot.clone_strategy = match clone_strategy {
+            Some(strategy) => strategy,
+            None => self.provisioning_config(&slot)?.strategy,
+        };
+        slot.creation = Some(CreationState::new(branch, prompt));
 
-        // Clean up stale clone directory from a previous failed attempt.
-        // Kill any lingering agent host first so files aren't locked.
-        if clone_path.exists() {
-            let _ = agent_host::kill(name, &self.slots_directory).await;
-            tokio::fs::remove_dir_all(&clone_path).await.map_err(|e| {
-                OrchestratorError::Process(format!(
-                    "failed to remove stale directory {}: {e}",
-                    clone_path.display()
-                ))
-            })?;
+        {
+            let mut slots = self.slots.write().await;
+            slots.push(slot);
         }
+        self.persist().await?;
 
-        // Clone repo
-        git::clone_repo(&source_label, &clone_path).await?;
+        self.run_creation(name).await
+    }
 
-        // Checkout branch
-        if let Some(branch) = branch {
-            let exists = git::branch_exists(&clone_path, branch).await?;
-            git::checkout(&clone_path, branch, !exists).await?;
-        } else {
-            let current = git::get_current_branch(&clone_path).await?;
-            slot.branch = current;
+    /// Resume a failed creation from the step that failed.
+    pub async fn retry_creation(&self, name: &str) -> Result<Slot> {
+        let slot = self
+            .get_slot(name)
+            .await
+            .ok_or_else(|| OrchestratorError::SlotNotFound(name.to_string()))?;
+        if !slot.creation_failed() {
+            return Err(OrchestratorError::NothingToRetry(name.to_string()));
         }
+        self.update_slot(name, |s| {
+            s.status = SlotStatus::Provisioning;
+            s.last_error = None;
+            if let Some(ref mut creation) = s.creation {
+                creation.failed = false;
+            }
+        })
+        .await?;
+        self.run_creation(name).await
+    }
 
-        // Load config (optional), allocate ports, run setup commands
-        let config = match config_loader::load(&clone_path) {
-            Ok(config) => Some(config),
-            Err(OrchestratorError::ConfigNotFound(_)) => None,
-            Err(e) => return Err(e),
-        };
+    /// Run the remaining creation steps, persisting progress after each one.
+    async fn run_creation(&self, name: &str) -> Result<Slot> {
+        loop {
+            let slot = self
+                .get_slot(name)
+                .await
+                .ok_or_else(|| OrchestratorError::SlotNotFound(name.to_string()))?;
+            let Some(creation) = slot.creation.clone() else {
+                return Ok(slot);
+            };
+            let step = creation.step;
+
+            if let Err(e) = self.run_creation_step(&slot, &creation).await {
+                let message = match e {
+                    OrchestratorError::Process(message) => message,
+                    e => e.to_string(),
+                };
+                self.log(name, format!("Creation failed at {step}: {message}"));
+                self.update_slot(name, |s| {
+                    s.status = SlotStatus::Error;
+                    s.last_error = Some(format!("{step}: {message}"));
+                    if let Some(ref mut c) = s.creation {
+                        c.failed = true;
+                    }
+                })
+                .await?;
+                return Err(OrchestratorError::CreationFailed { step, message });
+            }
 
-        if let Some(ref config) = config {
-            let allocations = self
-                .port_allocator
-                .allocate_for_overrides(&config.port_overrides)?;
-            slot.port_allocations = allocations;
+            self.update_slot(name, |s| match step.next() {
+                Some(next) => {
+                    if let Some(ref mut c) = s.creation {
+                        c.step = next;
+                    }
+                }
+                None => s.creation = None,
+            })
+            .await?;
         }
+    }
 
-        // Run setup commands as direct processes (no tmux needed)
-        if let Some(ref config) = config {
-            for cmd in &config.setup {
-                let log_tx = self.log_tx.clone();
-                let slot_name = name.to_string();
-                let output = tokio::process::Command::new("bash")
-                    .args(["-c", cmd])
-                    .current_dir(&clone_path)
-                    .output()
-                    .await
-                    .map_err(|e| {
-                        OrchestratorError::Process(format!("setup command failed: {e}"))
-                    })?;
-
-                // Forward stdout/stderr to log channel
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                for line in stdout.lines() {
-                    let _ = log_tx.send(LogLine {
-                        slot_name: slot_name.clone(),
-                        source: LogSource::Aspire,
-                        line: line.to_string(),
-                    });
+    async fn run_creation_step(&self, slot: &Slot, creation: &CreationState) -> Result<()> {
+        let name = slot.name.as_str();
+        let clone_path = PathBuf::from(&slot.clone_path);
+        match creation.step {
+            CreationStep::Clone => self.clone_working_copy(slot, creation).await,
+            CreationStep::Checkout => {
+                // Worktrees are created on their branch
+                if slot.clone_strategy == CloneStrategy::Worktree {
+                    return Ok(());
                 }
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                for line in stderr.lines() {
-                    let _ = log_tx.send(LogLine {
-                        slot_name: slot_name.clone(),
-                        source: LogSource::Aspire,
-                        line: line.to_string(),
-                    });
+                match creation.branch {
+                    Some(ref branch) => {
+                        let exists = git::branch_exists_or_tracked(&clone_path, branch).await?;
+                        git::checkout(&clone_path, branch, !exists).await
+                    }
+                    None => {
+                        let base = slot.base_branch.clone();
+                        self.update_slot(name, |s| s.branch = base).await
+                    }
                 }
             }
+            CreationStep::Config => load_optional_config(&clone_path).map(|_| ()),
+            CreationStep::Ports => {
+                // Release anything a previous attempt allocated
+                for alloc in &slot.port_allocations {
+                    self.port_allocator.release(alloc.port);
+                }
+                let allocations = match load_optional_config(&clone_path)? {
+                    Some(config) => {
+                        let mut allocations = self
+                            .port_allocator
+                            .allocate_for_overrides(&config.port_overrides)?;
+                        if config.telemetry.enabled {
+                            allocations.push(self.port_allocator.allocate(otlp::OTLP_PORT_NAME)?);
+                        }
+                        allocations
+                    }
+                    None => Vec::new(),
+                };
+                self.update_slot(name, |s| s.port_allocations = allocations)
+                    .await
+            }
+            CreationStep::Setup => self.run_setup(slot, creation.setup_index).await,
+            CreationStep::Agent => match creation.prompt {
+                Some(ref prompt) => self.spawn_agent(name, Some(prompt), None, None).await,
+                None => Ok(()),
+            },
         }
+    }
 
-        slot.status = SlotStatus::Ready;
+    /// Provisioning settings for a slot. The slot's copy may not exist yet, so
+    /// they come from the source repo (or the workspace root for remote sources).
+    fn provisioning_config(&self, slot: &Slot) -> Result<ProvisioningConfig> {
+        Ok(load_optional_config(&self.provisioning_config_dir(slot))?
+            .map(|c| c.provisioning)
+            .unwrap_or_default())
+    }
 
-        // Add to state and persist
-        {
-            let mut slots = self.slots.write().await;
-            slots.push(slot.clone());
+    fn provisioning_config_dir(&self, slot: &Slot) -> PathBuf {
+        if is_remote_source(&slot.repo_path) {
+            self.workspace_root().to_path_buf()
+        } else {
+            PathBuf::from(&slot.repo_path)
+        }
+    }
+
+    /// Create the slot's working copy with its clone strategy and record the
+    /// base branch. Anything left by an earlier attempt is removed first.
+    async fn clone_working_copy(&self, slot: &Slot, creation: &CreationState) -> Result<()> {
+        let name = slot.name.as_str();
+        let source = slot.repo_path.clone();
+        let is_remote = is_remote_source(&source);
+        let clone_path = PathBuf::from(&slot.clone_path);
+
+        // Kill any lingering agent host first so files aren't locked.
+        if clone_path.exists() {
+            let _ = agent_host::kill(name, &self.slots_directory).await;
+            if slot.clone_strategy == CloneStrategy::Worktree && !is_remote {
+                let _ = git::worktree_remove(Path::new(&source), &clone_path).await;
+            }
+            if clone_path.exists() {
+                tokio::fs::remove_dir_all(&clone_path).await.map_err(|e| {
+                    OrchestratorError::Process(format!(
+                        "failed to remove stale directory {}: {e}",
+                        clone_path.display()
+                    ))
+                })?;
+            }
+        }
+
+        let require_local = |strategy: &str| {
+            if is_remote {
+                Err(OrchestratorError::InvalidConfig(format!(
+                    "the {strategy} clone strategy needs a local source repository, got {source}"
+                )))
+            } else {
+                Ok(())
+            }
+        };
+
+        match slot.clone_strategy {
+            CloneStrategy::Worktree => {
+                require_local("worktree")?;
+                let source_path = Path::new(&source);
+                let base_branch = git::get_current_branch(source_path).await?;
+                // The base branch is checked out in the source repo and can't be
+                // checked out twice, so default to a branch named after the slot.
+                let branch = creation.branch.clone().unwrap_or_else(|| name.to_string());
+                let exists = git::branch_exists_or_tracked(source_path, &branch).await?;
+                git::worktree_add(source_path, &clone_path, &branch, !exists).await?;
+                git::exclude(&clone_path, ORCHESTRATOR_FILES).await?;
+                return self
+                    .update_slot(name, |s| {
+                        s.base_branch = base_branch;
+                        s.branch = branch;
+                    })
+                    .await;
+            }
+            CloneStrategy::Clone => git::clone_repo(&source, &clone_path).await?,
+            CloneStrategy::Shared => {
+                require_local("shared")?;
+                git::clone_repo_shared(&source, &clone_path).await?;
+            }
+            CloneStrategy::Reference => {
+                let provisioning = self.provisioning_config(slot)?;
+                let reference = match provisioning.reference_repo {
+                    Some(ref r) => self.provisioning_config_dir(slot).join(r),
+                    None => {
+                        require_local("reference")?;
+                        PathBuf::from(&source)
+                    }
+                };
+                git::clone_repo_reference(&source, &reference, &clone_path).await?;
+            }
         }
-        self.persist().await?;
 
-        // Auto-spawn agent if prompt provided
-        if let Some(p) = prompt {
-            if !p.is_empty() {
-                self.spawn_agent(name, Some(p), None, None).await?;
+        git::exclude(&clone_path, ORCHESTRATOR_FILES).await?;
+
+        // The branch checked out by the clone is the base for rebases
+        let base_branch = git::get_current_branch(&clone_path).await?;
+        self.update_slot(name, |s| s.base_branch = base_branch)
+            .await
+    }
+
+    /// Run setup commands from `start` onward as direct processes, streaming
+    /// output to the slot log, then mark the slot `Ready`.
+    async fn run_setup(&self, slot: &Slot, start: usize) -> Result<()> {
+        let name = slot.name.as_str();
+        let clone_path = PathBuf::from(&slot.clone_path);
+        let config = load_optional_config(&clone_path)?;
+
+        if let Some(ref config) = config {
+            let total = config.setup.len();
+            for (step, cmd) in config.setup.iter().enumerate().skip(start) {
+                self.update_slot(name, |s| s.status = SlotStatus::SettingUp { step, total })
+                    .await?;
+                let command = cmd.command();
+                let timeout = Duration::from_secs(cmd.timeout_secs(config.setup_timeout_secs));
+                self.log(name, format!("$ {command}"));
+
+                let status =
+                    run_streamed(command, &clone_path, name, &self.log_tx, Some(timeout)).await?;
+                if !status.success() {
+                    return Err(OrchestratorError::Process(format!(
+                        "`{command}` failed ({status})"
+                    )));
+                }
+                self.update_slot(name, |s| {
+                    if let Some(ref mut c) = s.creation {
+                        c.setup_index = step + 1;
+                    }
+                })
+                .await?;
             }
         }
 
-        Ok(slot)
+        self.update_slot(name, |s| s.status = SlotStatus::Ready)
+            .await?;
+        let write_env = config.as_ref().is_some_and(|c| c.agent.services_env_file);
+        if let Some(slot) = self.get_slot(name).await {
+            service_manifest::write(&slot, false, write_env).await?;
+        }
+        Ok(())
     }
 
     /// Start the Aspire stack for a slot (direct process).
@@ -251,16 +664,29 @@ impl SlotManager {
         let config = config_loader::load(&clone_path)?;
 
         // Update status
-        self.update_slot(name, |s| s.status = SlotStatus::Starting)
-            .await?;
+        self.update_slot(name, |s| {
+            s.status = SlotStatus::Starting;
+            s.last_error = None;
+        })
+        .await?;
 
         // Clear aspire log file
         let log_path = slot.aspire_log_path();
         tokio::fs::write(&log_path, "").await.ok();
 
+        let (port_allocations, telemetry_task) = self.start_telemetry(&slot, &config).await?;
+
         // Start the Aspire process
-        let (child, mut log_rx) =
-            aspire::start(&clone_path, &config, &slot.port_allocations).await?;
+        let (child, mut log_rx) = match aspire::start(&clone_path, &config, &port_allocations).await
+        {
+            Ok(started) => started,
+            Err(e) => {
+                if let Some(task) = telemetry_task {
+                    task.abort();
+                }
+                return Err(e);
+            }
+        };
 
         // Spawn a task that reads log lines, writes them to file, sends to TUI,
         // and runs service discovery
@@ -268,10 +694,11 @@ impl SlotManager {
         let slot_name = name.to_string();
         let slots = self.slots.clone();
         let log_path_clone = log_path.clone();
+        let write_env = config.agent.services_env_file;
 
         let log_task = tokio::spawn(async move {
             let mut full_log = String::new();
-            while let Some(line) = log_rx.recv().await {
+            while let Some((timestamp, line)) = log_rx.recv().await {
                 // Append to log file
                 if let Ok(mut f) = tokio::fs::OpenOptions::new()
                     .create(true)
@@ -280,7 +707,8 @@ impl SlotManager {
                     .await
                 {
                     use tokio::io::AsyncWriteExt;
-                    let _ = f.write_all(format!("{line}\n").as_bytes()).await;
+                    let entry = super::log_tailer::format_timestamped(timestamp, &line);
+                    let _ = f.write_all(format!("{entry}\n").as_bytes()).await;
                 }
 
                 // Send to TUI
@@ -288,6 +716,7 @@ impl SlotManager {
                     slot_name: slot_name.clone(),
                     source: LogSource::Aspire,
                     line: line.clone(),
+                    timestamp,
                 });
 
                 // Run service discovery on accumulated log
@@ -295,9 +724,22 @@ impl SlotManager {
                 full_log.push('\n');
                 let services = discovery::parse_log_content(&full_log);
                 if services.dashboard_url.is_some() || !services.service_urls.is_empty() {
-                    let mut slots_guard = slots.write().await;
-                    if let Some(s) = slots_guard.iter_mut().find(|s| s.name == slot_name) {
-                        s.services = services;
+                    let changed = {
+                        let mut slots_guard = slots.write().await;
+                        match slots_guard.iter_mut().find(|s| s.name == slot_name) {
+                            Some(s) if s.services != services => {
+                                s.services = services;
+                                Some(s.clone())
+                            }
+                            _ => None,
+                        }
+                    };
+
+                    // Keep the manifest in sync so running agents see new URLs
+                    if let Some(slot) = changed {
+                        if let Err(e) = service_manifest::write(&slot, true, write_env).await {
+                            tracing::warn!("failed to write services manifest: {e}");
+                        }
                     }
                 }
             }
@@ -311,6 +753,7 @@ impl SlotManager {
                 AspireProcess {
                     child,
                     _log_rx_task: log_task,
+                    telemetry_task,
                 },
             );
         }
@@ -321,10 +764,71 @@ impl SlotManager {
             s.aspire_started_at = Some(Utc::now());
         })
         .await?;
+        self.write_service_manifest(name).await?;
 
         Ok(())
     }
 
+    /// Start the slot's OTLP receiver if telemetry is enabled, allocating its
+    /// port for slots created before it was. Returns the port allocations to
+    /// start Aspire with; a receiver that can't bind is skipped with a warning.
+    async fn start_telemetry(
+        &self,
+        slot: &Slot,
+        config: &OrchestratorConfig,
+    ) -> Result<(Vec<PortAllocation>, Option<tokio::task::JoinHandle<()>>)> {
+        let mut allocations = slot.port_allocations.clone();
+        if !config.telemetry.enabled {
+            return Ok((allocations, None));
+        }
+        let port = match allocations.iter().find(|a| a.name == otlp::OTLP_PORT_NAME) {
+            Some(alloc) => alloc.port,
+            None => {
+                let alloc = self.port_allocator.allocate(otlp::OTLP_PORT_NAME)?;
+                let port = alloc.port;
+                allocations.push(alloc);
+                let persisted = allocations.clone();
+                self.update_slot(&slot.name, |s| s.port_allocations = persisted)
+                    .await?;
+                port
+            }
+        };
+
+        let listener = match otlp::bind(port).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::warn!("telemetry disabled for {}: {e}", slot.name);
+                let allocations = allocations
+                    .into_iter()
+                    .filter(|a| a.name != otlp::OTLP_PORT_NAME)
+                    .collect();
+                return Ok((allocations, None));
+            }
+        };
+        {
+            let mut slots = self.slots.write().await;
+            if let Some(s) = slots.iter_mut().find(|s| s.name == slot.name) {
+                s.telemetry = Some(Default::default());
+            }
+        }
+        let task = otlp::serve(
+            listener,
+            slot.name.clone(),
+            self.slots.clone(),
+            self.log_tx.clone(),
+        );
+        if config.telemetry.capture_resources {
+            tracing::warn!("{}: resources' telemetry goes to the receiver", slot.name);
+            self.log(
+                &slot.name,
+                "telemetry.capture_resources is set: resources' logs and traces go to the \
+                 orchestrator and won't appear in the Aspire dashboard"
+                    .into(),
+            );
+        }
+        Ok((allocations, Some(task)))
+    }
+
     /// Stop the Aspire stack for a slot.
     pub async fn stop_aspire(&self, name: &str) -> Result<()> {
         self.update_slot(name, |s| s.status = SlotStatus::Stopping)
@@ -335,7 +839,7 @@ impl SlotManager {
             let mut procs = self.aspire_processes.write().await;
             if let Some(mut proc) = procs.remove(name) {
                 aspire::stop(&mut proc.child).await?;
-                proc._log_rx_task.abort();
+                proc.abort_tasks();
             }
         }
 
@@ -343,8 +847,10 @@ impl SlotManager {
             s.status = SlotStatus::Ready;
             s.aspire_started_at = None;
             s.services = Default::default();
+            s.telemetry = None;
         })
         .await?;
+        self.write_service_manifest(name).await?;
 
         Ok(())
     }
@@ -365,8 +871,13 @@ impl SlotManager {
         self.update_slot(name, |s| s.agent_status = AgentStatus::Starting)
             .await?;
 
+        // Make sure the manifest the system prompt points at exists
+        self.write_service_manifest(name).await?;
+
         // Build the command argument list
-        let command = super::agent::build_claude_command(&slot, prompt, allowed_tools, max_turns);
+        let system_prompt = self.system_prompt_for(&slot).await?;
+        let command =
+            super::agent::build_claude_command(&system_prompt, prompt, allowed_tools, max_turns);
         let log_path = slot.agent_log_path();
 
         // Clear agent log file
@@ -405,27 +916,326 @@ impl SlotManager {
         Ok(())
     }
 
-    /// Rebase the slot's branch onto origin/master.
-    pub async fn rebase(&self, name: &str) -> Result<()> {
+    /// Rewrite the slot's services manifest from its current state.
+    async fn write_service_manifest(&self, name: &str) -> Result<()> {
+        let Some(slot) = self.get_slot(name).await else {
+            return Ok(());
+        };
+        let write_env = config_loader::load(Path::new(&slot.clone_path))
+            .map(|c| c.agent.services_env_file)
+            .unwrap_or(false);
+        let running = self.aspire_processes.read().await.contains_key(name);
+        service_manifest::write(&slot, running, write_env).await
+    }
+
+    /// Resolve the system prompt for a slot: the repo's `agent.system_prompt_file`
+    /// template if configured, otherwise the built-in prompt.
+    async fn system_prompt_for(&self, slot: &Slot) -> Result<String> {
+        let clone_path = PathBuf::from(&slot.clone_path);
+        let template_file = match config_loader::load(&clone_path) {
+            Ok(config) => config.agent.system_prompt_file,
+            Err(OrchestratorError::ConfigNotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+
+        let Some(template_file) = template_file else {
+            return Ok(super::agent::build_system_prompt(slot));
+        };
+
+        let template_path = clone_path.join(&template_file);
+        let template = tokio::fs::read_to_string(&template_path)
+            .await
+            .map_err(|e| {
+                OrchestratorError::InvalidConfig(format!(
+                    "cannot read system prompt file {}: {e}",
+                    template_path.display()
+                ))
+            })?;
+
+        let slot_index = {
+            let slots = self.slots.read().await;
+            slots.iter().position(|s| s.name == slot.name).unwrap_or(0) + 1
+        };
+        super::agent::render_system_prompt(&template, slot, slot_index)
+    }
+
+    /// Summarize the git state of a slot's clone.
+    pub async fn git_status(&self, name: &str) -> Result<GitStatus> {
+        let slot = self
+            .get_slot(name)
+            .await
+            .ok_or_else(|| OrchestratorError::SlotNotFound(name.to_string()))?;
+        let status = git::status(Path::new(&slot.clone_path), &slot.base_branch).await?;
+        self.sync_rebase_status(&slot, status.operation.as_ref())
+            .await?;
+        Ok(status)
+    }
+
+    /// Bring `rebase_status` and `conflicted_files` in line with the clone,
+    /// for rebases stopped or finished outside [`Self::rebase`] and its
+    /// continue/skip/abort, e.g. by the agent or by hand.
+    async fn sync_rebase_status(
+        &self,
+        slot: &Slot,
+        operation: Option<&GitOperation>,
+    ) -> Result<()> {
+        let (status, files) = match (&slot.rebase_status, operation) {
+            // One of our own rebase steps is still running
+            (RebaseStatus::Rebasing, _) => return Ok(()),
+            (_, Some(GitOperation::Rebase)) => (
+                RebaseStatus::Conflicted,
+                git::conflicted_files(Path::new(&slot.clone_path)).await?,
+            ),
+            _ => (RebaseStatus::None, Vec::new()),
+        };
+        if status == slot.rebase_status && files == slot.conflicted_files {
+            return Ok(());
+        }
+        self.update_slot(&slot.name, |s| {
+            s.rebase_status = status;
+            s.conflicted_files = files;
+        })
+        .await
+    }
+
+    /// Diff a slot's clone against its fork point from the base branch, or
+    /// against `HEAD` for uncommitted changes only.
+    pub async fn diff(&self, name: &str, target: DiffTarget) -> Result<Vec<FileDiff>> {
         let slot = self
             .get_slot(name)
             .await
             .ok_or_else(|| OrchestratorError::SlotNotFound(name.to_string()))?;
         let clone_path = PathBuf::from(&slot.clone_path);
-        git::fetch(&clone_path).await?;
-        git::rebase(&clone_path, "master").await?;
-        Ok(())
+        let base_ref = match target {
+            DiffTarget::WorkingTree => "HEAD".to_string(),
+            DiffTarget::Base => {
+                let remote_base = format!("origin/{}", slot.base_branch);
+                match git::merge_base(&clone_path, &remote_base).await {
+                    Ok(sha) => sha,
+                    Err(_) => git::merge_base(&clone_path, &slot.base_branch).await?,
+                }
+            }
+        };
+        git::diff(&clone_path, &base_ref).await
+    }
+
+    /// Rebase the slot's branch onto `origin/<base_branch>`. Conflicts leave the
+    /// clone mid-rebase and mark the slot `Conflicted`.
+    pub async fn rebase(&self, name: &str) -> Result<RebaseOutcome> {
+        let slot = self
+            .get_slot(name)
+            .await
+            .ok_or_else(|| OrchestratorError::SlotNotFound(name.to_string()))?;
+        let clone_path = PathBuf::from(&slot.clone_path);
+        self.update_slot(name, |s| s.rebase_status = RebaseStatus::Rebasing)
+            .await?;
+
+        let result = match git::fetch(&clone_path).await {
+            Ok(()) => git::rebase(&clone_path, &slot.base_branch).await,
+            Err(e) => Err(e),
+        };
+        self.record_rebase_outcome(name, result).await
+    }
+
+    /// Continue a conflicted rebase once the conflicts are resolved and staged.
+    pub async fn rebase_continue(&self, name: &str) -> Result<RebaseOutcome> {
+        let clone_path = self.clone_path_of(name).await?;
+        self.update_slot(name, |s| s.rebase_status = RebaseStatus::Rebasing)
+            .await?;
+        let result = git::rebase_continue(&clone_path).await;
+        self.record_rebase_outcome(name, result).await
+    }
+
+    /// Skip the commit that stopped a conflicted rebase.
+    pub async fn rebase_skip(&self, name: &str) -> Result<RebaseOutcome> {
+        let clone_path = self.clone_path_of(name).await?;
+        self.update_slot(name, |s| s.rebase_status = RebaseStatus::Rebasing)
+            .await?;
+        let result = git::rebase_skip(&clone_path).await;
+        self.record_rebase_outcome(name, result).await
+    }
+
+    /// Abort a conflicted rebase, restoring the branch to its pre-rebase state.
+    pub async fn rebase_abort(&self, name: &str) -> Result<()> {
+        let clone_path = self.clone_path_of(name).await?;
+        git::rebase_abort(&clone_path).await?;
+        self.update_slot(name, |s| {
+            s.rebase_status = RebaseStatus::None;
+            s.conflicted_files.clear();
+        })
+        .await
     }
 
-    /// Push the slot's current branch.
+    /// Hand a conflicted rebase to the slot's agent: typed into the running
+    /// session if there is one, otherwise a new agent is spawned with the prompt.
+    /// Returns `true` if a new agent was spawned.
+    pub async fn send_conflict_to_agent(&self, name: &str) -> Result<bool> {
+        let slot = self
+            .get_slot(name)
+            .await
+            .ok_or_else(|| OrchestratorError::SlotNotFound(name.to_string()))?;
+        if slot.rebase_status != RebaseStatus::Conflicted {
+            return Err(OrchestratorError::Git(format!(
+                "slot '{name}' has no conflicted rebase"
+            )));
+        }
+        let prompt = super::agent::build_conflict_prompt(&slot);
+        self.send_prompt_to_agent(name, &prompt).await
+    }
+
+    /// Ask the slot's agent to fix the problems from its last build, the same
+    /// way as [`Self::send_conflict_to_agent`]. Returns `true` if a new agent
+    /// was spawned.
+    pub async fn send_problems_to_agent(&self, name: &str) -> Result<bool> {
+        let slot = self
+            .get_slot(name)
+            .await
+            .ok_or_else(|| OrchestratorError::SlotNotFound(name.to_string()))?;
+        if slot.problems.is_empty() {
+            return Err(OrchestratorError::Agent(format!(
+                "slot '{name}' has no build problems"
+            )));
+        }
+        let prompt = super::agent::build_problems_prompt(&slot);
+        self.send_prompt_to_agent(name, &prompt).await
+    }
+
+    /// Type a prompt into the slot's running agent session, or spawn an agent
+    /// with it. Returns `true` if a new agent was spawned.
+    async fn send_prompt_to_agent(&self, name: &str, prompt: &str) -> Result<bool> {
+        if agent_host::is_running(name, &self.slots_directory)
+            .await
+            .unwrap_or(false)
+        {
+            let mut conn = agent_host::connect(name, &self.slots_directory).await?;
+            conn.send_input(prompt.as_bytes()).await?;
+            conn.send_input(b"\r").await?;
+            Ok(false)
+        } else {
+            self.spawn_agent(name, Some(prompt), None, None).await?;
+            Ok(true)
+        }
+    }
+
+    /// Store a rebase result on the slot and pass it through.
+    async fn record_rebase_outcome(
+        &self,
+        name: &str,
+        result: Result<RebaseOutcome>,
+    ) -> Result<RebaseOutcome> {
+        let (status, files) = match &result {
+            Ok(RebaseOutcome::Conflicted(files)) => (RebaseStatus::Conflicted, files.clone()),
+            Ok(RebaseOutcome::Completed) | Err(_) => (RebaseStatus::None, Vec::new()),
+        };
+        self.update_slot(name, |s| {
+            s.rebase_status = status;
+            s.conflicted_files = files;
+        })
+        .await?;
+        result
+    }
+
+    async fn clone_path_of(&self, name: &str) -> Result<PathBuf> {
+        self.get_slot(name)
+            .await
+            .map(|s| PathBuf::from(s.clone_path))
+            .ok_or_else(|| OrchestratorError::SlotNotFound(name.to_string()))
+    }
+
+    /// Push the slot's branch. Refuses protected branches, runs the configured
+    /// pre-push commands (output goes to the slot log), and uses
+    /// `--force-with-lease` when the upstream is no longer an ancestor of `HEAD`.
     pub async fn git_push(&self, name: &str) -> Result<()> {
         let slot = self
             .get_slot(name)
             .await
             .ok_or_else(|| OrchestratorError::SlotNotFound(name.to_string()))?;
         let clone_path = PathBuf::from(&slot.clone_path);
-        git::push(&clone_path, &slot.branch, true).await?;
-        Ok(())
+        let config = load_optional_config(&clone_path)?;
+        // Without a config there are no pre-push commands to time
+        let timeout = Duration::from_secs(
+            config
+                .as_ref()
+                .map_or(0, OrchestratorConfig::pre_push_timeout_secs),
+        );
+        let push_config = config.map(|c| c.push).unwrap_or_default();
+
+        if slot.branch == slot.base_branch || push_config.protected_branches.contains(&slot.branch)
+        {
+            return Err(OrchestratorError::ProtectedBranch(slot.branch));
+        }
+
+        for cmd in &push_config.pre_push {
+            self.log(name, format!("$ {cmd}"));
+            let status = run_streamed(cmd, &clone_path, name, &self.log_tx, Some(timeout)).await?;
+            if !status.success() {
+                return Err(OrchestratorError::Process(format!(
+                    "pre-push command `{cmd}` failed ({status})"
+                )));
+            }
+        }
+
+        // Decided from git rather than remembered, so a rebase finished by
+        // the agent or by hand is pushed the same way
+        let force = git::upstream_rewritten(&clone_path).await?;
+        if force {
+            self.log(
+                name,
+                format!("Pushing {} with --force-with-lease", slot.branch),
+            );
+        }
+        git::push(&clone_path, &slot.branch, true, force).await
+    }
+
+    /// Send an orchestrator message to the slot's log view.
+    fn log(&self, name: &str, line: String) {
+        let _ = self
+            .log_tx
+            .send(LogLine::new(name, LogSource::Aspire, line));
+    }
+
+    /// Open a pull request from the slot's branch into its base branch. The
+    /// branch must already be pushed.
+    pub async fn create_pull_request(&self, name: &str) -> Result<PullRequest> {
+        let slot = self
+            .get_slot(name)
+            .await
+            .ok_or_else(|| OrchestratorError::SlotNotFound(name.to_string()))?;
+        if let Some(ref pr) = slot.pull_request {
+            if pr.state.is_open() {
+                return Err(OrchestratorError::Forge(format!(
+                    "slot '{name}' already has pull request #{}",
+                    pr.number
+                )));
+            }
+        }
+        let pr = self
+            .forge
+            .create_pull_request(Path::new(&slot.clone_path), &slot.branch, &slot.base_branch)
+            .await?;
+        let stored = pr.clone();
+        self.update_slot(name, |s| s.pull_request = Some(stored))
+            .await?;
+        Ok(pr)
+    }
+
+    /// Re-fetch review/CI state for the slot's pull request, if it has one.
+    pub async fn refresh_pull_request(&self, name: &str) -> Result<Option<PullRequest>> {
+        let slot = self
+            .get_slot(name)
+            .await
+            .ok_or_else(|| OrchestratorError::SlotNotFound(name.to_string()))?;
+        let Some(number) = slot.pull_request.map(|pr| pr.number) else {
+            return Ok(None);
+        };
+        let pr = self
+            .forge
+            .pull_request_status(Path::new(&slot.clone_path), number)
+            .await?;
+        let stored = pr.clone();
+        self.update_slot(name, |s| s.pull_request = Some(stored))
+            .await?;
+        Ok(Some(pr))
     }
 
     /// Destroy a slot: kill agent host, stop Aspire, delete clone directory.
@@ -435,7 +1245,7 @@ impl SlotManager {
             let mut procs = self.aspire_processes.write().await;
             if let Some(mut proc) = procs.remove(name) {
                 let _ = aspire::stop(&mut proc.child).await;
-                proc._log_rx_task.abort();
+                proc.abort_tasks();
             }
         }
 
@@ -452,6 +1262,10 @@ impl SlotManager {
             let _ = agent_host::kill(name, &self.slots_directory).await;
 
             let clone_path = PathBuf::from(&slot.clone_path);
+            if slot.clone_strategy == CloneStrategy::Worktree {
+                // Unregister the worktree so its branch can be checked out again
+                let _ = git::worktree_remove(Path::new(&slot.repo_path), &clone_path).await;
+            }
             if clone_path.exists() {
                 tokio::fs::remove_dir_all(&clone_path).await.ok();
             }
@@ -500,3 +1314,854 @@ impl SlotManager {
         self.state_store.save(&slots).await
     }
 }
+
+fn is_remote_source(source: &str) -> bool {
+    source.starts_with("https://")
+        || source.starts_with("http://")
+        || source.starts_with("git@")
+        || source.starts_with("ssh://")
+}
+
+/// Fill in the base branch of slots saved before it was recorded, from
+/// each clone's `origin/HEAD`. Returns whether any slot changed; clones
+/// that can't be read are left for the next load.
+async fn resolve_base_branches(slots: &mut [Slot]) -> bool {
+    let mut resolved = false;
+    for slot in slots
+        .iter_mut()
+        .filter(|s| s.base_branch.is_empty() && s.creation.is_none())
+    {
+        match git::default_branch(Path::new(&slot.clone_path)).await {
+            Ok(branch) => {
+                slot.base_branch = branch;
+                resolved = true;
+            }
+            Err(e) => tracing::warn!("no base branch for {}: {e}", slot.name),
+        }
+    }
+    resolved
+}
+
+/// Load a repo's `.aspire-orchestrator.yaml`, treating a missing file as `None`.
+fn load_optional_config(dir: &Path) -> Result<Option<OrchestratorConfig>> {
+    match config_loader::load(dir) {
+        Ok(config) => Ok(Some(config)),
+        Err(OrchestratorError::ConfigNotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// How long to keep reading a finished command's output. A background process
+/// it started can hold the pipes open indefinitely.
+const OUTPUT_DRAIN_GRACE: Duration = Duration::from_secs(2);
+
+/// Run a shell command in `cwd`, forwarding stdout and stderr to the slot log
+/// line by line as they are produced.
+async fn run_streamed(
+    cmd: &str,
+    cwd: &Path,
+    slot_name: &str,
+    log_tx: &mpsc::UnboundedSender<LogLine>,
+    timeout: Option<Duration>,
+) -> Result<std::process::ExitStatus> {
+    use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+    let mut command = tokio::process::Command::new("bash");
+    command
+        .args(["-c", cmd])
+        .current_dir(cwd)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true);
+    // Own process group, so a timeout also kills whatever the shell started
+    #[cfg(unix)]
+    command.process_group(0);
+    let mut child = command
+        .spawn()
+        .map_err(|e| OrchestratorError::Process(format!("cannot run `{cmd}`: {e}")))?;
+
+    fn forward(
+        pipe: Option<impl AsyncRead + Unpin + Send + 'static>,
+        slot_name: &str,
+        log_tx: &mpsc::UnboundedSender<LogLine>,
+    ) -> tokio::task::JoinHandle<()> {
+        let slot_name = slot_name.to_string();
+        let log_tx = log_tx.clone();
+        tokio::spawn(async move {
+            let Some(pipe) = pipe else { return };
+            let mut reader = BufReader::new(pipe);
+            let mut line = Vec::new();
+            // Bytes rather than `lines()`, which stops at the first invalid
+            // UTF-8 and leaves the command writing to a closed pipe
+            while let Ok(n) = reader.read_until(b'\n', &mut line).await {
+                if n == 0 {
+                    break;
+                }
+                let text = String::from_utf8_lossy(&line);
+                let text = text.trim_end_matches(['\r', '\n']);
+                let _ = log_tx.send(LogLine::new(&slot_name, LogSource::Aspire, text));
+                line.clear();
+            }
+        })
+    }
+
+    let mut stdout = forward(child.stdout.take(), slot_name, log_tx);
+    let mut stderr = forward(child.stderr.take(), slot_name, log_tx);
+    let status = match timeout {
+        Some(limit) => match tokio::time::timeout(limit, child.wait()).await {
+            Ok(status) => status?,
+            Err(_) => {
+                #[cfg(unix)]
+                if let Some(pid) = child.id() {
+                    unsafe {
+                        libc::kill(-(pid as i32), libc::SIGKILL);
+                    }
+                }
+                let _ = child.kill().await;
+                stdout.abort();
+                stderr.abort();
+                return Err(OrchestratorError::Process(format!(
+                    "`{cmd}` timed out after {}s",
+                    limit.as_secs()
+                )));
+            }
+        },
+        None => child.wait().await?,
+    };
+    let drained = tokio::time::timeout(OUTPUT_DRAIN_GRACE, async {
+        let _ = (&mut stdout).await;
+        let _ = (&mut stderr).await;
+    })
+    .await;
+    if drained.is_err() {
+        stdout.abort();
+        stderr.abort();
+    }
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CheckState, PrState, ReviewState};
+    use crate::services::forge::FakeForge;
+
+    async fn manager_with_slot(dir: &Path, forge: Arc<FakeForge>) -> SlotManager {
+        manager_with_branch(dir, "feature/x", forge).await.0
+    }
+
+    async fn manager_with_branch(
+        dir: &Path,
+        branch: &str,
+        forge: Arc<FakeForge>,
+    ) -> (SlotManager, mpsc::UnboundedReceiver<LogLine>) {
+        let clone_path = dir.join("feat");
+        std::fs::create_dir_all(&clone_path).unwrap();
+        let mut slot = Slot::new(
+            "feat".into(),
+            "/repo".into(),
+            branch.into(),
+            clone_path.to_string_lossy().to_string(),
+        );
+        slot.base_branch = "master".into();
+        SlotStateStore::new(dir).save(&[slot]).await.unwrap();
+        let (tx, rx) = mpsc::unbounded_channel();
+        let manager = SlotManager::new(dir.to_path_buf(), tx).with_forge(forge);
+        manager.load_state().await.unwrap();
+        (manager, rx)
+    }
+
+    /// Run git in `dir`, returning its trimmed output.
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// A repo at `dir/origin` with one commit on `main`.
+    fn origin_repo(dir: &Path) -> PathBuf {
+        let origin = dir.join("origin");
+        std::fs::create_dir_all(&origin).unwrap();
+        git(&origin, &["init", "-q", "-b", "main"]);
+        std::fs::write(origin.join("notes.txt"), "one\n").unwrap();
+        git(&origin, &["add", "."]);
+        git(&origin, &["commit", "-q", "-m", "initial"]);
+        origin
+    }
+
+    /// A slot `feat` on branch `feature` whose rebase onto `origin/main`
+    /// stopped on a conflict in `notes.txt`. Returns the manager and clone.
+    async fn conflicted_slot(dir: &Path) -> (SlotManager, PathBuf) {
+        let origin = origin_repo(dir);
+        let slots_dir = dir.join("slots");
+        let clone_path = slots_dir.join("feat");
+        git(
+            dir,
+            &[
+                "clone",
+                "-q",
+                origin.to_str().unwrap(),
+                clone_path.to_str().unwrap(),
+            ],
+        );
+        git(&clone_path, &["config", "user.name", "Test"]);
+        git(&clone_path, &["config", "user.email", "test@example.com"]);
+        git(&clone_path, &["checkout", "-q", "-b", "feature"]);
+        std::fs::write(clone_path.join("notes.txt"), "feature\n").unwrap();
+        git(&clone_path, &["commit", "-q", "-am", "feature change"]);
+        std::fs::write(origin.join("notes.txt"), "upstream\n").unwrap();
+        git(&origin, &["commit", "-q", "-am", "upstream change"]);
+
+        let mut slot = Slot::new(
+            "feat".into(),
+            origin.to_string_lossy().to_string(),
+            "feature".into(),
+            clone_path.to_string_lossy().to_string(),
+        );
+        slot.base_branch = "main".into();
+        slot.status = SlotStatus::Ready;
+        SlotStateStore::new(&slots_dir).save(&[slot]).await.unwrap();
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let manager = SlotManager::new(slots_dir, tx);
+        manager.load_state().await.unwrap();
+
+        assert_eq!(
+            manager.rebase("feat").await.unwrap(),
+            RebaseOutcome::Conflicted(vec!["notes.txt".into()])
+        );
+        (manager, clone_path)
+    }
+
+    fn write_config(dir: &Path, yaml: &str) {
+        std::fs::write(
+            dir.join("feat").join(".aspire-orchestrator.yaml"),
+            format!("apphost: App.AppHost/App.AppHost.csproj\n{yaml}"),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn failed_creation_is_kept_and_retryable() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let manager = SlotManager::new(dir.path().to_path_buf(), tx);
+        let missing = dir.path().join("no-such-repo");
+
+        let err = manager
+            .create_slot("feat", missing.to_str().unwrap(), None, Some("go"), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            OrchestratorError::CreationFailed {
+                step: CreationStep::Clone,
+                ..
+            }
+        ));
+
+        let slot = manager.get_slot("feat").await.unwrap();
+        assert_eq!(slot.status, SlotStatus::Error);
+        assert!(slot.creation_failed());
+        assert!(slot.last_error.as_deref().unwrap().starts_with("clone: "));
+        assert_eq!(
+            slot.creation.as_ref().unwrap().prompt.as_deref(),
+            Some("go")
+        );
+
+        // Retrying re-runs the failed step and fails the same way
+        assert!(matches!(
+            manager.retry_creation("feat").await,
+            Err(OrchestratorError::CreationFailed {
+                step: CreationStep::Clone,
+                ..
+            })
+        ));
+        assert!(matches!(
+            manager.create_slot("feat", "x", None, None, None).await,
+            Err(OrchestratorError::SlotAlreadyExists(_))
+        ));
+        assert!(matches!(
+            manager.create_slot("../feat", "x", None, None, None).await,
+            Err(OrchestratorError::InvalidSlotName(_))
+        ));
+
+        manager.destroy_slot("feat").await.unwrap();
+        assert!(manager.get_slots().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn interrupted_creation_is_marked_failed_on_reconnect() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut slot = Slot::new("feat".into(), "/repo".into(), "b".into(), "/c".into());
+        let mut creation = CreationState::new(None, None);
+        creation.step = CreationStep::Setup;
+        slot.creation = Some(creation);
+        SlotStateStore::new(dir.path()).save(&[slot]).await.unwrap();
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let manager = SlotManager::new(dir.path().to_path_buf(), tx);
+        manager.load_state().await.unwrap();
+        manager.reconnect_existing_sessions().await.unwrap();
+
+        let slot = manager.get_slot("feat").await.unwrap();
+        assert_eq!(slot.status, SlotStatus::Error);
+        assert!(slot.creation_failed());
+        assert_eq!(slot.last_error.as_deref(), Some("setup: interrupted"));
+        assert!(matches!(
+            manager.retry_creation("missing").await,
+            Err(OrchestratorError::SlotNotFound(_))
+        ));
+    }
+
+    /// Agent lines recorded in the log history of `dir`, oldest first.
+    fn agent_history(dir: &Path) -> Vec<String> {
+        let query = crate::services::log_store::LogQuery {
+            source: Some(LogSource::Agent),
+            ..Default::default()
+        };
+        crate::services::log_store::LogStore::new(dir)
+            .search(&query, None, 100)
+            .unwrap()
+            .records
+            .into_iter()
+            .map(|r| r.text)
+            .collect()
+    }
+
+    /// Run the orchestrator's startup against `dir`, as a fresh process would,
+    /// append `line` to the agent's log, and wait for it to be recorded.
+    async fn restart_and_log(dir: &Path, line: &str) -> SlotManager {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let log_tx = crate::services::log_store::LogStore::new(dir).record(tx);
+        let manager = SlotManager::new(dir.to_path_buf(), log_tx);
+        manager.load_state().await.unwrap();
+        manager.reconnect_existing_sessions().await.unwrap();
+
+        let log_path = manager.get_slot("feat").await.unwrap().agent_log_path();
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(log_path)
+            .unwrap();
+        std::io::Write::write_all(&mut file, format!("{line}\n").as_bytes()).unwrap();
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while !agent_history(dir).iter().any(|l| l == line) {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "{line} never recorded"
+            );
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        manager
+    }
+
+    #[tokio::test]
+    async fn reconnecting_does_not_record_agent_output_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let clone_path = dir.path().join("feat");
+        std::fs::create_dir_all(&clone_path).unwrap();
+        // An agent host that outlives the orchestrator: this process stands in
+        std::fs::write(
+            clone_path.join(".agent-host.json"),
+            format!(r#"{{"port":1,"pid":{}}}"#, std::process::id()),
+        )
+        .unwrap();
+        let mut slot = Slot::new(
+            "feat".into(),
+            "/repo".into(),
+            "b".into(),
+            clone_path.to_string_lossy().to_string(),
+        );
+        slot.status = SlotStatus::Ready;
+        slot.agent_status = AgentStatus::Active;
+        std::fs::write(slot.agent_log_path(), "before restart\n").unwrap();
+        SlotStateStore::new(dir.path()).save(&[slot]).await.unwrap();
+
+        for line in ["first run", "second run"] {
+            let manager = restart_and_log(dir.path(), line).await;
+            // Quit: the tailers stop and the history is closed
+            for (_, handle) in manager.agent_tailer_handles.write().await.drain() {
+                handle.abort();
+            }
+        }
+
+        assert_eq!(agent_history(dir.path()), ["first run", "second run"]);
+    }
+
+    #[tokio::test]
+    async fn streamed_command_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let started = std::time::Instant::now();
+        let result = run_streamed(
+            "echo begin; sleep 30",
+            dir.path(),
+            "feat",
+            &tx,
+            Some(Duration::from_millis(300)),
+        )
+        .await;
+        assert!(
+            matches!(result, Err(OrchestratorError::Process(ref m)) if m.contains("timed out"))
+        );
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(rx.recv().await.unwrap().line, "begin");
+    }
+
+    #[tokio::test]
+    async fn streamed_output_survives_invalid_utf8() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let status = run_streamed(
+            r"printf 'caf\xe9\r\n'; seq 1 20000",
+            dir.path(),
+            "feat",
+            &tx,
+            Some(Duration::from_secs(30)),
+        )
+        .await
+        .unwrap();
+        assert!(status.success());
+
+        drop(tx);
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line.line);
+        }
+        assert_eq!(lines[0], "caf\u{FFFD}");
+        assert_eq!(lines.len(), 20001);
+        assert_eq!(lines[20000], "20000");
+    }
+
+    #[tokio::test]
+    async fn background_process_holding_the_pipe_does_not_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let started = std::time::Instant::now();
+        let status = run_streamed("echo done; sleep 30 &", dir.path(), "feat", &tx, None)
+            .await
+            .unwrap();
+        assert!(status.success());
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(rx.recv().await.unwrap().line, "done");
+    }
+
+    #[tokio::test]
+    async fn push_refuses_base_and_protected_branches() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, _rx) =
+            manager_with_branch(dir.path(), "master", Arc::new(FakeForge::new())).await;
+        assert!(matches!(
+            manager.git_push("feat").await,
+            Err(OrchestratorError::ProtectedBranch(b)) if b == "master"
+        ));
+
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, _rx) =
+            manager_with_branch(dir.path(), "release", Arc::new(FakeForge::new())).await;
+        write_config(dir.path(), "push:\n  protected_branches: [release]\n");
+        assert!(matches!(
+            manager.git_push("feat").await,
+            Err(OrchestratorError::ProtectedBranch(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn hung_pre_push_command_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, _rx) =
+            manager_with_branch(dir.path(), "feature/x", Arc::new(FakeForge::new())).await;
+        write_config(
+            dir.path(),
+            "push:\n  pre_push: [sleep 30]\n  pre_push_timeout_secs: 1\n",
+        );
+
+        let started = std::time::Instant::now();
+        let err = manager.git_push("feat").await.unwrap_err();
+        assert!(
+            matches!(err, OrchestratorError::Process(ref m) if m.contains("timed out")),
+            "{err}"
+        );
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn failing_pre_push_command_aborts_and_streams_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, mut rx) =
+            manager_with_branch(dir.path(), "feature/x", Arc::new(FakeForge::new())).await;
+        write_config(
+            dir.path(),
+            "push:\n  pre_push:\n    - echo checking && echo oops >&2 && exit 3\n",
+        );
+
+        let err = manager.git_push("feat").await.unwrap_err();
+        assert!(matches!(err, OrchestratorError::Process(_)), "{err}");
+
+        // Lines are relayed through the problems collector's thread
+        let mut lines = Vec::new();
+        while let Ok(Some(line)) = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await {
+            lines.push(line.line);
+            if lines.iter().any(|l| l == "checking") && lines.iter().any(|l| l == "oops") {
+                break;
+            }
+        }
+        assert!(lines.iter().any(|l| l == "checking"), "{lines:?}");
+        assert!(lines.iter().any(|l| l == "oops"), "{lines:?}");
+    }
+
+    #[tokio::test]
+    async fn pull_request_is_created_and_refreshed() {
+        let dir = tempfile::tempdir().unwrap();
+        let forge = Arc::new(FakeForge::new());
+        let manager = manager_with_slot(dir.path(), forge.clone()).await;
+
+        assert!(manager
+            .refresh_pull_request("feat")
+            .await
+            .unwrap()
+            .is_none());
+
+        let pr = manager.create_pull_request("feat").await.unwrap();
+        assert_eq!(pr.number, 1);
+        assert!(matches!(
+            manager.create_pull_request("feat").await,
+            Err(OrchestratorError::Forge(_))
+        ));
+
+        forge.set_status(1, PrState::Open, ReviewState::Approved, CheckState::Passing);
+        let pr = manager.refresh_pull_request("feat").await.unwrap().unwrap();
+        assert_eq!(pr.review, ReviewState::Approved);
+        assert_eq!(pr.checks, CheckState::Passing);
+
+        // Persisted on the slot
+        let reloaded = SlotStateStore::new(dir.path()).load().await.unwrap();
+        assert_eq!(reloaded[0].pull_request.as_ref(), Some(&pr));
+    }
+
+    #[tokio::test]
+    async fn reconcile_reports_missing_clone_and_orphans() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = manager_with_slot(dir.path(), Arc::new(FakeForge::new())).await;
+        std::fs::create_dir_all(dir.path().join("stray")).unwrap();
+        std::fs::create_dir_all(dir.path().join("blueprints")).unwrap();
+
+        // `feat` exists but is not a git working copy
+        let found = manager.reconcile().await;
+        assert_eq!(found.len(), 2);
+        assert!(matches!(&found[0], Discrepancy::MissingClone { slot, .. } if slot == "feat"));
+        assert!(matches!(
+            &found[1],
+            Discrepancy::OrphanDirectory { name, is_repo: false, .. } if name == "stray"
+        ));
+
+        assert!(matches!(
+            manager
+                .resolve_discrepancy(&found[1], ReconcileAction::Adopt)
+                .await,
+            Err(OrchestratorError::Reconcile(_))
+        ));
+        manager
+            .resolve_discrepancy(&found[1], ReconcileAction::Remove)
+            .await
+            .unwrap();
+        assert!(!dir.path().join("stray").exists());
+
+        manager
+            .resolve_discrepancy(&found[0], ReconcileAction::Remove)
+            .await
+            .unwrap();
+        assert!(manager.get_slots().await.is_empty());
+        assert!(manager.reconcile().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn base_branch_of_older_slots_comes_from_origin_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let origin = origin_repo(dir.path());
+        let slots_dir = dir.path().join("slots");
+        let clone_path = slots_dir.join("feat");
+        git(
+            dir.path(),
+            &[
+                "clone",
+                "-q",
+                origin.to_str().unwrap(),
+                clone_path.to_str().unwrap(),
+            ],
+        );
+        // Saved before the base branch was recorded
+        let slot = Slot::new(
+            "feat".into(),
+            origin.to_string_lossy().to_string(),
+            "main".into(),
+            clone_path.to_string_lossy().to_string(),
+        );
+        SlotStateStore::new(&slots_dir).save(&[slot]).await.unwrap();
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let manager = SlotManager::new(slots_dir.clone(), tx);
+        manager.load_state().await.unwrap();
+        assert_eq!(manager.get_slot("feat").await.unwrap().base_branch, "main");
+        let reloaded = SlotStateStore::new(&slots_dir).load().await.unwrap();
+        assert_eq!(reloaded[0].base_branch, "main");
+    }
+
+    #[tokio::test]
+    async fn adopted_clone_gets_its_base_branch_from_origin_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let origin = origin_repo(dir.path());
+        let slots_dir = dir.path().join("slots");
+        let clone_path = slots_dir.join("stray");
+        git(
+            dir.path(),
+            &[
+                "clone",
+                "-q",
+                origin.to_str().unwrap(),
+                clone_path.to_str().unwrap(),
+            ],
+        );
+        git(&clone_path, &["checkout", "-q", "-b", "feature"]);
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let manager = SlotManager::new(slots_dir, tx);
+        let found = manager.reconcile().await;
+        assert!(matches!(
+            &found[..],
+            [Discrepancy::OrphanDirectory { name, is_repo: true, .. }] if name == "stray"
+        ));
+        manager
+            .resolve_discrepancy(&found[0], ReconcileAction::Adopt)
+            .await
+            .unwrap();
+
+        let slot = manager.get_slot("stray").await.unwrap();
+        assert_eq!(slot.branch, "feature");
+        assert_eq!(slot.base_branch, "main");
+        assert!(manager.git_status("stray").await.unwrap().vs_base.is_some());
+    }
+
+    #[tokio::test]
+    async fn rebase_finished_outside_the_orchestrator_clears_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, clone_path) = conflicted_slot(dir.path()).await;
+
+        // Resolved and continued by hand, as the agent would
+        std::fs::write(clone_path.join("notes.txt"), "merged\n").unwrap();
+        git(&clone_path, &["add", "notes.txt"]);
+        git(
+            &clone_path,
+            &["-c", "core.editor=true", "rebase", "--continue"],
+        );
+
+        manager.git_status("feat").await.unwrap();
+        let slot = manager.get_slot("feat").await.unwrap();
+        assert_eq!(slot.rebase_status, RebaseStatus::None);
+        assert!(slot.conflicted_files.is_empty());
+        assert!(manager.reconcile().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn conflicting_rebase_is_detected_and_continued() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, clone_path) = conflicted_slot(dir.path()).await;
+        let slot = manager.get_slot("feat").await.unwrap();
+        assert_eq!(slot.rebase_status, RebaseStatus::Conflicted);
+        assert_eq!(slot.conflicted_files, ["notes.txt"]);
+
+        std::fs::write(clone_path.join("notes.txt"), "merged\n").unwrap();
+        git(&clone_path, &["add", "notes.txt"]);
+        assert_eq!(
+            manager.rebase_continue("feat").await.unwrap(),
+            RebaseOutcome::Completed
+        );
+        let slot = manager.get_slot("feat").await.unwrap();
+        assert_eq!(slot.rebase_status, RebaseStatus::None);
+        assert!(slot.conflicted_files.is_empty());
+        assert_eq!(
+            git(&clone_path, &["log", "--format=%s"]),
+            "feature change\nupstream change\ninitial"
+        );
+    }
+
+    #[tokio::test]
+    async fn conflicting_rebase_can_be_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, clone_path) = conflicted_slot(dir.path()).await;
+
+        assert_eq!(
+            manager.rebase_skip("feat").await.unwrap(),
+            RebaseOutcome::Completed
+        );
+        assert_eq!(
+            manager.get_slot("feat").await.unwrap().rebase_status,
+            RebaseStatus::None
+        );
+        // The conflicting commit was dropped
+        assert_eq!(
+            std::fs::read_to_string(clone_path.join("notes.txt")).unwrap(),
+            "upstream\n"
+        );
+        assert_eq!(
+            git(&clone_path, &["rev-parse", "--abbrev-ref", "HEAD"]),
+            "feature"
+        );
+    }
+
+    #[tokio::test]
+    async fn conflicting_rebase_can_be_aborted() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, clone_path) = conflicted_slot(dir.path()).await;
+
+        manager.rebase_abort("feat").await.unwrap();
+        let slot = manager.get_slot("feat").await.unwrap();
+        assert_eq!(slot.rebase_status, RebaseStatus::None);
+        assert!(slot.conflicted_files.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(clone_path.join("notes.txt")).unwrap(),
+            "feature\n"
+        );
+        assert_eq!(
+            git(&clone_path, &["log", "--format=%s"]),
+            "feature change\ninitial"
+        );
+    }
+
+    #[tokio::test]
+    async fn push_after_rebase_outside_the_orchestrator_forces_with_lease() {
+        let dir = tempfile::tempdir().unwrap();
+        let origin = origin_repo(dir.path());
+        let slots_dir = dir.path().join("slots");
+        let clone_path = slots_dir.join("feat");
+        git(
+            dir.path(),
+            &[
+                "clone",
+                "-q",
+                origin.to_str().unwrap(),
+                clone_path.to_str().unwrap(),
+            ],
+        );
+        git(&clone_path, &["checkout", "-q", "-b", "feature"]);
+        std::fs::write(clone_path.join("feature.txt"), "feature\n").unwrap();
+        git(&clone_path, &["add", "feature.txt"]);
+        git(&clone_path, &["commit", "-q", "-m", "feature change"]);
+        git(&clone_path, &["push", "-q", "-u", "origin", "feature"]);
+
+        let mut slot = Slot::new(
+            "feat".into(),
+            origin.to_string_lossy().to_string(),
+            "feature".into(),
+            clone_path.to_string_lossy().to_string(),
+        );
+        slot.base_branch = "main".into();
+        slot.status = SlotStatus::Ready;
+        SlotStateStore::new(&slots_dir).save(&[slot]).await.unwrap();
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let manager = SlotManager::new(slots_dir, tx);
+        manager.load_state().await.unwrap();
+
+        // Rebased by hand, as an agent would, so the orchestrator never saw it
+        std::fs::write(origin.join("notes.txt"), "upstream\n").unwrap();
+        git(&origin, &["commit", "-q", "-am", "upstream change"]);
+        git(&clone_path, &["fetch", "-q", "origin"]);
+        git(&clone_path, &["rebase", "-q", "origin/main"]);
+
+        manager.git_push("feat").await.unwrap();
+        assert_eq!(
+            git(&origin, &["rev-parse", "feature"]),
+            git(&clone_path, &["rev-parse", "HEAD"])
+        );
+        // Up to date with its upstream again, so a further push is a plain one
+        assert!(!git::upstream_rewritten(&clone_path).await.unwrap());
+        manager.git_push("feat").await.unwrap();
+    }
+
+    /// Create slot `name` from a fresh origin repo with `strategy`.
+    async fn provisioned_slot(dir: &Path, strategy: CloneStrategy) -> (SlotManager, PathBuf, Slot) {
+        let origin = origin_repo(dir);
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let manager = SlotManager::new(dir.join("slots"), tx);
+        let slot = manager
+            .create_slot(
+                "feat",
+                origin.to_str().unwrap(),
+                Some("feature/x"),
+                None,
+                Some(strategy),
+            )
+            .await
+            .unwrap();
+        (manager, origin, slot)
+    }
+
+    fn alternates(slot: &Slot) -> String {
+        std::fs::read_to_string(Path::new(&slot.clone_path).join(".git/objects/info/alternates"))
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn worktree_slot_is_added_and_removed_from_the_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, origin, slot) = provisioned_slot(dir.path(), CloneStrategy::Worktree).await;
+        let clone_path = PathBuf::from(&slot.clone_path);
+        assert_eq!(slot.status, SlotStatus::Ready);
+        assert_eq!(slot.clone_strategy, CloneStrategy::Worktree);
+        assert_eq!(
+            (slot.branch.as_str(), slot.base_branch.as_str()),
+            ("feature/x", "main")
+        );
+        assert!(clone_path.join(".git").is_file());
+        assert!(git(&origin, &["worktree", "list"]).contains("feature/x"));
+
+        manager.destroy_slot("feat").await.unwrap();
+        assert!(!clone_path.exists());
+        assert!(!git(&origin, &["worktree", "list"]).contains("feature/x"));
+        // Unregistered, so the branch can be checked out again
+        git(&origin, &["checkout", "-q", "feature/x"]);
+    }
+
+    #[tokio::test]
+    async fn reference_slot_borrows_objects_from_the_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, origin, slot) = provisioned_slot(dir.path(), CloneStrategy::Reference).await;
+        assert_eq!(slot.clone_strategy, CloneStrategy::Reference);
+        assert_eq!(slot.base_branch, "main");
+        assert!(Path::new(&slot.clone_path).join(".git").is_dir());
+        assert!(alternates(&slot).contains(origin.join(".git/objects").to_str().unwrap()));
+        assert_eq!(
+            git(
+                Path::new(&slot.clone_path),
+                &["rev-parse", "--abbrev-ref", "HEAD"]
+            ),
+            "feature/x"
+        );
+
+        manager.destroy_slot("feat").await.unwrap();
+        assert!(!Path::new(&slot.clone_path).exists());
+        assert!(origin.join(".git").is_dir());
+    }
+
+    #[tokio::test]
+    async fn shared_slot_shares_the_source_objects() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, origin, slot) = provisioned_slot(dir.path(), CloneStrategy::Shared).await;
+        assert_eq!(slot.clone_strategy, CloneStrategy::Shared);
+        assert_eq!(slot.base_branch, "main");
+        assert!(alternates(&slot).contains(origin.join(".git/objects").to_str().unwrap()));
+
+        manager.destroy_slot("feat").await.unwrap();
+        assert!(!Path::new(&slot.clone_path).exists());
+        assert!(manager.get_slots().await.is_empty());
+    }
+}
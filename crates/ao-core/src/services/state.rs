@@ -1,57 +1,286 @@
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::error::{OrchestratorError, Result};
 use crate::models::Slot;
 
+/// Schema version written to `state.json`.
+pub const STATE_VERSION: u32 = 1;
+
+/// `MIGRATIONS[n]` upgrades a version `n` document to version `n + 1`.
+const MIGRATIONS: &[fn(Value) -> Value] = &[migrate_v0_bare_array];
+
+/// Version 0 was a bare slot array with no envelope.
+fn migrate_v0_bare_array(slots: Value) -> Value {
+    serde_json::json!({ "version": 1, "slots": slots })
+}
+
+/// Modification time and size of `state.json` as last read or written by this
+/// store, to notice writes from elsewhere.
+type FileStamp = (SystemTime, u64);
+
+#[derive(Serialize)]
+struct StateFileRef<'a> {
+    version: u32,
+    slots: &'a [Slot],
+}
+
+#[derive(Deserialize)]
+struct StateFile {
+    slots: Vec<Slot>,
+}
+
+enum Parsed {
+    Slots(Vec<Slot>),
+    /// Written by a newer build; never overwritten or "recovered".
+    TooNew(u64),
+    Invalid(String),
+}
+
 pub struct SlotStateStore {
     state_file_path: PathBuf,
+    last_stamp: Mutex<Option<FileStamp>>,
+    recovery_note: Mutex<Option<String>>,
+    /// Held for a whole save, since callers save concurrently (under a read
+    /// lock) and the `.bak` copy and temporary file are shared.
+    save_lock: tokio::sync::Mutex<()>,
 }
 
 impl SlotStateStore {
     pub fn new(slots_directory: &Path) -> Self {
         Self {
             state_file_path: slots_directory.join("state.json"),
+            last_stamp: Mutex::new(None),
+            recovery_note: Mutex::new(None),
+            save_lock: tokio::sync::Mutex::new(()),
         }
     }
 
+    /// Load the slots, migrating older schema versions. A file that can't be
+    /// parsed is moved aside to `state.json.corrupt-<time>` and the slots are
+    /// recovered from `state.json.bak`, or salvaged one by one from the damaged
+    /// file; [`Self::take_recovery_note`] then says what happened.
     pub async fn load(&self) -> Result<Vec<Slot>> {
         if !self.state_file_path.exists() {
             return Ok(Vec::new());
         }
-        let json = tokio::fs::read_to_string(&self.state_file_path)
-            .await
-            .map_err(|e| OrchestratorError::State(format!("failed to read state file: {e}")))?;
-        let slots: Vec<Slot> = serde_json::from_str(&json)?;
+        let stamp = self.current_stamp();
+        let json = self.read_file().await?;
+        let slots = match parse_state(&json) {
+            Parsed::Slots(slots) => slots,
+            Parsed::TooNew(version) => return Err(too_new(version)),
+            Parsed::Invalid(reason) => {
+                let slots = self.recover(&json, &reason).await?;
+                self.save(&slots).await?;
+                return Ok(slots);
+            }
+        };
+        self.set_stamp(stamp);
         Ok(slots)
     }
 
+    /// Parse the current file without migrating it on disk or recovering
+    /// from damage, for readers that don't own the slots directory.
+    pub async fn read_snapshot(&self) -> Result<Vec<Slot>> {
+        if !self.state_file_path.exists() {
+            return Ok(Vec::new());
+        }
+        match parse_state(&self.read_file().await?) {
+            Parsed::Slots(slots) => Ok(slots),
+            Parsed::TooNew(version) => Err(too_new(version)),
+            Parsed::Invalid(reason) => Err(OrchestratorError::State(format!(
+                "state file is unreadable: {reason}"
+            ))),
+        }
+    }
+
+    /// Write the state to a temporary file and rename it over `state.json`, so
+    /// readers and crashes never see a partial file. The file being replaced
+    /// is kept as `state.json.bak`.
     pub async fn save(&self, slots: &[Slot]) -> Result<()> {
         if let Some(parent) = self.state_file_path.parent() {
             tokio::fs::create_dir_all(parent).await.map_err(|e| {
                 OrchestratorError::State(format!("failed to create state dir: {e}"))
             })?;
         }
-        let json = serde_json::to_string_pretty(slots)?;
-        tokio::fs::write(&self.state_file_path, json)
-            .await
-            .map_err(|e| OrchestratorError::State(format!("failed to write state file: {e}")))?;
+        let json = serde_json::to_string_pretty(&StateFileRef {
+            version: STATE_VERSION,
+            slots,
+        })?;
+        let path = self.state_file_path.clone();
+        let backup = self.sibling("bak");
+        let _saving = self.save_lock.lock().await;
+        tokio::task::spawn_blocking(move || {
+            if path.exists() {
+                std::fs::copy(&path, &backup)?;
+            }
+            write_atomically(&path, json.as_bytes())
+        })
+        .await
+        .map_err(|e| OrchestratorError::State(format!("state writer panicked: {e}")))?
+        .map_err(|e| OrchestratorError::State(format!("failed to write state file: {e}")))?;
+        self.set_stamp(self.current_stamp());
         Ok(())
     }
+
+    /// Whether `state.json` was written by someone else since this store last
+    /// loaded or saved it.
+    pub fn changed_on_disk(&self) -> bool {
+        let last = *self.last_stamp.lock().unwrap_or_else(|e| e.into_inner());
+        self.current_stamp() != last
+    }
+
+    /// What the last [`Self::load`] had to do to recover from a damaged file.
+    pub fn take_recovery_note(&self) -> Option<String> {
+        self.recovery_note
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    async fn recover(&self, damaged: &str, reason: &str) -> Result<Vec<Slot>> {
+        let corrupt_path = self.sibling(&format!(
+            "corrupt-{}",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S")
+        ));
+        tokio::fs::rename(&self.state_file_path, &corrupt_path)
+            .await
+            .map_err(|e| OrchestratorError::State(format!("failed to move damaged state: {e}")))?;
+        let kept = format!("the damaged file was kept as {}", corrupt_path.display());
+
+        let backup = tokio::fs::read_to_string(self.sibling("bak")).await.ok();
+        let (slots, how) = match backup.as_deref().map(parse_state) {
+            Some(Parsed::Slots(slots)) => (slots, "restored the previous save".to_string()),
+            _ => {
+                let (slots, total) = salvage_slots(damaged);
+                let how = format!("recovered {} of {total} slot(s)", slots.len());
+                (slots, how)
+            }
+        };
+        let note = format!("state.json was unreadable ({reason}); {how}; {kept}");
+        tracing::warn!("{note}");
+        *self.recovery_note.lock().unwrap_or_else(|e| e.into_inner()) = Some(note);
+        Ok(slots)
+    }
+
+    async fn read_file(&self) -> Result<String> {
+        tokio::fs::read_to_string(&self.state_file_path)
+            .await
+            .map_err(|e| OrchestratorError::State(format!("failed to read state file: {e}")))
+    }
+
+    /// `state.json.<suffix>` next to the state file.
+    fn sibling(&self, suffix: &str) -> PathBuf {
+        let mut name = self.state_file_path.as_os_str().to_owned();
+        name.push(format!(".{suffix}"));
+        PathBuf::from(name)
+    }
+
+    fn current_stamp(&self) -> Option<FileStamp> {
+        let metadata = std::fs::metadata(&self.state_file_path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+
+    fn set_stamp(&self, stamp: Option<FileStamp>) {
+        *self.last_stamp.lock().unwrap_or_else(|e| e.into_inner()) = stamp;
+    }
+}
+
+fn parse_state(json: &str) -> Parsed {
+    let mut value: Value = match serde_json::from_str(json) {
+        Ok(value) => value,
+        Err(e) => return Parsed::Invalid(e.to_string()),
+    };
+    let version = match &value {
+        Value::Array(_) => 0,
+        _ => match value.get("version").and_then(Value::as_u64) {
+            Some(version) => version,
+            None => return Parsed::Invalid("missing schema version".into()),
+        },
+    };
+    if version > u64::from(STATE_VERSION) {
+        return Parsed::TooNew(version);
+    }
+    for migrate in &MIGRATIONS[version as usize..] {
+        value = migrate(value);
+    }
+    match serde_json::from_value::<StateFile>(value) {
+        Ok(file) => Parsed::Slots(file.slots),
+        Err(e) => Parsed::Invalid(e.to_string()),
+    }
+}
+
+/// Keep every slot entry of a damaged file that still deserializes on its
+/// own. Returns the salvaged slots and how many entries there were.
+fn salvage_slots(damaged: &str) -> (Vec<Slot>, usize) {
+    let entries = match serde_json::from_str::<Value>(damaged) {
+        Ok(Value::Array(entries)) => entries,
+        Ok(Value::Object(mut file)) => match file.remove("slots") {
+            Some(Value::Array(entries)) => entries,
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    let total = entries.len();
+    let slots = entries
+        .into_iter()
+        .filter_map(|entry| serde_json::from_value(entry).ok())
+        .collect();
+    (slots, total)
+}
+
+fn too_new(version: u64) -> OrchestratorError {
+    OrchestratorError::State(format!(
+        "state.json has schema version {version}, but this build only understands up to \
+         {STATE_VERSION}; upgrade ao-tui"
+    ))
+}
+
+fn write_atomically(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = PathBuf::from(tmp_name);
+
+    let result = (|| {
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp_path, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{AgentStatus, DiscoveredServices, SlotStatus};
+    use crate::models::{AgentStatus, CloneStrategy, DiscoveredServices, RebaseStatus, SlotStatus};
 
     fn test_slot() -> Slot {
         Slot {
             name: "test-1".into(),
             repo_path: "/repo".into(),
             branch: "main".into(),
+            base_branch: "main".into(),
             clone_path: "/clone/test-1".into(),
+            clone_strategy: CloneStrategy::Clone,
             status: SlotStatus::Ready,
+            last_error: None,
+            creation: None,
             agent_status: AgentStatus::None,
+            rebase_status: RebaseStatus::None,
+            conflicted_files: vec![],
+            pull_request: None,
+            problems: vec![],
+            telemetry: None,
             port_allocations: vec![],
             services: DiscoveredServices::default(),
             created_at: chrono::Utc::now(),
@@ -61,6 +290,38 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn concurrent_saves_all_succeed() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = std::sync::Arc::new(SlotStateStore::new(dir.path()));
+        store.save(&[test_slot()]).await.unwrap();
+
+        let saves: Vec<_> = (0..16)
+            .map(|_| {
+                let store = std::sync::Arc::clone(&store);
+                tokio::spawn(async move { store.save(&[test_slot()]).await })
+            })
+            .collect();
+        for save in saves {
+            save.await.unwrap().unwrap();
+        }
+
+        assert_eq!(store.load().await.unwrap().len(), 1);
+        let backup = std::fs::read_to_string(store.sibling("bak")).unwrap();
+        assert!(matches!(parse_state(&backup), Parsed::Slots(_)));
+        let leftovers = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter(|e| {
+                e.as_ref()
+                    .unwrap()
+                    .path()
+                    .to_string_lossy()
+                    .ends_with(".tmp")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+
     #[tokio::test]
     async fn round_trip_state() {
         let dir = tempfile::tempdir().unwrap();
@@ -83,6 +344,115 @@ mod tests {
         assert!(loaded.is_empty());
     }
 
+    #[tokio::test]
+    async fn save_replaces_file_without_leftovers() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SlotStateStore::new(dir.path());
+        store.save(&[test_slot()]).await.unwrap();
+        store.save(&[]).await.unwrap();
+
+        let mut names: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["state.json", "state.json.bak"]);
+        assert!(store.load().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn migrates_bare_array_from_version_zero() {
+        let dir = tempfile::tempdir().unwrap();
+        let legacy = serde_json::to_string(&vec![test_slot()]).unwrap();
+        std::fs::write(dir.path().join("state.json"), legacy).unwrap();
+
+        let store = SlotStateStore::new(dir.path());
+        let loaded = store.load().await.unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert!(store.take_recovery_note().is_none());
+
+        store.save(&loaded).await.unwrap();
+        let saved: Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join("state.json")).unwrap())
+                .unwrap();
+        assert_eq!(saved["version"], STATE_VERSION);
+        assert_eq!(saved["slots"][0]["name"], "test-1");
+    }
+
+    #[tokio::test]
+    async fn refuses_newer_schema_without_touching_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let newer = r#"{"version": 99, "slots": []}"#;
+        std::fs::write(dir.path().join("state.json"), newer).unwrap();
+
+        let err = SlotStateStore::new(dir.path()).load().await.unwrap_err();
+        assert!(err.to_string().contains("schema version 99"));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("state.json")).unwrap(),
+            newer
+        );
+    }
+
+    #[tokio::test]
+    async fn restores_previous_save_when_file_is_damaged() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SlotStateStore::new(dir.path());
+        store.save(&[test_slot()]).await.unwrap();
+        store.save(&[test_slot()]).await.unwrap();
+        std::fs::write(dir.path().join("state.json"), "{\"version\": 1, \"slo").unwrap();
+
+        let loaded = store.load().await.unwrap();
+        assert_eq!(loaded.len(), 1);
+        let note = store.take_recovery_note().unwrap();
+        assert!(note.contains("restored the previous save"), "{note}");
+
+        let names: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert!(names.iter().any(|n| n.starts_with("state.json.corrupt-")));
+        // The recovered state is written back as a valid file
+        assert_eq!(
+            SlotStateStore::new(dir.path()).load().await.unwrap().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn salvages_valid_slots_without_a_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = serde_json::to_value(test_slot()).unwrap();
+        let damaged = serde_json::json!({
+            "version": 1,
+            "slots": [good, { "name": "broken" }],
+        });
+        std::fs::write(dir.path().join("state.json"), damaged.to_string()).unwrap();
+
+        let store = SlotStateStore::new(dir.path());
+        let loaded = store.load().await.unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "test-1");
+        let note = store.take_recovery_note().unwrap();
+        assert!(note.contains("recovered 1 of 2 slot(s)"), "{note}");
+    }
+
+    #[tokio::test]
+    async fn detects_writes_from_another_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SlotStateStore::new(dir.path());
+        store.save(&[]).await.unwrap();
+        assert!(!store.changed_on_disk());
+
+        SlotStateStore::new(dir.path())
+            .save(&[test_slot()])
+            .await
+            .unwrap();
+        assert!(store.changed_on_disk());
+
+        assert_eq!(store.load().await.unwrap().len(), 1);
+        assert!(!store.changed_on_disk());
+    }
+
     #[tokio::test]
     async fn state_uses_camel_case() {
         let dir = tempfile::tempdir().unwrap();
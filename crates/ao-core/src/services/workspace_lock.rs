@@ -0,0 +1,71 @@
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+
+use crate::error::{OrchestratorError, Result};
+
+const LOCK_FILE: &str = ".lock";
+
+/// Advisory lock on a slots directory, held by the one process that manages
+/// its slots (an in-process TUI or CLI command, or the daemon) until dropped.
+/// The lock file records the holder's PID for error messages.
+#[derive(Debug)]
+pub struct WorkspaceLock {
+    _file: File,
+}
+
+impl WorkspaceLock {
+    /// Take the lock, failing with [`OrchestratorError::WorkspaceLocked`] if
+    /// another process holds it.
+    pub fn acquire(slots_directory: &Path) -> Result<WorkspaceLock> {
+        std::fs::create_dir_all(slots_directory)?;
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(slots_directory.join(LOCK_FILE))?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut holder = String::new();
+                let _ = file.read_to_string(&mut holder);
+                let holder = match holder.trim().parse::<u32>() {
+                    Ok(pid) => format!(" (pid {pid})"),
+                    Err(_) => String::new(),
+                };
+                return Err(OrchestratorError::WorkspaceLocked(format!(
+                    "{}{holder}",
+                    slots_directory.display()
+                )));
+            }
+            Err(TryLockError::Error(e)) => return Err(e.into()),
+        }
+
+        file.set_len(0)?;
+        file.rewind()?;
+        write!(file, "{}", std::process::id())?;
+        file.flush()?;
+        Ok(WorkspaceLock { _file: file })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_holder_is_refused_until_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = WorkspaceLock::acquire(dir.path()).unwrap();
+
+        let err = WorkspaceLock::acquire(dir.path()).unwrap_err();
+        let message = err.to_string();
+        assert!(matches!(err, OrchestratorError::WorkspaceLocked(_)));
+        assert!(message.contains(&format!("pid {}", std::process::id())));
+
+        drop(lock);
+        WorkspaceLock::acquire(dir.path()).unwrap();
+    }
+}
@@ -2,13 +2,24 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use std::time::Instant;
 
+use chrono::{DateTime, Utc};
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use tokio::sync::Mutex;
 
-use ao_core::models::{AgentStatus, RepoCandidate, Slot, SlotStatus};
+use ao_core::models::{
+    AgentStatus, DiffTarget, Discrepancy, ErrorSpan, FileDiff, GitStatus, RebaseStatus,
+    RepoCandidate, SavedPrompt, Slot, SlotStatus,
+};
 use ao_core::services::agent_host::AgentConnection;
-use ao_core::services::log_tailer::LogSource as CoreLogSource;
+use ao_core::services::blueprint;
+use ao_core::services::git;
+use ao_core::services::log_parser::LogParser;
+pub use ao_core::services::log_store::Severity;
+use ao_core::services::log_store::{LogCursor, LogPage, LogQuery, LogRecord};
+use ao_core::services::log_tailer::{self, LogSource as CoreLogSource};
+
+use crate::text_area::TextArea;
 
 /// The active mode determines which UI is shown and how keys are dispatched.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -28,6 +39,21 @@ pub enum Mode {
     BatchProgress,
     /// Full-screen terminal mode for the selected slot.
     Terminal,
+    /// Full-screen diff viewer for the selected slot.
+    DiffView,
+    /// Actions for a rebase that stopped on conflicts.
+    RebaseConflictDialog {
+        slot_name: String,
+    },
+    /// Problems from a slot's last build, scrolled by `scroll` problems.
+    ProblemsDialog {
+        slot_name: String,
+        scroll: usize,
+    },
+    /// Differences between saved state and the slots directory, found at startup.
+    ReconcileDialog,
+    /// Saved prompts to insert into the create or spawn-agent dialog.
+    PromptPickerDialog,
 }
 
 /// What a confirmed dialog action should do.
@@ -80,15 +106,6 @@ impl Default for SlotActivity {
 
 // ─── Log Multiplexer ───────────────────────────────────────────────────
 
-/// Severity level heuristically determined from log text.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Severity {
-    Error,
-    Warn,
-    Info,
-    Debug,
-}
-
 /// A single entry in the multiplexed log buffer.
 pub struct LogEntry {
     pub slot_name: String,
@@ -96,6 +113,55 @@ pub struct LogEntry {
     pub text: String,
     pub severity: Severity,
     pub color_index: u8,
+    /// When the line was captured.
+    pub timestamp: DateTime<Utc>,
+    /// The logger category, for lines in a .NET log format.
+    pub category: Option<String>,
+    /// Set for a line that continues the entry above it, such as a stack
+    /// trace under an error.
+    pub continuation: bool,
+}
+
+/// A line of the selected slot's log view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogViewLine {
+    /// When the line was captured, if known. Lines read back from an agent
+    /// log file have no time.
+    pub timestamp: Option<DateTime<Utc>>,
+    pub text: String,
+}
+
+impl From<&str> for LogViewLine {
+    /// A line as read from a slot's log file, keeping a capture time
+    /// written before it.
+    fn from(line: &str) -> Self {
+        let (timestamp, text) = log_tailer::split_timestamp(line);
+        Self {
+            timestamp,
+            text: text.to_string(),
+        }
+    }
+}
+
+/// How log views show when each line was captured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeDisplay {
+    #[default]
+    Hidden,
+    /// Local time of day.
+    Clock,
+    /// Time elapsed since, e.g. `5m ago`.
+    Relative,
+}
+
+impl TimeDisplay {
+    pub fn next(self) -> Self {
+        match self {
+            TimeDisplay::Hidden => TimeDisplay::Clock,
+            TimeDisplay::Clock => TimeDisplay::Relative,
+            TimeDisplay::Relative => TimeDisplay::Hidden,
+        }
+    }
 }
 
 /// Ring buffer holding interleaved log entries from all slots.
@@ -103,6 +169,8 @@ pub struct LogBuffer {
     pub entries: VecDeque<LogEntry>,
     pub slot_colors: HashMap<String, u8>,
     next_color: u8,
+    /// One parser per slot and source, as each is a separate stream.
+    parsers: HashMap<(String, CoreLogSource), LogParser>,
 }
 
 impl Default for LogBuffer {
@@ -111,6 +179,7 @@ impl Default for LogBuffer {
             entries: VecDeque::with_capacity(Self::CAPACITY),
             slot_colors: HashMap::new(),
             next_color: 0,
+            parsers: HashMap::new(),
         }
     }
 }
@@ -122,21 +191,43 @@ impl LogBuffer {
         Self::default()
     }
 
-    pub fn push(&mut self, slot_name: String, source: CoreLogSource, text: String) {
+    /// Add a line in order of capture time. Each source sends its lines in
+    /// order, so only lines racing in from different slots or sources land
+    /// anywhere but the end.
+    pub fn push(
+        &mut self,
+        slot_name: String,
+        source: CoreLogSource,
+        text: String,
+        timestamp: DateTime<Utc>,
+    ) {
         let color_index = self.color_for_slot(&slot_name);
-        let severity = classify_severity(&text);
+        let parsed = self
+            .parsers
+            .entry((slot_name.clone(), source))
+            .or_default()
+            .parse(source, &text);
 
         if self.entries.len() >= Self::CAPACITY {
             self.entries.pop_front();
         }
 
-        self.entries.push_back(LogEntry {
+        let entry = LogEntry {
             slot_name,
             source,
             text,
-            severity,
+            severity: parsed.severity,
             color_index,
-        });
+            timestamp,
+            category: parsed.category,
+            continuation: parsed.continuation,
+        };
+        let position = self
+            .entries
+            .iter()
+            .rposition(|e| e.timestamp <= timestamp)
+            .map_or(0, |i| i + 1);
+        self.entries.insert(position, entry);
     }
 
     fn color_for_slot(&mut self, name: &str) -> u8 {
@@ -150,17 +241,107 @@ impl LogBuffer {
     }
 }
 
-/// Heuristic severity classification from log text.
-fn classify_severity(text: &str) -> Severity {
-    let lower = text.to_lowercase();
-    if lower.contains("error") || lower.contains("exception") || lower.contains("fatal") {
-        Severity::Error
-    } else if lower.contains("warn") {
-        Severity::Warn
-    } else if lower.contains("debug") || lower.contains("trace") {
-        Severity::Debug
-    } else {
-        Severity::Info
+/// How far back a history search reaches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryRange {
+    LastHour,
+    LastDay,
+    LastWeek,
+    All,
+}
+
+impl HistoryRange {
+    pub fn next(self) -> Self {
+        match self {
+            HistoryRange::LastHour => HistoryRange::LastDay,
+            HistoryRange::LastDay => HistoryRange::LastWeek,
+            HistoryRange::LastWeek => HistoryRange::All,
+            HistoryRange::All => HistoryRange::LastHour,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HistoryRange::LastHour => "last hour",
+            HistoryRange::LastDay => "last 24h",
+            HistoryRange::LastWeek => "last 7 days",
+            HistoryRange::All => "all",
+        }
+    }
+
+    pub fn since(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            HistoryRange::LastHour => Some(now - chrono::Duration::hours(1)),
+            HistoryRange::LastDay => Some(now - chrono::Duration::days(1)),
+            HistoryRange::LastWeek => Some(now - chrono::Duration::days(7)),
+            HistoryRange::All => None,
+        }
+    }
+}
+
+/// A search of the persisted log history, shown in the multiplex view in
+/// place of the live buffer.
+pub struct LogHistoryState {
+    pub query: LogQuery,
+    pub range: HistoryRange,
+    /// Loaded results, oldest first. Older pages are prepended.
+    pub records: Vec<LogRecord>,
+    /// Where older results continue; `None` once all are loaded.
+    pub next: Option<LogCursor>,
+    pub loading: bool,
+    /// Lines scrolled up from the newest result.
+    pub scroll_back: usize,
+    /// Identifies the current search, so pages of a replaced one are dropped.
+    pub generation: u64,
+    /// Set when the search must be rerun even if the filters are unchanged.
+    pub stale: bool,
+}
+
+impl LogHistoryState {
+    /// Results per page.
+    pub const PAGE: usize = 500;
+
+    pub fn new(range: HistoryRange) -> Self {
+        Self {
+            query: LogQuery::default(),
+            range,
+            records: Vec::new(),
+            next: None,
+            loading: false,
+            scroll_back: 0,
+            generation: 0,
+            stale: true,
+        }
+    }
+
+    /// Start over with a new query. Returns the generation to tag its pages.
+    pub fn restart(&mut self, query: LogQuery) -> u64 {
+        self.query = query;
+        self.records.clear();
+        self.next = None;
+        self.scroll_back = 0;
+        self.loading = true;
+        self.stale = false;
+        self.generation += 1;
+        self.generation
+    }
+
+    /// Add a page of older results if it belongs to the current search.
+    pub fn add_page(&mut self, generation: u64, page: LogPage) {
+        if generation != self.generation {
+            return;
+        }
+        self.records.splice(0..0, page.records);
+        self.next = page.next;
+        self.loading = false;
+    }
+
+    /// Whether scrolling has come close enough to the oldest loaded result
+    /// that the next page should be fetched.
+    pub fn wants_older(&self) -> bool {
+        !self.loading
+            && self.next.is_some()
+            && self.scroll_back + Self::PAGE / 5 >= self.records.len()
     }
 }
 
@@ -169,6 +350,8 @@ fn classify_severity(text: &str) -> Severity {
 pub struct MultiplexFilter {
     pub hidden_slots: HashSet<String>,
     pub source_filter: Option<CoreLogSource>,
+    /// The least severe lines shown; `None` shows all.
+    pub min_severity: Option<Severity>,
     pub search_text: String,
     pub search_regex: Option<regex::Regex>,
     pub search_filter_mode: bool,
@@ -192,10 +375,26 @@ impl MultiplexFilter {
         self.source_filter = match self.source_filter {
             None => Some(CoreLogSource::Agent),
             Some(CoreLogSource::Agent) => Some(CoreLogSource::Aspire),
-            Some(CoreLogSource::Aspire) => None,
+            Some(CoreLogSource::Aspire) => Some(CoreLogSource::Telemetry),
+            Some(CoreLogSource::Telemetry) => None,
+        };
+    }
+
+    pub fn cycle_severity(&mut self) {
+        self.min_severity = match self.min_severity {
+            None => Some(Severity::Warn),
+            Some(Severity::Warn) => Some(Severity::Error),
+            Some(_) => None,
         };
     }
 
+    /// Whether the search matches a line's text or category.
+    pub fn search_matches(&self, entry: &LogEntry) -> bool {
+        self.search_regex.as_ref().is_none_or(|re| {
+            re.is_match(&entry.text) || entry.category.as_ref().is_some_and(|c| re.is_match(c))
+        })
+    }
+
     pub fn matches_entry(&self, entry: &LogEntry) -> bool {
         if self.hidden_slots.contains(&entry.slot_name) {
             return false;
@@ -205,14 +404,12 @@ impl MultiplexFilter {
                 return false;
             }
         }
-        if self.search_filter_mode {
-            if let Some(ref re) = self.search_regex {
-                if !re.is_match(&entry.text) {
-                    return false;
-                }
+        if let Some(min) = self.min_severity {
+            if !entry.severity.is_at_least(min) {
+                return false;
             }
         }
-        true
+        !self.search_filter_mode || self.search_matches(entry)
     }
 }
 
@@ -241,6 +438,106 @@ impl BlueprintListState {
     }
 }
 
+/// State for the reconcile dialog.
+#[derive(Default)]
+pub struct ReconcileState {
+    pub items: Vec<Discrepancy>,
+    pub selected: usize,
+}
+
+impl ReconcileState {
+    /// Drop the selected item after it has been acted on.
+    pub fn take_selected(&mut self) -> Option<Discrepancy> {
+        if self.selected >= self.items.len() {
+            return None;
+        }
+        let item = self.items.remove(self.selected);
+        if self.selected >= self.items.len() && self.selected > 0 {
+            self.selected -= 1;
+        }
+        Some(item)
+    }
+}
+
+/// State for the prompt library picker.
+pub struct PromptPickerState {
+    pub prompts: Vec<SavedPrompt>,
+    pub query: String,
+    /// Indices into `prompts` that match the query, best first.
+    pub filtered: Vec<usize>,
+    pub selected: usize,
+    pub loading: bool,
+    /// The dialog the picked prompt goes into, and returns to.
+    pub target: PromptField,
+}
+
+impl Default for PromptPickerState {
+    fn default() -> Self {
+        Self::new(PromptField::CreateSlot)
+    }
+}
+
+impl PromptPickerState {
+    pub fn new(target: PromptField) -> Self {
+        Self {
+            prompts: Vec::new(),
+            query: String::new(),
+            filtered: Vec::new(),
+            selected: 0,
+            loading: true,
+            target,
+        }
+    }
+
+    pub fn set_prompts(&mut self, prompts: Vec<SavedPrompt>) {
+        self.prompts = prompts;
+        self.loading = false;
+        self.apply_filter();
+    }
+
+    /// Fuzzy-match names and descriptions against the query.
+    pub fn apply_filter(&mut self) {
+        let query = self.query.trim();
+        self.filtered = if query.is_empty() {
+            (0..self.prompts.len()).collect()
+        } else {
+            let matcher = SkimMatcherV2::default();
+            let mut scored: Vec<(i64, usize)> = self
+                .prompts
+                .iter()
+                .enumerate()
+                .filter_map(|(i, p)| {
+                    let name_score = matcher.fuzzy_match(&p.name, query);
+                    let desc_score = p
+                        .description
+                        .as_deref()
+                        .and_then(|d| matcher.fuzzy_match(d, query));
+                    name_score.max(desc_score).map(|score| (score, i))
+                })
+                .collect();
+            scored.sort_by_key(|s| std::cmp::Reverse(s.0));
+            scored.into_iter().map(|(_, i)| i).collect()
+        };
+        self.selected = 0;
+    }
+
+    pub fn selected_prompt(&self) -> Option<&SavedPrompt> {
+        self.filtered
+            .get(self.selected)
+            .and_then(|&i| self.prompts.get(i))
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.filtered.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
 /// State for the blueprint save dialog.
 pub struct BlueprintSaveState {
     pub name: String,
@@ -293,32 +590,128 @@ impl BatchProgressState {
     }
 }
 
+// ─── Diff Viewer ───────────────────────────────────────────────────────
+
+/// State for the diff viewer of a single slot.
+pub struct DiffViewState {
+    pub slot_name: String,
+    pub target: DiffTarget,
+    pub files: Vec<FileDiff>,
+    pub selected_file: usize,
+    pub scroll: usize,
+    pub loading: bool,
+}
+
+impl DiffViewState {
+    pub fn new(slot_name: &str, target: DiffTarget) -> Self {
+        Self {
+            slot_name: slot_name.to_string(),
+            target,
+            files: Vec::new(),
+            selected_file: 0,
+            scroll: 0,
+            loading: true,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&FileDiff> {
+        self.files.get(self.selected_file)
+    }
+
+    /// Number of rendered rows for the selected file (hunk headers + lines).
+    pub fn line_count(&self) -> usize {
+        self.selected()
+            .map(|f| f.hunks.iter().map(|h| h.lines.len() + 1).sum())
+            .unwrap_or(0)
+    }
+
+    pub fn next_file(&mut self) {
+        if self.selected_file + 1 < self.files.len() {
+            self.selected_file += 1;
+            self.scroll = 0;
+        }
+    }
+
+    pub fn prev_file(&mut self) {
+        if self.selected_file > 0 {
+            self.selected_file -= 1;
+            self.scroll = 0;
+        }
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.line_count().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+
+    /// Replace the file list, keeping the selection on the same path if it still exists.
+    pub fn set_files(&mut self, files: Vec<FileDiff>) {
+        let current = self.selected().map(|f| f.path.clone());
+        self.files = files;
+        self.loading = false;
+        match current.and_then(|p| self.files.iter().position(|f| f.path == p)) {
+            Some(idx) => self.selected_file = idx,
+            None => {
+                self.selected_file = 0;
+                self.scroll = 0;
+            }
+        }
+        self.scroll = self.scroll.min(self.line_count().saturating_sub(1));
+    }
+}
+
 // ─── Forms ─────────────────────────────────────────────────────────────
 
 /// Form state for the create-slot dialog.
 #[derive(Debug)]
 pub struct CreateSlotForm {
     pub source: String,
-    pub prompt: String,
+    /// Slot name; suggested from the source until the user edits it.
+    pub name: String,
+    pub name_edited: bool,
+    /// Branch to check out. Empty means the source's default branch.
+    pub branch: String,
+    /// Create `branch` from the base branch instead of checking out an existing one.
+    pub new_branch: bool,
+    pub prompt: TextArea,
     pub focus: CreateSlotField,
     pub all_candidates: Vec<RepoCandidate>,
     pub filtered_candidates: Vec<RepoCandidate>,
     pub selected_candidate: Option<usize>,
     pub scan_loading: bool,
     pub filter_deadline: Option<Instant>,
+    /// Branches of the source, for completion and validation.
+    pub all_branches: Vec<String>,
+    pub filtered_branches: Vec<String>,
+    pub selected_branch: Option<usize>,
+    /// The source `all_branches` was (or is being) listed for.
+    pub branches_source: Option<String>,
+    pub branches_loading: bool,
+    /// Problems found when the form was last submitted, shown next to their field.
+    pub errors: Vec<(CreateSlotField, String)>,
 }
 
 impl Default for CreateSlotForm {
     fn default() -> Self {
         Self {
             source: String::new(),
-            prompt: String::new(),
+            name: String::new(),
+            name_edited: false,
+            branch: String::new(),
+            new_branch: false,
+            prompt: TextArea::new(),
             focus: CreateSlotField::default(),
             all_candidates: Vec::new(),
             filtered_candidates: Vec::new(),
             selected_candidate: None,
             scan_loading: true,
             filter_deadline: None,
+            all_branches: Vec::new(),
+            filtered_branches: Vec::new(),
+            selected_branch: None,
+            branches_source: None,
+            branches_loading: false,
+            errors: Vec::new(),
         }
     }
 }
@@ -349,7 +742,7 @@ impl CreateSlotForm {
                     }
                 })
                 .collect();
-            scored.sort_by(|a, b| b.0.cmp(&a.0));
+            scored.sort_by_key(|s| std::cmp::Reverse(s.0));
             self.filtered_candidates = scored.into_iter().take(6).map(|(_, c)| c.clone()).collect();
         }
 
@@ -399,19 +792,182 @@ impl CreateSlotForm {
         }
         false
     }
+
+    /// Suggest `<repo>-N` as the slot name, with the lowest N not taken by
+    /// `slots`, unless the user has typed a name.
+    pub fn suggest_name(&mut self, slots: &[Slot]) {
+        if self.name_edited {
+            return;
+        }
+        let source = self.source.trim().trim_end_matches('/');
+        let repo = source.rsplit(['/', '\\', ':']).next().unwrap_or_default();
+        let repo: String = repo
+            .trim_end_matches(".git")
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        if repo.is_empty() {
+            self.name.clear();
+            return;
+        }
+        self.name = (1..)
+            .map(|n| format!("{repo}-{n}"))
+            .find(|name| slots.iter().all(|s| &s.name != name))
+            .unwrap_or_default();
+    }
+
+    /// Fuzzy-match the source's branches against the branch field. Nothing is
+    /// offered for a new branch.
+    pub fn filter_branches(&mut self) {
+        let query = self.branch.trim();
+        self.filtered_branches = if self.new_branch {
+            Vec::new()
+        } else if query.is_empty() {
+            self.all_branches.iter().take(6).cloned().collect()
+        } else {
+            let matcher = SkimMatcherV2::default();
+            let mut scored: Vec<(i64, &String)> = self
+                .all_branches
+                .iter()
+                .filter_map(|b| matcher.fuzzy_match(b, query).map(|score| (score, b)))
+                .collect();
+            scored.sort_by_key(|s| std::cmp::Reverse(s.0));
+            scored.into_iter().take(6).map(|(_, b)| b.clone()).collect()
+        };
+        self.selected_branch = if self.filtered_branches.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+    }
+
+    pub fn select_next_branch(&mut self) {
+        if let Some(idx) = self.selected_branch {
+            if idx + 1 < self.filtered_branches.len() {
+                self.selected_branch = Some(idx + 1);
+            }
+        }
+    }
+
+    pub fn select_prev_branch(&mut self) {
+        if let Some(idx) = self.selected_branch {
+            if idx > 0 {
+                self.selected_branch = Some(idx - 1);
+            }
+        }
+    }
+
+    pub fn accept_selected_branch(&mut self) -> bool {
+        let Some(branch) = self
+            .selected_branch
+            .and_then(|idx| self.filtered_branches.get(idx))
+        else {
+            return false;
+        };
+        self.branch = branch.clone();
+        self.filtered_branches.clear();
+        self.selected_branch = None;
+        true
+    }
+
+    /// Whether `all_branches` is a complete list for the current source.
+    fn branches_known(&self) -> bool {
+        !self.branches_loading
+            && !self.all_branches.is_empty()
+            && self.branches_source.as_deref() == Some(self.source.trim())
+    }
+
+    /// Check the form before creating the slot.
+    pub fn validate(&self, slots: &[Slot]) -> Vec<(CreateSlotField, String)> {
+        let mut errors = Vec::new();
+        if self.source.trim().is_empty() {
+            errors.push((CreateSlotField::Source, "source is required".to_string()));
+        }
+
+        let name = self.name.trim();
+        if let Err(e) = Slot::validate_name(name) {
+            errors.push((CreateSlotField::Name, e));
+        } else if slots.iter().any(|s| s.name == name) {
+            errors.push((CreateSlotField::Name, format!("'{name}' already exists")));
+        }
+
+        let branch = self.branch.trim();
+        if branch.is_empty() {
+            if self.new_branch {
+                errors.push((CreateSlotField::Branch, "name the new branch".to_string()));
+            }
+        } else if let Err(e) = git::check_branch_name(branch) {
+            errors.push((CreateSlotField::Branch, e));
+        } else if self.branches_known() {
+            let exists = self.all_branches.iter().any(|b| b == branch);
+            if self.new_branch && exists {
+                errors.push((
+                    CreateSlotField::Branch,
+                    format!("'{branch}' exists; turn off new branch"),
+                ));
+            } else if !self.new_branch && !exists {
+                errors.push((
+                    CreateSlotField::Branch,
+                    format!("no branch '{branch}'; turn on new branch"),
+                ));
+            }
+        }
+        errors
+    }
+
+    pub fn error_for(&self, field: CreateSlotField) -> Option<&str> {
+        self.errors
+            .iter()
+            .find(|(f, _)| *f == field)
+            .map(|(_, e)| e.as_str())
+    }
+
+    /// The branch to pass to `create_slot`.
+    pub fn branch_request(&self) -> Option<String> {
+        let branch = self.branch.trim();
+        (!branch.is_empty()).then(|| branch.to_string())
+    }
 }
 
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum CreateSlotField {
     #[default]
     Source,
+    Name,
+    Branch,
     Prompt,
 }
 
+impl CreateSlotField {
+    pub fn next(self) -> Self {
+        match self {
+            CreateSlotField::Source => CreateSlotField::Name,
+            CreateSlotField::Name => CreateSlotField::Branch,
+            CreateSlotField::Branch => CreateSlotField::Prompt,
+            CreateSlotField::Prompt => CreateSlotField::Source,
+        }
+    }
+
+    pub fn prev(self) -> Self {
+        match self {
+            CreateSlotField::Source => CreateSlotField::Prompt,
+            CreateSlotField::Name => CreateSlotField::Source,
+            CreateSlotField::Branch => CreateSlotField::Name,
+            CreateSlotField::Prompt => CreateSlotField::Branch,
+        }
+    }
+}
+
 /// Form state for the spawn-agent dialog.
 #[derive(Debug)]
 pub struct SpawnAgentForm {
-    pub prompt: String,
+    pub prompt: TextArea,
     pub allowed_tools: String,
     pub max_turns: String,
     pub focus: SpawnAgentField,
@@ -420,7 +976,7 @@ pub struct SpawnAgentForm {
 impl Default for SpawnAgentForm {
     fn default() -> Self {
         Self {
-            prompt: String::new(),
+            prompt: TextArea::new(),
             allowed_tools: "Bash,Read,Glob,Grep,Write,Edit,WebFetch,WebSearch,Task".into(),
             max_turns: String::new(),
             focus: SpawnAgentField::Prompt,
@@ -436,6 +992,14 @@ pub enum SpawnAgentField {
     MaxTurns,
 }
 
+/// A prompt field that can be handed to `$EDITOR` or filled from the prompt
+/// library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptField {
+    CreateSlot,
+    SpawnAgent,
+}
+
 // ─── Main App State ────────────────────────────────────────────────────
 
 /// Top-level application state.
@@ -445,13 +1009,18 @@ pub struct App {
     pub slots: Vec<Slot>,
     pub selected_index: usize,
     pub log_source: LogSource,
-    pub log_lines: Vec<String>,
+    pub log_lines: Vec<LogViewLine>,
     pub log_scroll: usize,
     pub log_auto_follow: bool,
+    /// How the log views show capture times.
+    pub log_time: TimeDisplay,
     pub should_quit: bool,
     pub status_message: Option<String>,
     pub create_form: CreateSlotForm,
     pub agent_form: SpawnAgentForm,
+    /// A prompt to open in `$EDITOR`; the main loop owns the terminal, so it
+    /// does the editing.
+    pub external_edit: Option<PromptField>,
 
     // Terminal
     pub terminal_parsers: HashMap<String, vt100::Parser>,
@@ -461,18 +1030,32 @@ pub struct App {
     pub activity: HashMap<String, SlotActivity>,
     pub dashboard_selected: usize,
 
+    // Git
+    pub git_status: HashMap<String, GitStatus>,
+
     // Log Multiplexer
     pub log_buffer: LogBuffer,
     pub multiplex_filter: MultiplexFilter,
     pub multiplex_scroll: usize,
     pub multiplex_auto_follow: bool,
+    /// Set while the multiplex view shows a history search.
+    pub log_history: Option<LogHistoryState>,
 
     // Blueprint
     pub blueprint_list: BlueprintListState,
     pub blueprint_save: BlueprintSaveState,
 
+    // Reconcile
+    pub reconcile: ReconcileState,
+
+    // Prompt library
+    pub prompt_picker: PromptPickerState,
+
     // Batch
     pub batch_progress: Option<BatchProgressState>,
+
+    // Diff
+    pub diff_view: Option<DiffViewState>,
 }
 
 impl Default for App {
@@ -492,24 +1075,112 @@ impl App {
             log_lines: Vec::new(),
             log_scroll: 0,
             log_auto_follow: true,
+            log_time: TimeDisplay::default(),
             should_quit: false,
             status_message: None,
             create_form: CreateSlotForm::default(),
             agent_form: SpawnAgentForm::default(),
+            external_edit: None,
             terminal_parsers: HashMap::new(),
             agent_connections: HashMap::new(),
             activity: HashMap::new(),
             dashboard_selected: 0,
+            git_status: HashMap::new(),
             log_buffer: LogBuffer::new(),
             multiplex_filter: MultiplexFilter::new(),
             multiplex_scroll: 0,
             multiplex_auto_follow: true,
+            log_history: None,
             blueprint_list: BlueprintListState::new(),
             blueprint_save: BlueprintSaveState::new(),
+            reconcile: ReconcileState::default(),
+            prompt_picker: PromptPickerState::default(),
             batch_progress: None,
+            diff_view: None,
         }
     }
 
+    pub fn prompt_mut(&mut self, field: PromptField) -> &mut TextArea {
+        match field {
+            PromptField::CreateSlot => &mut self.create_form.prompt,
+            PromptField::SpawnAgent => &mut self.agent_form.prompt,
+        }
+    }
+
+    /// The history query matching the multiplex view's filters: its search
+    /// text, source, and shown slots. With no slot hidden, every slot is
+    /// searched, including deleted ones.
+    pub fn history_query(&self, range: HistoryRange, now: DateTime<Utc>) -> LogQuery {
+        let filter = &self.multiplex_filter;
+        let slots = if filter.hidden_slots.is_empty() {
+            Vec::new()
+        } else {
+            self.slots
+                .iter()
+                .filter(|s| !filter.hidden_slots.contains(&s.name))
+                .map(|s| s.name.clone())
+                .collect()
+        };
+        LogQuery {
+            text: filter.search_text.clone(),
+            slots,
+            source: filter.source_filter,
+            min_severity: filter.min_severity,
+            since: range.since(now),
+            until: None,
+        }
+    }
+
+    /// The dialog mode a prompt field belongs to.
+    pub fn prompt_dialog_mode(field: PromptField) -> Mode {
+        match field {
+            PromptField::CreateSlot => Mode::CreateSlotDialog,
+            PromptField::SpawnAgent => Mode::SpawnAgentDialog,
+        }
+    }
+
+    /// Insert a library prompt into the picker's target dialog, interpolated
+    /// for the slot it is for, and return to that dialog. An empty prompt is
+    /// replaced; otherwise the text goes in at the cursor. In the spawn-agent
+    /// dialog the prompt's tools and max turns fill those fields.
+    pub fn insert_library_prompt(&mut self, prompt: &SavedPrompt) {
+        let target = self.prompt_picker.target;
+        let (slot_name, branch) = match target {
+            PromptField::CreateSlot => (
+                self.create_form.name.trim().to_string(),
+                self.create_form.branch.trim().to_string(),
+            ),
+            PromptField::SpawnAgent => self
+                .selected_slot()
+                .map(|s| (s.name.clone(), s.branch.clone()))
+                .unwrap_or_default(),
+        };
+        // Blueprints assume `main` for a slot without a branch too
+        let branch = if branch.is_empty() { "main" } else { &branch };
+        let text = blueprint::interpolate(&prompt.body, &slot_name, branch);
+
+        let area = self.prompt_mut(target);
+        if area.is_empty() {
+            area.set_text(&text);
+        } else {
+            area.insert_str(&text);
+        }
+        match target {
+            PromptField::CreateSlot => self.create_form.focus = CreateSlotField::Prompt,
+            PromptField::SpawnAgent => {
+                let form = &mut self.agent_form;
+                form.focus = SpawnAgentField::Prompt;
+                if let Some(tools) = &prompt.allowed_tools {
+                    form.allowed_tools = tools.clone();
+                }
+                if let Some(turns) = prompt.max_turns {
+                    form.max_turns = turns.to_string();
+                }
+            }
+        }
+        self.mode = Self::prompt_dialog_mode(target);
+    }
+
     /// Feed terminal output bytes to the slot's vt100 parser.
     pub fn feed_terminal_bytes(&mut self, slot_name: &str, bytes: &[u8]) {
         let parser = self
@@ -519,6 +1190,20 @@ impl App {
         parser.process(bytes);
     }
 
+    /// The first slot listed as a batch failure that is stuck on rebase conflicts.
+    pub fn first_conflicted_failure(&self) -> Option<String> {
+        let progress = self.batch_progress.as_ref()?;
+        progress
+            .failures
+            .iter()
+            .find(|(name, _)| {
+                self.slots
+                    .iter()
+                    .any(|s| &s.name == name && s.rebase_status == RebaseStatus::Conflicted)
+            })
+            .map(|(name, _)| name.clone())
+    }
+
     pub fn selected_slot(&self) -> Option<&Slot> {
         self.slots.get(self.selected_index)
     }
@@ -616,7 +1301,14 @@ impl App {
                 activity.attention_reason = Some("Agent blocked".to_string());
             } else if slot.status == SlotStatus::Error {
                 activity.needs_attention = true;
-                activity.attention_reason = Some("Aspire error".to_string());
+                activity.attention_reason = Some(
+                    slot.last_error
+                        .clone()
+                        .unwrap_or_else(|| "Aspire error".to_string()),
+                );
+            } else if let Some(span) = recent_error_span(slot) {
+                activity.needs_attention = true;
+                activity.attention_reason = Some(format!("Failed: {}", span.name));
             } else if slot.agent_status == AgentStatus::Active {
                 // Check idle time
                 if let Some(last_ts) = activity.log_timestamps.back() {
@@ -660,3 +1352,9 @@ impl App {
         }
     }
 }
+
+/// The latest failed span reported over OTLP in the last five minutes.
+fn recent_error_span(slot: &Slot) -> Option<&ErrorSpan> {
+    let span = slot.telemetry.as_ref()?.error_spans.last()?;
+    (Utc::now() - span.time < chrono::Duration::minutes(5)).then_some(span)
+}
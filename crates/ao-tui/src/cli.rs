@@ -0,0 +1,627 @@
+//! Headless subcommands (`ao-tui list`, `ao-tui create ...`) for scripts and
+//! CI. They go through the daemon when one is running for the slots
+//! directory, and drive an in-process `SlotManager` otherwise.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use ao_core::models::{CloneStrategy, RebaseOutcome, Slot, SlotStatus};
+use ao_core::services::blueprint::{self, BlueprintStore};
+use ao_core::services::daemon;
+use ao_core::services::log_store::LogStore;
+use ao_core::services::log_tailer::LogLine;
+use ao_core::services::orchestrator::Orchestrator;
+use ao_core::services::prompt_library::PromptLibrary;
+use ao_core::services::slot_manager::SlotManager;
+use ao_core::services::state::SlotStateStore;
+use ao_core::OrchestratorError;
+
+use crate::event::spawn_pull_request_task;
+
+/// The command succeeded.
+pub const EXIT_OK: i32 = 0;
+/// The operation failed.
+pub const EXIT_FAILURE: i32 = 1;
+/// Bad arguments.
+pub const EXIT_USAGE: i32 = 2;
+/// A rebase stopped on conflicts.
+pub const EXIT_CONFLICT: i32 = 3;
+
+pub const USAGE: &str = "\
+Usage: ao-tui [--debug] [--headless <script>]
+       ao-tui <command> [--json]
+
+Commands:
+  list                               List slots
+  create <name> <source> [--branch <b>] [--prompt <p>] [--strategy <s>]
+                                     Create a slot (strategy: clone, worktree, reference, shared)
+  start <name>                       Start Aspire (in the foreground until Ctrl-C
+                                     unless a daemon is running)
+  stop <name>                        Stop Aspire and mark the slot ready
+  spawn-agent <name> [--prompt <p>]  Spawn an agent in the slot
+  rebase <name>                      Rebase onto origin/<base branch> (exit 3 on conflicts)
+  push <name>                        Push the slot's branch
+  destroy <name>                     Destroy the slot and its working copy
+  blueprint load <name>              Create every slot in a blueprint
+  daemon                             Own the slots and serve the control API until
+                                     Ctrl-C; other commands and the TUI attach to it
+  help                               Show this help
+
+Exit codes: 0 ok, 1 failed, 2 usage error, 3 rebase conflicts";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    List,
+    Create {
+        name: String,
+        source: String,
+        branch: Option<String>,
+        prompt: Option<String>,
+        strategy: Option<CloneStrategy>,
+    },
+    Start {
+        name: String,
+    },
+    Stop {
+        name: String,
+    },
+    SpawnAgent {
+        name: String,
+        prompt: Option<String>,
+    },
+    Rebase {
+        name: String,
+    },
+    Push {
+        name: String,
+    },
+    Destroy {
+        name: String,
+    },
+    BlueprintLoad {
+        name: String,
+    },
+    Daemon,
+    Help,
+}
+
+impl Command {
+    /// The slot this command acts on, if it names one.
+    fn slot(&self) -> Option<&str> {
+        match self {
+            Command::Create { name, .. }
+            | Command::Start { name }
+            | Command::Stop { name }
+            | Command::SpawnAgent { name, .. }
+            | Command::Rebase { name }
+            | Command::Push { name }
+            | Command::Destroy { name } => Some(name),
+            Command::List | Command::BlueprintLoad { .. } | Command::Daemon | Command::Help => None,
+        }
+    }
+}
+
+/// A parsed subcommand invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    pub command: Command,
+    /// Print machine-readable JSON on stdout instead of text.
+    pub json: bool,
+}
+
+const SUBCOMMANDS: &[&str] = &[
+    "list",
+    "create",
+    "start",
+    "stop",
+    "spawn-agent",
+    "rebase",
+    "push",
+    "destroy",
+    "blueprint",
+    "daemon",
+    "help",
+];
+
+/// Parse the arguments after the program name. Returns `None` when they don't
+/// name a subcommand (so the TUI should run), or `Some(Err(message))` for a
+/// subcommand with bad arguments.
+pub fn parse(args: &[String]) -> Option<Result<Invocation, String>> {
+    let first = args.first()?;
+    if first == "--help" || first == "-h" {
+        return Some(Ok(Invocation {
+            command: Command::Help,
+            json: false,
+        }));
+    }
+    if !SUBCOMMANDS.contains(&first.as_str()) {
+        return None;
+    }
+    Some(parse_subcommand(args))
+}
+
+fn parse_subcommand(args: &[String]) -> Result<Invocation, String> {
+    let mut json = false;
+    let mut options: Vec<(String, String)> = Vec::new();
+    let mut positional: Vec<String> = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--branch" | "--prompt" | "--strategy" => {
+                let value = iter.next().ok_or_else(|| format!("{arg} needs a value"))?;
+                options.push((arg.clone(), value.clone()));
+            }
+            other if other.starts_with("--") => return Err(format!("unknown option {other}")),
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    let option = |flag: &str| {
+        options
+            .iter()
+            .find(|(f, _)| f == flag)
+            .map(|(_, v)| v.clone())
+    };
+    let allow_options =
+        |allowed: &[&str]| match options.iter().find(|(f, _)| !allowed.contains(&f.as_str())) {
+            Some((flag, _)) => Err(format!("{flag} is not valid for {}", positional[0])),
+            None => Ok(()),
+        };
+    let expect_args = |n: usize| {
+        if positional.len() == n + 1 {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} expects {n} argument(s), got {}",
+                positional[0],
+                positional.len() - 1
+            ))
+        }
+    };
+    let name = || positional[1].clone();
+
+    let command = match positional[0].as_str() {
+        "list" => {
+            expect_args(0)?;
+            allow_options(&[])?;
+            Command::List
+        }
+        "create" => {
+            expect_args(2)?;
+            allow_options(&["--branch", "--prompt", "--strategy"])?;
+            let strategy = option("--strategy")
+                .map(|s| {
+                    serde_json::from_value::<CloneStrategy>(serde_json::Value::String(s.clone()))
+                        .map_err(|_| format!("unknown clone strategy '{s}'"))
+                })
+                .transpose()?;
+            Command::Create {
+                name: name(),
+                source: positional[2].clone(),
+                branch: option("--branch"),
+                prompt: option("--prompt"),
+                strategy,
+            }
+        }
+        "spawn-agent" => {
+            expect_args(1)?;
+            allow_options(&["--prompt"])?;
+            Command::SpawnAgent {
+                name: name(),
+                prompt: option("--prompt"),
+            }
+        }
+        "blueprint" => {
+            if positional.get(1).map(String::as_str) != Some("load") || positional.len() != 3 {
+                return Err("usage: blueprint load <name>".into());
+            }
+            allow_options(&[])?;
+            Command::BlueprintLoad {
+                name: positional[2].clone(),
+            }
+        }
+        "daemon" => {
+            expect_args(0)?;
+            allow_options(&[])?;
+            Command::Daemon
+        }
+        "help" => Command::Help,
+        single => {
+            expect_args(1)?;
+            allow_options(&[])?;
+            let name = name();
+            match single {
+                "start" => Command::Start { name },
+                "stop" => Command::Stop { name },
+                "rebase" => Command::Rebase { name },
+                "push" => Command::Push { name },
+                "destroy" => Command::Destroy { name },
+                other => return Err(format!("unknown command {other}")),
+            }
+        }
+    };
+
+    Ok(Invocation { command, json })
+}
+
+/// Run a subcommand against the slots in `slots_directory` and return the
+/// process exit code.
+pub async fn run(invocation: Invocation, slots_directory: PathBuf) -> i32 {
+    let json = invocation.json;
+    match invocation.command {
+        Command::Help => {
+            out(USAGE);
+            return EXIT_OK;
+        }
+        Command::Daemon => return run_daemon(slots_directory, json).await,
+        _ => {}
+    }
+
+    // Setup, Aspire and pre-push output goes to stderr so stdout stays parseable.
+    // A daemon streams every slot's output, so keep only the one being acted on.
+    let (log_tx, mut log_rx) = mpsc::unbounded_channel::<LogLine>();
+    let show_logs = invocation.command != Command::List;
+    let log_slot = invocation.command.slot().map(str::to_string);
+    let printer = tokio::spawn(async move {
+        while let Some(line) = log_rx.recv().await {
+            if show_logs && log_slot.as_ref().is_none_or(|s| *s == line.slot_name) {
+                eprintln!("[{}] {}", line.slot_name, line.line);
+            }
+        }
+    });
+
+    let slot_manager = match Orchestrator::connect_or_load(slots_directory.clone(), log_tx).await {
+        Ok(sm) => Arc::new(sm),
+        // Listing only reads state.json, which the owning orchestrator keeps current
+        Err(OrchestratorError::WorkspaceLocked(_)) if invocation.command == Command::List => {
+            return match SlotStateStore::new(&slots_directory).read_snapshot().await {
+                Ok(slots) => list_slots(&slots, json),
+                Err(e) => report_error(json, &e.to_string(), None),
+            };
+        }
+        Err(e) => return report_error(json, &e.to_string(), None),
+    };
+    if let Some(note) = slot_manager
+        .local()
+        .and_then(|sm| sm.take_state_recovery_note())
+    {
+        eprintln!("warning: {note}");
+    }
+
+    let code = execute(invocation.command, &slot_manager, json).await;
+    // Background tailers keep log senders alive, so drain briefly rather than to the end
+    drop(slot_manager);
+    let _ = tokio::time::timeout(std::time::Duration::from_millis(200), printer).await;
+    code
+}
+
+/// Own the slots and serve the control API until Ctrl-C or SIGTERM. Aspire
+/// processes started through the daemon are stopped on the way out; agent
+/// hosts are separate processes and keep running.
+async fn run_daemon(slots_directory: PathBuf, json: bool) -> i32 {
+    let (log_tx, log_rx) = mpsc::unbounded_channel::<LogLine>();
+    let log_tx = LogStore::new(&slots_directory).record(log_tx);
+    let sm = Arc::new(SlotManager::new(slots_directory.clone(), log_tx));
+    if let Err(e) = sm.lock_workspace() {
+        return report_error(json, &e.to_string(), None);
+    }
+    let (listener, token) = match daemon::bind(&slots_directory).await {
+        Ok(bound) => bound,
+        Err(e) => return report_error(json, &e.to_string(), None),
+    };
+    let loaded = match sm.load_state().await {
+        Ok(()) => sm.reconnect_existing_sessions().await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = loaded {
+        daemon::remove_info(&slots_directory).await;
+        return report_error(json, &e.to_string(), None);
+    }
+    if let Some(note) = sm.take_state_recovery_note() {
+        eprintln!("warning: {note}");
+    }
+    let _pull_request_task = spawn_pull_request_task(Arc::new(Orchestrator::Local(sm.clone())));
+
+    let port = listener.local_addr().map(|a| a.port()).unwrap_or_default();
+    let pid = std::process::id();
+    if json {
+        print_json(&serde_json::json!({ "ok": true, "port": port, "pid": pid }));
+    } else {
+        out(format!(
+            "Daemon listening on 127.0.0.1:{port} (pid {pid}); Ctrl-C to stop"
+        ));
+    }
+
+    let code = tokio::select! {
+        result = daemon::serve(sm.clone(), log_rx, listener, token) => match result {
+            Ok(()) => EXIT_OK,
+            Err(e) => report_error(json, &e.to_string(), None),
+        },
+        _ = shutdown_signal() => EXIT_OK,
+    };
+
+    for slot in sm.get_slots().await {
+        if matches!(slot.status, SlotStatus::Running | SlotStatus::Starting) {
+            let _ = sm.stop_aspire(&slot.name).await;
+        }
+    }
+    daemon::remove_info(&slots_directory).await;
+    code
+}
+
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+async fn execute(command: Command, sm: &Arc<Orchestrator>, json: bool) -> i32 {
+    match command {
+        Command::List => list_slots(&sm.get_slots().await, json),
+        Command::Create {
+            name,
+            source,
+            branch,
+            prompt,
+            strategy,
+        } => {
+            let result = sm
+                .create_slot(
+                    &name,
+                    &source,
+                    branch.as_deref(),
+                    prompt.as_deref(),
+                    strategy,
+                )
+                .await;
+            match result {
+                Ok(slot) => report_slot(json, &slot, &format!("Created slot '{name}'")),
+                Err(e) => report_error(json, &e.to_string(), sm.get_slot(&name).await.as_ref()),
+            }
+        }
+        Command::Start { name } if sm.is_remote() => {
+            let result = sm.start_aspire(&name).await;
+            outcome(json, sm, &name, result, "Daemon started Aspire for").await
+        }
+        Command::Start { name } => {
+            if let Err(e) = sm.start_aspire(&name).await {
+                return report_error(json, &e.to_string(), None);
+            }
+            if let Some(slot) = sm.get_slot(&name).await {
+                report_slot(
+                    json,
+                    &slot,
+                    &format!("Started Aspire for '{name}' (Ctrl-C to stop)"),
+                );
+            }
+            let _ = tokio::signal::ctrl_c().await;
+            outcome(
+                json,
+                sm,
+                &name,
+                sm.stop_aspire(&name).await,
+                "Stopped Aspire",
+            )
+            .await
+        }
+        Command::Stop { name } => {
+            let result = sm.stop_aspire(&name).await;
+            outcome(json, sm, &name, result, "Stopped Aspire").await
+        }
+        Command::SpawnAgent { name, prompt } => {
+            let result = sm.spawn_agent(&name, prompt.as_deref(), None, None).await;
+            outcome(json, sm, &name, result, "Spawned agent").await
+        }
+        Command::Rebase { name } => match sm.rebase(&name).await {
+            Ok(RebaseOutcome::Completed) => outcome(json, sm, &name, Ok(()), "Rebased").await,
+            Ok(RebaseOutcome::Conflicted(files)) => {
+                if json {
+                    print_json(&serde_json::json!({
+                        "ok": false,
+                        "error": "rebase stopped on conflicts",
+                        "conflicts": files,
+                    }));
+                } else {
+                    eprintln!("Rebase of '{name}' stopped on conflicts:");
+                    for file in &files {
+                        eprintln!("  {file}");
+                    }
+                }
+                EXIT_CONFLICT
+            }
+            Err(e) => report_error(json, &e.to_string(), None),
+        },
+        Command::Push { name } => {
+            let result = sm.git_push(&name).await;
+            outcome(json, sm, &name, result, "Pushed").await
+        }
+        Command::Destroy { name } if sm.get_slot(&name).await.is_none() => report_error(
+            json,
+            &OrchestratorError::SlotNotFound(name).to_string(),
+            None,
+        ),
+        Command::Destroy { name } => match sm.destroy_slot(&name).await {
+            Ok(()) => {
+                if json {
+                    print_json(&serde_json::json!({ "ok": true }));
+                } else {
+                    out(format!("Destroyed slot '{name}'"));
+                }
+                EXIT_OK
+            }
+            Err(e) => report_error(json, &e.to_string(), None),
+        },
+        Command::BlueprintLoad { name } => load_blueprint(sm, &name, json).await,
+        Command::Daemon | Command::Help => EXIT_OK,
+    }
+}
+
+fn list_slots(slots: &[Slot], json: bool) -> i32 {
+    if json {
+        print_json(&serde_json::json!(slots));
+    } else {
+        print_slot_table(slots);
+    }
+    EXIT_OK
+}
+
+/// Create every slot in a blueprint, continuing past failures.
+async fn load_blueprint(sm: &Arc<Orchestrator>, name: &str, json: bool) -> i32 {
+    let slots_dir = sm.workspace_root().join(".slots");
+    let store = BlueprintStore::new(&slots_dir);
+    let resolved = async {
+        let bp = store.load(name).await?;
+        let prompts = PromptLibrary::new(&slots_dir).list().await?;
+        blueprint::resolve(&bp, &prompts)
+    };
+    let resolved = match resolved.await {
+        Ok(r) => r,
+        Err(e) => return report_error(json, &e.to_string(), None),
+    };
+
+    let mut created = Vec::new();
+    let mut failures = Vec::new();
+    for slot_config in &resolved {
+        let prompt = if slot_config.auto_spawn_agent {
+            slot_config.prompt.as_deref()
+        } else {
+            None
+        };
+        match sm
+            .create_slot(
+                &slot_config.name,
+                &slot_config.source,
+                slot_config.branch.as_deref(),
+                prompt,
+                slot_config.clone_strategy,
+            )
+            .await
+        {
+            Ok(slot) => {
+                if !json {
+                    out(format!("Created slot '{}'", slot.name));
+                    if slot_config.auto_start_aspire {
+                        out(format!(
+                            "  run `ao-tui start {}` to start Aspire",
+                            slot.name
+                        ));
+                    }
+                }
+                created.push(slot);
+            }
+            Err(e) => {
+                if !json {
+                    eprintln!("Failed to create '{}': {e}", slot_config.name);
+                }
+                failures.push(serde_json::json!({
+                    "slot": slot_config.name,
+                    "error": e.to_string(),
+                }));
+            }
+        }
+    }
+
+    if json {
+        print_json(&serde_json::json!({
+            "ok": failures.is_empty(),
+            "created": created,
+            "failures": failures,
+        }));
+    }
+    if failures.is_empty() {
+        EXIT_OK
+    } else {
+        EXIT_FAILURE
+    }
+}
+
+/// Report the result of a single-slot operation, with the updated slot.
+async fn outcome(
+    json: bool,
+    sm: &Orchestrator,
+    name: &str,
+    result: ao_core::Result<()>,
+    done: &str,
+) -> i32 {
+    match result {
+        Ok(()) => match sm.get_slot(name).await {
+            Some(slot) => report_slot(json, &slot, &format!("{done} '{name}'")),
+            None => report_error(
+                json,
+                &OrchestratorError::SlotNotFound(name.into()).to_string(),
+                None,
+            ),
+        },
+        Err(e) => report_error(json, &e.to_string(), None),
+    }
+}
+
+fn report_slot(json: bool, slot: &Slot, message: &str) -> i32 {
+    if json {
+        print_json(&serde_json::json!({ "ok": true, "slot": slot }));
+    } else {
+        out(message);
+    }
+    EXIT_OK
+}
+
+fn report_error(json: bool, message: &str, slot: Option<&Slot>) -> i32 {
+    let message = message.trim_end();
+    if json {
+        let mut value = serde_json::json!({ "ok": false, "error": message });
+        if let Some(slot) = slot {
+            value["slot"] = serde_json::json!(slot);
+        }
+        print_json(&value);
+    } else {
+        eprintln!("error: {message}");
+    }
+    EXIT_FAILURE
+}
+
+fn print_json(value: &serde_json::Value) {
+    match serde_json::to_string_pretty(value) {
+        Ok(s) => out(s),
+        Err(e) => eprintln!("error: cannot serialize output: {e}"),
+    }
+}
+
+/// Print a line to stdout, ignoring a closed pipe (e.g. `ao-tui list | head`).
+fn out(line: impl std::fmt::Display) {
+    use std::io::Write;
+    let _ = writeln!(std::io::stdout(), "{line}");
+}
+
+fn print_slot_table(slots: &[Slot]) {
+    if slots.is_empty() {
+        out("No slots");
+        return;
+    }
+    let width = slots.iter().map(|s| s.name.len()).max().unwrap_or(4).max(4);
+    out(format!(
+        "{:<width$}  {:<14}  {:<8}  BRANCH",
+        "NAME", "STATUS", "AGENT"
+    ));
+    for slot in slots {
+        out(format!(
+            "{:<width$}  {:<14}  {:<8}  {}",
+            slot.name,
+            slot.status.to_string(),
+            format!("{:?}", slot.agent_status),
+            slot.branch
+        ));
+    }
+}
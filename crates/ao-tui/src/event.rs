@@ -1,16 +1,21 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use crossterm::event::{self, Event, KeyEvent};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
-use ao_core::models::RepoCandidate;
+use ao_core::models::{FileDiff, GitStatus, RepoCandidate, SavedPrompt, SlotStatus};
+use ao_core::services::log_store::LogPage;
 use ao_core::services::log_tailer::LogLine;
+use ao_core::services::orchestrator::Orchestrator;
 
 /// Events flowing into the main loop.
 #[derive(Debug)]
 pub enum AppEvent {
     /// A key press from the user.
     Key(KeyEvent),
+    /// Text pasted into the terminal (bracketed paste).
+    Paste(String),
     /// Periodic tick for refreshing slot state.
     Tick,
     /// A new log line from a slot's log tailer or aspire process.
@@ -21,8 +26,15 @@ pub enum AppEvent {
     Info(String),
     /// Repo scan completed with discovered candidates.
     RepoCandidatesLoaded(Vec<RepoCandidate>),
+    /// Branches of the create dialog's source, for completion.
+    BranchesLoaded {
+        source: String,
+        branches: Vec<String>,
+    },
     /// Blueprint names loaded from disk.
     BlueprintNamesLoaded(Vec<String>),
+    /// Prompt library entries loaded from disk.
+    PromptsLoaded(Vec<SavedPrompt>),
     /// Progress update from a batch operation.
     BatchProgress {
         completed: usize,
@@ -35,12 +47,72 @@ pub enum AppEvent {
     AgentSpawned { slot_name: String },
     /// Terminal output bytes from an agent host connection.
     TerminalOutput { slot_name: String, bytes: Vec<u8> },
+    /// A rebase stopped on conflicts and needs a decision.
+    RebaseConflicted { slot_name: String },
+    /// A page of log history search results.
+    LogHistoryLoaded {
+        generation: u64,
+        result: Result<LogPage, String>,
+    },
+    /// Diff for the diff viewer finished loading.
+    DiffLoaded {
+        slot_name: String,
+        result: Result<Vec<FileDiff>, String>,
+    },
+    /// Refreshed git status for a slot's clone.
+    GitStatusLoaded {
+        slot_name: String,
+        status: GitStatus,
+    },
+}
+
+/// Asks the input task to stop reading: it sends on `stopped` once no poll
+/// is in flight, then waits until `resume` fires or is dropped.
+struct PauseRequest {
+    stopped: oneshot::Sender<()>,
+    resume: oneshot::Receiver<()>,
+}
+
+/// Control over the input task, for handing the terminal's input to another
+/// program such as `$EDITOR`.
+pub struct InputControl {
+    pause_tx: mpsc::UnboundedSender<PauseRequest>,
+}
+
+impl InputControl {
+    /// Stop reading terminal input until the returned guard is dropped.
+    /// Returns once the input task has stopped polling, so nothing it reads
+    /// afterwards can be taken from the other program.
+    pub async fn pause(&self) -> InputPaused {
+        let (stopped_tx, stopped_rx) = oneshot::channel();
+        let (resume_tx, resume_rx) = oneshot::channel();
+        let _ = self.pause_tx.send(PauseRequest {
+            stopped: stopped_tx,
+            resume: resume_rx,
+        });
+        // An error means the task has exited, so nothing is polling either
+        let _ = stopped_rx.await;
+        InputPaused { _resume: resume_tx }
+    }
+}
+
+/// Input stays paused while this is alive.
+pub struct InputPaused {
+    _resume: oneshot::Sender<()>,
 }
 
 /// Spawn the crossterm input polling task.
-pub fn spawn_input_task(tx: mpsc::UnboundedSender<AppEvent>) -> tokio::task::JoinHandle<()> {
+pub fn spawn_input_task(tx: mpsc::UnboundedSender<AppEvent>) -> InputControl {
+    let (pause_tx, mut pause_rx) = mpsc::unbounded_channel::<PauseRequest>();
     tokio::spawn(async move {
         loop {
+            // Only checked between polls, so acknowledging means none is running
+            if let Ok(request) = pause_rx.try_recv() {
+                let _ = request.stopped.send(());
+                let _ = request.resume.await;
+                continue;
+            }
+
             // Poll crossterm events with 50ms timeout (non-blocking feel)
             let has_event = tokio::task::spawn_blocking(|| {
                 event::poll(Duration::from_millis(50)).unwrap_or(false)
@@ -49,17 +121,21 @@ pub fn spawn_input_task(tx: mpsc::UnboundedSender<AppEvent>) -> tokio::task::Joi
             .unwrap_or(false);
 
             if has_event {
-                if let Ok(Event::Key(key)) = tokio::task::spawn_blocking(event::read)
+                let event = match tokio::task::spawn_blocking(event::read)
                     .await
                     .unwrap_or(Err(std::io::Error::other("spawn_blocking failed")))
                 {
-                    if tx.send(AppEvent::Key(key)).is_err() {
-                        break;
-                    }
+                    Ok(Event::Key(key)) => AppEvent::Key(key),
+                    Ok(Event::Paste(text)) => AppEvent::Paste(text),
+                    _ => continue,
+                };
+                if tx.send(event).is_err() {
+                    break;
                 }
             }
         }
-    })
+    });
+    InputControl { pause_tx }
 }
 
 /// Spawn the periodic tick task.
@@ -74,3 +150,52 @@ pub fn spawn_tick_task(tx: mpsc::UnboundedSender<AppEvent>) -> tokio::task::Join
         }
     })
 }
+
+/// Spawn the periodic git status refresh task.
+pub fn spawn_git_status_task(
+    slot_manager: Arc<Orchestrator>,
+    tx: mpsc::UnboundedSender<AppEvent>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(10));
+        loop {
+            interval.tick().await;
+            for slot in slot_manager.get_slots().await {
+                if slot.status == SlotStatus::Provisioning || slot.creation.is_some() {
+                    continue;
+                }
+                match slot_manager.git_status(&slot.name).await {
+                    Ok(status) => {
+                        let event = AppEvent::GitStatusLoaded {
+                            slot_name: slot.name,
+                            status,
+                        };
+                        if tx.send(event).is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => tracing::debug!(slot = %slot.name, error = %e, "git_status_failed"),
+                }
+            }
+        }
+    })
+}
+
+/// Spawn the periodic pull request status poll. Results are stored on the slot,
+/// so they reach the UI with the next tick's slot refresh.
+pub fn spawn_pull_request_task(slot_manager: Arc<Orchestrator>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            for slot in slot_manager.get_slots().await {
+                if !slot.pull_request.is_some_and(|pr| pr.state.is_open()) {
+                    continue;
+                }
+                if let Err(e) = slot_manager.refresh_pull_request(&slot.name).await {
+                    tracing::debug!(slot = %slot.name, error = %e, "pull_request_refresh_failed");
+                }
+            }
+        }
+    })
+}
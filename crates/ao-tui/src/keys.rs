@@ -1,25 +1,32 @@
 use std::path::Path;
 use std::sync::Arc;
 
+use chrono::Utc;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use tokio::sync::mpsc;
 
 use crate::app::{
     App, BatchProgressState, BlueprintSaveField, BlueprintSaveState, ConfirmAction,
-    CreateSlotField, LogSource, Mode, SpawnAgentField, ViewMode,
+    CreateSlotField, DiffViewState, HistoryRange, LogHistoryState, LogSource, LogViewLine, Mode,
+    PromptField, PromptPickerState, SpawnAgentField, ViewMode,
 };
 use crate::event::AppEvent;
 
-use ao_core::models::{AgentStatus, SlotStatus};
+use ao_core::models::{
+    AgentStatus, DiffTarget, RebaseOutcome, RebaseStatus, ReconcileAction, SlotStatus,
+};
 use ao_core::services::agent_host;
 use ao_core::services::blueprint::BlueprintStore;
-use ao_core::services::slot_manager::SlotManager;
+use ao_core::services::git;
+use ao_core::services::log_store::LogStore;
+use ao_core::services::orchestrator::Orchestrator;
+use ao_core::services::prompt_library::PromptLibrary;
 
 /// Handle a key event, dispatching based on current mode.
 pub async fn handle_key(
     app: &mut App,
     key: KeyEvent,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     // On Windows, crossterm sends both Press and Release events for every key.
@@ -35,7 +42,8 @@ pub async fn handle_key(
             ViewMode::Dashboard => handle_dashboard(app, key, slot_manager, event_tx).await,
         },
         Mode::Terminal => handle_terminal(app, key, slot_manager).await,
-        Mode::MultiplexLog => handle_multiplex_log(app, key),
+        Mode::MultiplexLog => handle_multiplex_log(app, key, slot_manager, event_tx),
+        Mode::DiffView => handle_diff_view(app, key, slot_manager, event_tx),
         Mode::CreateSlotDialog => handle_create_dialog(app, key, slot_manager, event_tx),
         Mode::SpawnAgentDialog => handle_agent_dialog(app, key, slot_manager, event_tx),
         Mode::ConfirmDialog { .. } => {
@@ -53,6 +61,16 @@ pub async fn handle_key(
         Mode::BatchProgress => {
             handle_batch_progress(app, key);
         }
+        Mode::RebaseConflictDialog { .. } => {
+            handle_rebase_conflict_dialog(app, key, slot_manager, event_tx);
+        }
+        Mode::ProblemsDialog { .. } => {
+            handle_problems_dialog(app, key, slot_manager, event_tx);
+        }
+        Mode::ReconcileDialog => {
+            handle_reconcile_dialog(app, key, slot_manager, event_tx);
+        }
+        Mode::PromptPickerDialog => handle_prompt_picker_dialog(app, key),
         Mode::Loading(_) => {}
     }
 }
@@ -62,7 +80,7 @@ pub async fn handle_key(
 async fn handle_slot_list(
     app: &mut App,
     key: KeyEvent,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
@@ -199,26 +217,36 @@ async fn handle_slot_list(
                 };
             }
         }
-        KeyCode::Char('a') => {
-            if app.selected_slot().is_some() {
-                app.agent_form = Default::default();
-                app.mode = Mode::SpawnAgentDialog;
-            }
+        KeyCode::Char('a') if app.selected_slot().is_some() => {
+            app.agent_form = Default::default();
+            app.mode = Mode::SpawnAgentDialog;
         }
         KeyCode::Char('r') => {
             if let Some(slot) = app.selected_slot() {
                 let name = slot.name.clone();
+                if slot.rebase_status == RebaseStatus::Conflicted {
+                    app.mode = Mode::RebaseConflictDialog { slot_name: name };
+                    return;
+                }
                 let tx = event_tx.clone();
                 let sm = Arc::clone(slot_manager);
-                tokio::spawn(async move {
-                    match sm.rebase(&name).await {
-                        Ok(()) => {
-                            let _ = tx.send(AppEvent::Info(format!("Rebased {name}")));
-                        }
-                        Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("Rebase failed: {e}")));
+                if slot.creation_failed() {
+                    app.set_status(format!("Retrying creation of {name}..."));
+                    tokio::spawn(async move {
+                        match sm.retry_creation(&name).await {
+                            Ok(_) => {
+                                let _ = tx.send(AppEvent::Info(format!("Created slot '{name}'")));
+                            }
+                            Err(e) => {
+                                let _ = tx.send(AppEvent::Error(format!("Retry failed: {e}")));
+                            }
                         }
-                    }
+                    });
+                    return;
+                }
+                tokio::spawn(async move {
+                    let result = sm.rebase(&name).await;
+                    send_rebase_result(&tx, &name, result);
                 });
             }
         }
@@ -239,6 +267,52 @@ async fn handle_slot_list(
                 });
             }
         }
+        KeyCode::Char('o') => {
+            if let Some(slot) = app.selected_slot() {
+                let name = slot.name.clone();
+                let has_open_pr = slot
+                    .pull_request
+                    .as_ref()
+                    .is_some_and(|pr| pr.state.is_open());
+                let tx = event_tx.clone();
+                let sm = Arc::clone(slot_manager);
+                if has_open_pr {
+                    tokio::spawn(async move {
+                        match sm.refresh_pull_request(&name).await {
+                            Ok(Some(pr)) => {
+                                let _ = tx.send(AppEvent::Info(format!(
+                                    "{name}: PR #{} {}",
+                                    pr.number, pr.url
+                                )));
+                            }
+                            Ok(None) => {}
+                            Err(e) => {
+                                let _ = tx.send(AppEvent::Error(format!("PR refresh failed: {e}")));
+                            }
+                        }
+                    });
+                } else {
+                    app.set_status(format!("Opening pull request for {name}..."));
+                    tokio::spawn(async move {
+                        let result = match sm.git_push(&name).await {
+                            Ok(()) => sm.create_pull_request(&name).await,
+                            Err(e) => Err(e),
+                        };
+                        match result {
+                            Ok(pr) => {
+                                let _ = tx.send(AppEvent::Info(format!(
+                                    "Opened PR #{} for {name}: {}",
+                                    pr.number, pr.url
+                                )));
+                            }
+                            Err(e) => {
+                                let _ = tx.send(AppEvent::Error(format!("PR failed: {e}")));
+                            }
+                        }
+                    });
+                }
+            }
+        }
         KeyCode::Char('p') | KeyCode::Enter => {
             if let Some(slot) = app.selected_slot() {
                 if slot.agent_status == AgentStatus::Active
@@ -252,6 +326,30 @@ async fn handle_slot_list(
             app.toggle_log_source();
             reload_log_for_selected(app);
         }
+        KeyCode::Char('T') => {
+            app.log_time = app.log_time.next();
+        }
+        KeyCode::Char('v') => {
+            if let Some(slot) = app.selected_slot() {
+                let name = slot.name.clone();
+                app.diff_view = Some(DiffViewState::new(&name, DiffTarget::Base));
+                app.mode = Mode::DiffView;
+                load_diff(&name, DiffTarget::Base, slot_manager, event_tx);
+            }
+        }
+        KeyCode::Char('e') => {
+            if let Some(slot) = app.selected_slot() {
+                let name = slot.name.clone();
+                if slot.problems.is_empty() {
+                    app.set_status(format!("No build problems in {name}"));
+                } else {
+                    app.mode = Mode::ProblemsDialog {
+                        slot_name: name,
+                        scroll: 0,
+                    };
+                }
+            }
+        }
         KeyCode::Char('?') => {
             app.mode = Mode::HelpDialog;
         }
@@ -264,7 +362,7 @@ async fn handle_slot_list(
 async fn handle_dashboard(
     app: &mut App,
     key: KeyEvent,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
@@ -388,7 +486,7 @@ async fn handle_dashboard(
 
 // ─── Terminal Mode ──────────────────────────────────────────────────────
 
-async fn handle_terminal(app: &mut App, key: KeyEvent, slot_manager: &Arc<SlotManager>) {
+async fn handle_terminal(app: &mut App, key: KeyEvent, slot_manager: &Arc<Orchestrator>) {
     // Esc exits terminal mode
     if key.code == KeyCode::Esc {
         app.mode = Mode::SlotList;
@@ -477,7 +575,17 @@ fn key_to_bytes(key: KeyEvent) -> Vec<u8> {
 
 // ─── Multiplex Log Mode ────────────────────────────────────────────────
 
-fn handle_multiplex_log(app: &mut App, key: KeyEvent) {
+fn handle_multiplex_log(
+    app: &mut App,
+    key: KeyEvent,
+    slot_manager: &Arc<Orchestrator>,
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+) {
+    handle_multiplex_log_key(app, key);
+    refresh_log_history(app, slot_manager, event_tx);
+}
+
+fn handle_multiplex_log_key(app: &mut App, key: KeyEvent) {
     // Handle search input mode
     if app.multiplex_filter.search_input_active {
         match key.code {
@@ -501,10 +609,20 @@ fn handle_multiplex_log(app: &mut App, key: KeyEvent) {
         return;
     }
 
+    if app.log_history.is_some() && handle_log_history_key(app, key) {
+        return;
+    }
+
     match key.code {
         KeyCode::Char('m') | KeyCode::Esc => {
             app.mode = Mode::SlotList;
         }
+        KeyCode::Char('h') => {
+            app.log_history = Some(LogHistoryState::new(HistoryRange::LastDay));
+        }
+        KeyCode::Char('T') => {
+            app.log_time = app.log_time.next();
+        }
         KeyCode::Char('1')
         | KeyCode::Char('2')
         | KeyCode::Char('3')
@@ -527,6 +645,9 @@ fn handle_multiplex_log(app: &mut App, key: KeyEvent) {
         KeyCode::Tab => {
             app.multiplex_filter.cycle_source();
         }
+        KeyCode::Char('v') => {
+            app.multiplex_filter.cycle_severity();
+        }
         KeyCode::Char('/') => {
             app.multiplex_filter.search_input_active = true;
         }
@@ -578,32 +699,159 @@ fn handle_multiplex_log(app: &mut App, key: KeyEvent) {
     }
 }
 
+/// Keys that act differently while history is shown. Returns whether the
+/// key was handled; filter keys fall through to the live view's handling.
+fn handle_log_history_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(history) = app.log_history.as_mut() else {
+        return false;
+    };
+    match key.code {
+        KeyCode::Char('h') | KeyCode::Esc => app.log_history = None,
+        KeyCode::Char('t') => {
+            history.range = history.range.next();
+            history.stale = true;
+        }
+        KeyCode::Char('f') => history.scroll_back = 0,
+        KeyCode::Up => history.scroll_back = (history.scroll_back + 1).min(history.records.len()),
+        KeyCode::PageUp => {
+            history.scroll_back = (history.scroll_back + 20).min(history.records.len())
+        }
+        KeyCode::Down => history.scroll_back = history.scroll_back.saturating_sub(1),
+        KeyCode::PageDown => history.scroll_back = history.scroll_back.saturating_sub(20),
+        // Error jumps and slot jumps only apply to the live buffer
+        KeyCode::Char('e') | KeyCode::Char('w') | KeyCode::Enter => {}
+        _ => return false,
+    }
+    true
+}
+
+/// Start a new history search when the filters changed, or fetch older
+/// results when scrolling nears the oldest one loaded.
+fn refresh_log_history(
+    app: &mut App,
+    slot_manager: &Arc<Orchestrator>,
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+) {
+    let Some(history) = app.log_history.as_ref() else {
+        return;
+    };
+    let mut query = app.history_query(history.range, Utc::now());
+    // Keep the window's start while only the other filters change
+    if !history.stale {
+        query.since = history.query.since;
+    }
+
+    let Some(history) = app.log_history.as_mut() else {
+        return;
+    };
+    let before = if history.stale || query != history.query {
+        history.restart(query);
+        None
+    } else if history.wants_older() {
+        history.loading = true;
+        history.next
+    } else {
+        return;
+    };
+
+    let generation = history.generation;
+    let query = history.query.clone();
+    let store = LogStore::new(&slot_manager.workspace_root().join(".slots"));
+    let tx = event_tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let result = store
+            .search(&query, before, LogHistoryState::PAGE)
+            .map_err(|e| e.to_string());
+        let _ = tx.send(AppEvent::LogHistoryLoaded { generation, result });
+    });
+}
+
+// ─── Diff Viewer ────────────────────────────────────────────────────────
+
+fn handle_diff_view(
+    app: &mut App,
+    key: KeyEvent,
+    slot_manager: &Arc<Orchestrator>,
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+) {
+    let Some(ref mut view) = app.diff_view else {
+        app.mode = Mode::SlotList;
+        return;
+    };
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => {
+            app.diff_view = None;
+            app.mode = Mode::SlotList;
+        }
+        KeyCode::Up | KeyCode::Char('k') => view.scroll_by(-1),
+        KeyCode::Down | KeyCode::Char('j') => view.scroll_by(1),
+        KeyCode::PageUp => view.scroll_by(-20),
+        KeyCode::PageDown | KeyCode::Char(' ') => view.scroll_by(20),
+        KeyCode::Home | KeyCode::Char('g') => view.scroll = 0,
+        KeyCode::End | KeyCode::Char('G') => view.scroll_by(isize::MAX),
+        KeyCode::Tab | KeyCode::Char('n') | KeyCode::Char(']') => view.next_file(),
+        KeyCode::BackTab | KeyCode::Char('N') | KeyCode::Char('[') => view.prev_file(),
+        KeyCode::Char('w') => {
+            view.target = match view.target {
+                DiffTarget::Base => DiffTarget::WorkingTree,
+                DiffTarget::WorkingTree => DiffTarget::Base,
+            };
+            view.loading = true;
+            load_diff(&view.slot_name, view.target, slot_manager, event_tx);
+        }
+        KeyCode::Char('r') => {
+            view.loading = true;
+            load_diff(&view.slot_name, view.target, slot_manager, event_tx);
+        }
+        _ => {}
+    }
+}
+
+fn load_diff(
+    name: &str,
+    target: DiffTarget,
+    slot_manager: &Arc<Orchestrator>,
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+) {
+    let name = name.to_string();
+    let tx = event_tx.clone();
+    let sm = Arc::clone(slot_manager);
+    tokio::spawn(async move {
+        let result = sm.diff(&name, target).await.map_err(|e| e.to_string());
+        let _ = tx.send(AppEvent::DiffLoaded {
+            slot_name: name,
+            result,
+        });
+    });
+}
+
 fn jump_to_error(app: &mut App, forward: bool) {
     use crate::app::Severity;
 
-    let filtered: Vec<usize> = app
+    // Positions in the filtered view, which is what the scroll counts, of
+    // the first line of each error or warning
+    let problems: Vec<usize> = app
         .log_buffer
         .entries
         .iter()
+        .filter(|e| app.multiplex_filter.matches_entry(e))
         .enumerate()
-        .filter(|(_, e)| {
-            app.multiplex_filter.matches_entry(e)
-                && matches!(e.severity, Severity::Error | Severity::Warn)
-        })
+        .filter(|(_, e)| !e.continuation && e.severity.is_at_least(Severity::Warn))
         .map(|(i, _)| i)
         .collect();
 
-    if filtered.is_empty() {
+    if problems.is_empty() {
         return;
     }
 
     let current_scroll = app.multiplex_scroll;
     if forward {
-        if let Some(&idx) = filtered.iter().find(|&&i| i > current_scroll) {
+        if let Some(&idx) = problems.iter().find(|&&i| i > current_scroll) {
             app.multiplex_scroll = idx;
             app.multiplex_auto_follow = false;
         }
-    } else if let Some(&idx) = filtered.iter().rev().find(|&&i| i < current_scroll) {
+    } else if let Some(&idx) = problems.iter().rev().find(|&&i| i < current_scroll) {
         app.multiplex_scroll = idx;
         app.multiplex_auto_follow = false;
     }
@@ -627,22 +875,18 @@ fn get_entry_at_scroll(app: &App) -> Option<String> {
 async fn handle_blueprint_list_dialog(
     app: &mut App,
     key: KeyEvent,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     _event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     match key.code {
         KeyCode::Esc => {
             app.mode = Mode::SlotList;
         }
-        KeyCode::Up => {
-            if app.blueprint_list.selected > 0 {
-                app.blueprint_list.selected -= 1;
-            }
+        KeyCode::Up if app.blueprint_list.selected > 0 => {
+            app.blueprint_list.selected -= 1;
         }
-        KeyCode::Down => {
-            if app.blueprint_list.selected + 1 < app.blueprint_list.names.len() {
-                app.blueprint_list.selected += 1;
-            }
+        KeyCode::Down if app.blueprint_list.selected + 1 < app.blueprint_list.names.len() => {
+            app.blueprint_list.selected += 1;
         }
         KeyCode::Char('s') => {
             app.blueprint_save = BlueprintSaveState::new();
@@ -694,7 +938,7 @@ async fn handle_blueprint_list_dialog(
 fn handle_blueprint_save_dialog(
     app: &mut App,
     key: KeyEvent,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     match key.code {
@@ -756,7 +1000,20 @@ fn handle_batch_progress(app: &mut App, key: KeyEvent) {
     if let Some(ref progress) = app.batch_progress {
         if progress.done {
             match key.code {
-                KeyCode::Esc | KeyCode::Enter => {
+                KeyCode::Enter => {
+                    let conflicted = app.first_conflicted_failure();
+                    app.batch_progress = None;
+                    app.mode = Mode::SlotList;
+                    // Jump straight to the first slot that needs conflict resolution
+                    if let Some(name) = conflicted {
+                        if let Some(idx) = app.slots.iter().position(|s| s.name == name) {
+                            app.selected_index = idx;
+                            reload_log_for_selected(app);
+                        }
+                        app.mode = Mode::RebaseConflictDialog { slot_name: name };
+                    }
+                }
+                KeyCode::Esc => {
                     app.batch_progress = None;
                     app.mode = Mode::SlotList;
                 }
@@ -766,12 +1023,214 @@ fn handle_batch_progress(app: &mut App, key: KeyEvent) {
     }
 }
 
+// ─── Rebase Conflicts ───────────────────────────────────────────────────
+
+fn handle_rebase_conflict_dialog(
+    app: &mut App,
+    key: KeyEvent,
+    slot_manager: &Arc<Orchestrator>,
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+) {
+    let Mode::RebaseConflictDialog { slot_name } = &app.mode else {
+        return;
+    };
+    let name = slot_name.clone();
+    let tx = event_tx.clone();
+    let sm = Arc::clone(slot_manager);
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.mode = Mode::SlotList;
+        }
+        KeyCode::Char('c') => {
+            app.mode = Mode::Loading(format!("Continuing rebase of {name}..."));
+            tokio::spawn(async move {
+                let result = sm.rebase_continue(&name).await;
+                send_rebase_result(&tx, &name, result);
+            });
+        }
+        KeyCode::Char('s') => {
+            app.mode = Mode::Loading(format!("Skipping commit in {name}..."));
+            tokio::spawn(async move {
+                let result = sm.rebase_skip(&name).await;
+                send_rebase_result(&tx, &name, result);
+            });
+        }
+        KeyCode::Char('x') => {
+            app.mode = Mode::Loading(format!("Aborting rebase of {name}..."));
+            tokio::spawn(async move {
+                match sm.rebase_abort(&name).await {
+                    Ok(()) => {
+                        let _ = tx.send(AppEvent::Info(format!("Rebase of {name} aborted")));
+                    }
+                    Err(e) => {
+                        let _ = tx.send(AppEvent::Error(format!("Abort failed: {e}")));
+                    }
+                }
+            });
+        }
+        KeyCode::Char('a') => {
+            app.mode = Mode::Loading(format!("Sending conflicts to {name}'s agent..."));
+            tokio::spawn(async move {
+                match sm.send_conflict_to_agent(&name).await {
+                    Ok(spawned) => {
+                        if spawned {
+                            let _ = tx.send(AppEvent::AgentSpawned {
+                                slot_name: name.clone(),
+                            });
+                        }
+                        let _ = tx.send(AppEvent::Info(format!(
+                            "Asked agent to resolve conflicts in {name}"
+                        )));
+                    }
+                    Err(e) => {
+                        let _ = tx.send(AppEvent::Error(format!("Send to agent failed: {e}")));
+                    }
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+// ─── Build Problems ─────────────────────────────────────────────────────
+
+fn handle_problems_dialog(
+    app: &mut App,
+    key: KeyEvent,
+    slot_manager: &Arc<Orchestrator>,
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+) {
+    let count = match &app.mode {
+        Mode::ProblemsDialog { slot_name, .. } => app
+            .slots
+            .iter()
+            .find(|s| &s.name == slot_name)
+            .map_or(0, |s| s.problems.len()),
+        _ => return,
+    };
+    let Mode::ProblemsDialog { slot_name, scroll } = &mut app.mode else {
+        return;
+    };
+    let name = slot_name.clone();
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.mode = Mode::SlotList;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            *scroll = scroll.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') if *scroll + 1 < count => {
+            *scroll += 1;
+        }
+        KeyCode::Char('a') => {
+            app.mode = Mode::Loading(format!("Sending build problems to {name}'s agent..."));
+            let tx = event_tx.clone();
+            let sm = Arc::clone(slot_manager);
+            tokio::spawn(async move {
+                match sm.send_problems_to_agent(&name).await {
+                    Ok(spawned) => {
+                        if spawned {
+                            let _ = tx.send(AppEvent::AgentSpawned {
+                                slot_name: name.clone(),
+                            });
+                        }
+                        let _ = tx.send(AppEvent::Info(format!(
+                            "Asked agent to fix the build problems in {name}"
+                        )));
+                    }
+                    Err(e) => {
+                        let _ = tx.send(AppEvent::Error(format!("Send to agent failed: {e}")));
+                    }
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+// ─── Reconcile Dialog ───────────────────────────────────────────────────
+
+fn handle_reconcile_dialog(
+    app: &mut App,
+    key: KeyEvent,
+    slot_manager: &Arc<Orchestrator>,
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+) {
+    let action = match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.mode = Mode::SlotList;
+            return;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.reconcile.selected = app.reconcile.selected.saturating_sub(1);
+            return;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            if app.reconcile.selected + 1 < app.reconcile.items.len() {
+                app.reconcile.selected += 1;
+            }
+            return;
+        }
+        KeyCode::Char('a') => ReconcileAction::Adopt,
+        KeyCode::Char('d') => ReconcileAction::Remove,
+        KeyCode::Char('f') => ReconcileAction::Fix,
+        _ => return,
+    };
+
+    let Some(label) = app
+        .reconcile
+        .items
+        .get(app.reconcile.selected)
+        .and_then(|d| d.action_label(action))
+    else {
+        return;
+    };
+    let Some(discrepancy) = app.reconcile.take_selected() else {
+        return;
+    };
+    if app.reconcile.items.is_empty() {
+        app.mode = Mode::SlotList;
+    }
+
+    let name = discrepancy.name().to_string();
+    let tx = event_tx.clone();
+    let sm = Arc::clone(slot_manager);
+    tokio::spawn(async move {
+        match sm.resolve_discrepancy(&discrepancy, action).await {
+            Ok(()) => {
+                let _ = tx.send(AppEvent::Info(format!("{name}: {label}")));
+            }
+            Err(e) => {
+                let _ = tx.send(AppEvent::Error(format!("{name}: {label} failed: {e}")));
+            }
+        }
+    });
+}
+
+/// Report the result of a rebase step: done, stopped on conflicts, or failed.
+fn send_rebase_result(
+    tx: &mpsc::UnboundedSender<AppEvent>,
+    name: &str,
+    result: ao_core::Result<RebaseOutcome>,
+) {
+    let event = match result {
+        Ok(RebaseOutcome::Completed) => AppEvent::Info(format!("Rebased {name}")),
+        Ok(RebaseOutcome::Conflicted(_)) => AppEvent::RebaseConflicted {
+            slot_name: name.to_string(),
+        },
+        Err(e) => AppEvent::Error(format!("Rebase failed: {e}")),
+    };
+    let _ = tx.send(event);
+}
+
 // ─── Confirm Dialog ────────────────────────────────────────────────────
 
 fn handle_confirm_dialog(
     app: &mut App,
     key: KeyEvent,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     match key.code {
@@ -811,13 +1270,11 @@ fn handle_confirm_dialog(
                     tokio::spawn(async move {
                         match sm.stop_aspire(&name).await {
                             Ok(()) => {
-                                let _ = tx.send(AppEvent::Info(format!(
-                                    "Aspire stopped for {name}"
-                                )));
+                                let _ =
+                                    tx.send(AppEvent::Info(format!("Aspire stopped for {name}")));
                             }
                             Err(e) => {
-                                let _ =
-                                    tx.send(AppEvent::Error(format!("Stop failed: {e}")));
+                                let _ = tx.send(AppEvent::Error(format!("Stop failed: {e}")));
                             }
                         }
                     });
@@ -839,72 +1296,95 @@ fn handle_confirm_dialog(
 fn handle_create_dialog(
     app: &mut App,
     key: KeyEvent,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
+    if is_ctrl_p(key) {
+        open_prompt_picker(app, PromptField::CreateSlot, slot_manager, event_tx);
+        return;
+    }
+    let focus = app.create_form.focus;
+    if focus == CreateSlotField::Prompt && edit_prompt(app, PromptField::CreateSlot, key) {
+        return;
+    }
     match key.code {
         KeyCode::Esc => {
             app.mode = Mode::SlotList;
         }
-        KeyCode::Tab | KeyCode::BackTab => {
-            app.create_form.focus = match app.create_form.focus {
-                CreateSlotField::Source => {
-                    app.create_form.filtered_candidates.clear();
-                    app.create_form.selected_candidate = None;
-                    CreateSlotField::Prompt
-                }
-                CreateSlotField::Prompt => {
-                    app.create_form.apply_filter();
-                    CreateSlotField::Source
-                }
-            };
-        }
-        KeyCode::Up => {
-            if app.create_form.focus == CreateSlotField::Source
-                && !app.create_form.filtered_candidates.is_empty()
-            {
-                app.create_form.select_prev_candidate();
+        KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.create_form.new_branch = !app.create_form.new_branch;
+            if focus == CreateSlotField::Branch {
+                app.create_form.filter_branches();
             }
         }
-        KeyCode::Down => {
-            if app.create_form.focus == CreateSlotField::Source
-                && !app.create_form.filtered_candidates.is_empty()
-            {
-                app.create_form.select_next_candidate();
-            }
+        KeyCode::Tab => focus_create_field(app, focus.next(), event_tx),
+        KeyCode::BackTab => focus_create_field(app, focus.prev(), event_tx),
+        KeyCode::Up
+            if focus == CreateSlotField::Source
+                && !app.create_form.filtered_candidates.is_empty() =>
+        {
+            app.create_form.select_prev_candidate();
+        }
+        KeyCode::Down
+            if focus == CreateSlotField::Source
+                && !app.create_form.filtered_candidates.is_empty() =>
+        {
+            app.create_form.select_next_candidate();
+        }
+        KeyCode::Up
+            if focus == CreateSlotField::Branch
+                && !app.create_form.filtered_branches.is_empty() =>
+        {
+            app.create_form.select_prev_branch();
+        }
+        KeyCode::Down
+            if focus == CreateSlotField::Branch
+                && !app.create_form.filtered_branches.is_empty() =>
+        {
+            app.create_form.select_next_branch();
         }
         KeyCode::Enter => {
-            if app.create_form.focus == CreateSlotField::Source
-                && app.create_form.selected_candidate.is_some()
-            {
+            if focus == CreateSlotField::Source && app.create_form.selected_candidate.is_some() {
                 app.create_form.accept_selected();
+                app.create_form.suggest_name(&app.slots);
+                load_source_branches(app, event_tx);
+                return;
+            }
+            if focus == CreateSlotField::Branch && app.create_form.accept_selected_branch() {
+                revalidate_create_form(app);
                 return;
             }
 
-            let source = app.create_form.source.trim().to_string();
-            if source.is_empty() {
-                app.set_status("Source path is required");
+            app.create_form.errors = app.create_form.validate(&app.slots);
+            if let Some((field, _)) = app.create_form.errors.first() {
+                let field = *field;
+                focus_create_field(app, field, event_tx);
                 return;
             }
-            let prompt = app.create_form.prompt.trim().to_string();
+
+            let source = app.create_form.source.trim().to_string();
+            let name = app.create_form.name.trim().to_string();
+            let branch = app.create_form.branch_request();
+            let prompt = app.create_form.prompt.text().trim().to_string();
             let prompt_opt = if prompt.is_empty() {
                 None
             } else {
                 Some(prompt)
             };
 
-            let name = Path::new(&source)
-                .file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_else(|| "slot".into());
-
             let tx = event_tx.clone();
             let sm = Arc::clone(slot_manager);
             app.mode = Mode::Loading(format!("Creating slot {name}..."));
 
             tokio::spawn(async move {
                 match sm
-                    .create_slot(&name, &source, None, prompt_opt.as_deref())
+                    .create_slot(
+                        &name,
+                        &source,
+                        branch.as_deref(),
+                        prompt_opt.as_deref(),
+                        None,
+                    )
                     .await
                 {
                     Ok(slot) => {
@@ -916,22 +1396,220 @@ fn handle_create_dialog(
                 }
             });
         }
-        KeyCode::Backspace => match app.create_form.focus {
-            CreateSlotField::Source => {
-                app.create_form.source.pop();
-                app.create_form.schedule_filter();
+        KeyCode::Backspace => {
+            match focus {
+                CreateSlotField::Source => {
+                    app.create_form.source.pop();
+                    app.create_form.schedule_filter();
+                }
+                CreateSlotField::Name => {
+                    app.create_form.name.pop();
+                    app.create_form.name_edited = !app.create_form.name.is_empty();
+                }
+                CreateSlotField::Branch => {
+                    app.create_form.branch.pop();
+                    app.create_form.filter_branches();
+                }
+                // Edited by its text area above
+                CreateSlotField::Prompt => {}
             }
-            CreateSlotField::Prompt => {
-                app.create_form.prompt.pop();
+            revalidate_create_form(app);
+        }
+        KeyCode::Char(c) => {
+            match focus {
+                CreateSlotField::Source => {
+                    app.create_form.source.push(c);
+                    app.create_form.schedule_filter();
+                }
+                CreateSlotField::Name => {
+                    app.create_form.name.push(c);
+                    app.create_form.name_edited = true;
+                }
+                CreateSlotField::Branch => {
+                    app.create_form.branch.push(c);
+                    app.create_form.filter_branches();
+                }
+                CreateSlotField::Prompt => {}
             }
-        },
-        KeyCode::Char(c) => match app.create_form.focus {
-            CreateSlotField::Source => {
-                app.create_form.source.push(c);
-                app.create_form.schedule_filter();
+            revalidate_create_form(app);
+        }
+        _ => {}
+    }
+}
+
+/// Move focus in the create dialog, opening or closing the completion list
+/// that belongs to each field.
+fn focus_create_field(
+    app: &mut App,
+    field: CreateSlotField,
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+) {
+    let form = &mut app.create_form;
+    if form.focus == CreateSlotField::Source && field != CreateSlotField::Source {
+        form.filtered_candidates.clear();
+        form.selected_candidate = None;
+        form.suggest_name(&app.slots);
+        load_source_branches(app, event_tx);
+    }
+
+    let form = &mut app.create_form;
+    form.filtered_branches.clear();
+    form.selected_branch = None;
+    match field {
+        CreateSlotField::Source => form.apply_filter(),
+        CreateSlotField::Branch => form.filter_branches(),
+        CreateSlotField::Name | CreateSlotField::Prompt => {}
+    }
+    form.focus = field;
+}
+
+/// Re-check a form that failed validation, so errors clear as they're fixed.
+fn revalidate_create_form(app: &mut App) {
+    if !app.create_form.errors.is_empty() {
+        app.create_form.errors = app.create_form.validate(&app.slots);
+    }
+}
+
+/// List the branches of a local source for completion. Remote sources get
+/// no completion, and their branch is checked when the slot is created.
+fn load_source_branches(app: &mut App, event_tx: &mpsc::UnboundedSender<AppEvent>) {
+    let source = app.create_form.source.trim().to_string();
+    if source.is_empty() || app.create_form.branches_source.as_deref() == Some(source.as_str()) {
+        return;
+    }
+    app.create_form.branches_source = Some(source.clone());
+    app.create_form.branches_loading = true;
+    app.create_form.all_branches.clear();
+
+    let tx = event_tx.clone();
+    tokio::spawn(async move {
+        let path = Path::new(&source);
+        let branches = if path.is_dir() {
+            git::list_branches(path).await.unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let _ = tx.send(AppEvent::BranchesLoaded { source, branches });
+    });
+}
+
+/// Pass a key to a focused prompt. Ctrl+E asks the main loop to open the
+/// prompt in `$EDITOR`. Returns false for keys the dialog should handle.
+fn edit_prompt(app: &mut App, field: PromptField, key: KeyEvent) -> bool {
+    if key.code == KeyCode::Char('e') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        app.external_edit = Some(field);
+        return true;
+    }
+    app.prompt_mut(field).handle_key(key)
+}
+
+fn is_ctrl_p(key: KeyEvent) -> bool {
+    key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Open the prompt library over a dialog, loading its prompts in the background.
+fn open_prompt_picker(
+    app: &mut App,
+    target: PromptField,
+    slot_manager: &Arc<Orchestrator>,
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+) {
+    app.prompt_picker = PromptPickerState::new(target);
+    app.mode = Mode::PromptPickerDialog;
+
+    let tx = event_tx.clone();
+    let library = PromptLibrary::new(&slot_manager.workspace_root().join(".slots"));
+    tokio::spawn(async move {
+        match library.list().await {
+            Ok(prompts) => {
+                let _ = tx.send(AppEvent::PromptsLoaded(prompts));
             }
-            CreateSlotField::Prompt => app.create_form.prompt.push(c),
-        },
+            Err(e) => {
+                let _ = tx.send(AppEvent::PromptsLoaded(Vec::new()));
+                let _ = tx.send(AppEvent::Error(format!("Loading prompts failed: {e}")));
+            }
+        }
+    });
+}
+
+fn handle_prompt_picker_dialog(app: &mut App, key: KeyEvent) {
+    let picker = &mut app.prompt_picker;
+    match key.code {
+        KeyCode::Esc => app.mode = App::prompt_dialog_mode(picker.target),
+        KeyCode::Up => picker.select_prev(),
+        KeyCode::Down => picker.select_next(),
+        KeyCode::Enter => {
+            if let Some(prompt) = picker.selected_prompt().cloned() {
+                app.insert_library_prompt(&prompt);
+            }
+        }
+        KeyCode::Backspace => {
+            picker.query.pop();
+            picker.apply_filter();
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            picker.query.push(c);
+            picker.apply_filter();
+        }
+        _ => {}
+    }
+}
+
+/// Insert pasted text into the focused field. Prompts keep line breaks;
+/// single-line fields get the text joined onto one line. In terminal mode
+/// the paste is forwarded to the agent as a bracketed paste.
+pub async fn handle_paste(app: &mut App, text: &str) {
+    let one_line = || {
+        text.lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    match app.mode {
+        Mode::CreateSlotDialog => {
+            let form = &mut app.create_form;
+            match form.focus {
+                CreateSlotField::Source => {
+                    form.source.push_str(&one_line());
+                    form.schedule_filter();
+                }
+                CreateSlotField::Name => {
+                    form.name.push_str(&one_line());
+                    form.name_edited = !form.name.is_empty();
+                }
+                CreateSlotField::Branch => {
+                    form.branch.push_str(&one_line());
+                    form.filter_branches();
+                }
+                CreateSlotField::Prompt => form.prompt.insert_str(text),
+            }
+            revalidate_create_form(app);
+        }
+        Mode::SpawnAgentDialog => {
+            let form = &mut app.agent_form;
+            match form.focus {
+                SpawnAgentField::Prompt => form.prompt.insert_str(text),
+                SpawnAgentField::AllowedTools => form.allowed_tools.push_str(&one_line()),
+                SpawnAgentField::MaxTurns => form
+                    .max_turns
+                    .extend(text.chars().filter(|c| c.is_ascii_digit())),
+            }
+        }
+        Mode::Terminal => {
+            let Some(slot_name) = app.selected_slot().map(|s| s.name.clone()) else {
+                return;
+            };
+            if let Some(conn) = app.agent_connections.get(&slot_name) {
+                let bytes = format!("\x1b[200~{text}\x1b[201~");
+                let mut conn = conn.lock().await;
+                let _ = conn.send_input(bytes.as_bytes()).await;
+            }
+        }
+        Mode::PromptPickerDialog => {
+            app.prompt_picker.query.push_str(&one_line());
+            app.prompt_picker.apply_filter();
+        }
         _ => {}
     }
 }
@@ -939,9 +1617,18 @@ fn handle_create_dialog(
 fn handle_agent_dialog(
     app: &mut App,
     key: KeyEvent,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
+    if is_ctrl_p(key) {
+        open_prompt_picker(app, PromptField::SpawnAgent, slot_manager, event_tx);
+        return;
+    }
+    if app.agent_form.focus == SpawnAgentField::Prompt
+        && edit_prompt(app, PromptField::SpawnAgent, key)
+    {
+        return;
+    }
     match key.code {
         KeyCode::Esc => {
             app.mode = Mode::SlotList;
@@ -965,7 +1652,7 @@ fn handle_agent_dialog(
                 return;
             };
             let name = slot.name.clone();
-            let prompt = app.agent_form.prompt.trim().to_string();
+            let prompt = app.agent_form.prompt.text().trim().to_string();
             let tools = app.agent_form.allowed_tools.trim().to_string();
             let max_turns: Option<u32> = app.agent_form.max_turns.trim().parse().ok();
 
@@ -1003,9 +1690,8 @@ fn handle_agent_dialog(
             });
         }
         KeyCode::Backspace => match app.agent_form.focus {
-            SpawnAgentField::Prompt => {
-                app.agent_form.prompt.pop();
-            }
+            // Edited by its text area above
+            SpawnAgentField::Prompt => {}
             SpawnAgentField::AllowedTools => {
                 app.agent_form.allowed_tools.pop();
             }
@@ -1014,7 +1700,7 @@ fn handle_agent_dialog(
             }
         },
         KeyCode::Char(c) => match app.agent_form.focus {
-            SpawnAgentField::Prompt => app.agent_form.prompt.push(c),
+            SpawnAgentField::Prompt => {}
             SpawnAgentField::AllowedTools => app.agent_form.allowed_tools.push(c),
             SpawnAgentField::MaxTurns => {
                 if c.is_ascii_digit() {
@@ -1047,7 +1733,7 @@ fn reload_log_for_selected(app: &mut App) {
             LogSource::Aspire => slot.aspire_log_path(),
         };
         let tail = ao_core::services::log_tailer::read_tail(&log_path, 50);
-        app.log_lines = tail.lines().map(|l| l.to_string()).collect();
+        app.log_lines = tail.lines().map(LogViewLine::from).collect();
         if app.log_auto_follow {
             app.log_scroll = app.log_lines.len().saturating_sub(1);
         }
@@ -1058,7 +1744,7 @@ fn reload_log_for_selected(app: &mut App) {
 
 fn open_blueprint_list(
     app: &mut App,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     app.blueprint_list.loading = true;
@@ -1078,10 +1764,12 @@ fn open_blueprint_list(
 fn launch_blueprint_load(
     app: &mut App,
     name: &str,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
-    let store = BlueprintStore::new(&slot_manager.workspace_root().join(".slots"));
+    let slots_dir = slot_manager.workspace_root().join(".slots");
+    let store = BlueprintStore::new(&slots_dir);
+    let prompt_library = PromptLibrary::new(&slots_dir);
     let name = name.to_string();
     let tx = event_tx.clone();
     let sm = Arc::clone(slot_manager);
@@ -1106,7 +1794,11 @@ fn launch_blueprint_load(
             }
         };
 
-        let resolved = match ao_core::services::blueprint::resolve(&blueprint) {
+        let resolved = match prompt_library
+            .list()
+            .await
+            .and_then(|prompts| ao_core::services::blueprint::resolve(&blueprint, &prompts))
+        {
             Ok(r) => r,
             Err(e) => {
                 let _ = tx.send(AppEvent::Error(format!("Resolve failed: {e}")));
@@ -1151,6 +1843,7 @@ fn launch_blueprint_load(
                     &slot_config.source,
                     slot_config.branch.as_deref(),
                     prompt,
+                    slot_config.clone_strategy,
                 )
                 .await
             {
@@ -1201,7 +1894,7 @@ fn launch_blueprint_load(
 
 fn launch_batch_start_all(
     app: &mut App,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     let targets: Vec<String> = app
@@ -1259,7 +1952,7 @@ fn launch_batch_start_all(
 
 fn launch_batch_stop_all(
     app: &mut App,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     let targets: Vec<String> = app
@@ -1314,7 +2007,7 @@ fn launch_batch_stop_all(
 
 fn launch_batch_rebase_all(
     app: &mut App,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     let targets: Vec<String> = app.slots.iter().map(|s| s.name.clone()).collect();
@@ -1341,12 +2034,19 @@ fn launch_batch_rebase_all(
                 done: false,
             });
 
-            if let Err(e) = sm.rebase(name).await {
+            let failure = match sm.rebase(name).await {
+                Ok(RebaseOutcome::Completed) => None,
+                Ok(RebaseOutcome::Conflicted(files)) => {
+                    Some(format!("conflicts in {} file(s)", files.len()))
+                }
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(err) = failure {
                 let _ = tx.send(AppEvent::BatchProgress {
                     completed: i + 1,
                     total,
                     current_slot: None,
-                    failure: Some((name.clone(), e.to_string())),
+                    failure: Some((name.clone(), err)),
                     done: false,
                 });
             }
@@ -1364,7 +2064,7 @@ fn launch_batch_rebase_all(
 
 fn launch_batch_push_all(
     app: &mut App,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     let targets: Vec<String> = app.slots.iter().map(|s| s.name.clone()).collect();
@@ -1414,7 +2114,7 @@ fn launch_batch_push_all(
 
 fn launch_batch_destroy_all(
     app: &mut App,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     let targets: Vec<String> = app.slots.iter().map(|s| s.name.clone()).collect();
@@ -1464,7 +2164,7 @@ fn launch_batch_destroy_all(
 
 fn launch_batch_spawn_agents(
     app: &mut App,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     let targets: Vec<String> = app
@@ -1,5 +1,7 @@
 pub mod app;
+pub mod cli;
 pub mod event;
 pub mod host;
 pub mod keys;
+pub mod text_area;
 pub mod ui;
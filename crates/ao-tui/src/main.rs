@@ -2,6 +2,7 @@ use std::io;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste};
 use crossterm::execute;
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
@@ -10,14 +11,18 @@ use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 use tokio::sync::mpsc;
 
-use ao_core::models::RepoCandidate;
+use ao_core::models::{Discrepancy, RepoCandidate};
 use ao_core::services::agent_host;
 use ao_core::services::log_tailer::LogLine;
-use ao_core::services::slot_manager::SlotManager;
+use ao_core::services::orchestrator::Orchestrator;
+use ao_core::OrchestratorError;
 
-use ao_tui::app::{App, CreateSlotField, Mode};
-use ao_tui::event::{spawn_input_task, spawn_tick_task, AppEvent};
-use ao_tui::{keys, ui};
+use ao_tui::app::{App, CreateSlotField, LogViewLine, Mode, PromptField};
+use ao_tui::event::{
+    spawn_git_status_task, spawn_input_task, spawn_pull_request_task, spawn_tick_task, AppEvent,
+    InputControl,
+};
+use ao_tui::{keys, text_area, ui};
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
@@ -37,6 +42,18 @@ async fn main() -> color_eyre::Result<()> {
         return run_host_agent(&args).await;
     }
 
+    // Headless subcommands (list, create, start, ...)
+    if let Some(parsed) = ao_tui::cli::parse(&args[1..]) {
+        let code = match parsed {
+            Ok(invocation) => ao_tui::cli::run(invocation, find_slots_directory()).await,
+            Err(message) => {
+                eprintln!("error: {message}\n\n{}", ao_tui::cli::USAGE);
+                ao_tui::cli::EXIT_USAGE
+            }
+        };
+        std::process::exit(code);
+    }
+
     // Set up debug logging if requested
     let _guard = if debug || headless_script.is_some() {
         Some(setup_debug_logging())
@@ -99,13 +116,21 @@ async fn run_interactive() -> color_eyre::Result<()> {
     std::fs::create_dir_all(&slots_directory)?;
 
     let (log_tx, mut log_rx) = mpsc::unbounded_channel::<LogLine>();
-    let slot_manager = Arc::new(SlotManager::new(slots_directory, log_tx));
-    slot_manager.load_state().await?;
-    slot_manager.reconnect_existing_sessions().await?;
+    let slot_manager = open_orchestrator(slots_directory, log_tx).await?;
+    // Before reconnecting, which quietly clears stale agent host files
+    let discrepancies = reconcile_on_startup(&slot_manager).await;
+    // A daemon owns its own sessions; only an in-process manager reconnects
+    if let Some(sm) = slot_manager.local() {
+        sm.reconnect_existing_sessions().await?;
+    }
 
     let (event_tx, mut event_rx) = mpsc::unbounded_channel::<AppEvent>();
-    let _input_task = spawn_input_task(event_tx.clone());
+    let input = spawn_input_task(event_tx.clone());
     let _tick_task = spawn_tick_task(event_tx.clone());
+    let _git_status_task = spawn_git_status_task(slot_manager.clone(), event_tx.clone());
+    // An attached daemon polls pull requests itself
+    let _pull_request_task =
+        (!slot_manager.is_remote()).then(|| spawn_pull_request_task(slot_manager.clone()));
 
     // Forward log lines into the event channel
     let log_event_tx = event_tx.clone();
@@ -121,12 +146,16 @@ async fn run_interactive() -> color_eyre::Result<()> {
     // Initialize terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = App::new();
     app.slots = slot_manager.get_slots().await;
+    if !discrepancies.is_empty() {
+        app.reconcile.items = discrepancies;
+        app.mode = Mode::ReconcileDialog;
+    }
 
     // Main event loop
     let mut prev_mode_is_dialog = false;
@@ -142,6 +171,10 @@ async fn run_interactive() -> color_eyre::Result<()> {
                 | Mode::BlueprintListDialog
                 | Mode::BlueprintSaveDialog
                 | Mode::BatchProgress
+                | Mode::RebaseConflictDialog { .. }
+                | Mode::ProblemsDialog { .. }
+                | Mode::ReconcileDialog
+                | Mode::PromptPickerDialog
         );
         if prev_mode_is_dialog && !cur_mode_is_dialog {
             terminal.clear()?;
@@ -156,6 +189,10 @@ async fn run_interactive() -> color_eyre::Result<()> {
             tokio::time::sleep(std::time::Duration::from_millis(10)).await;
         }
 
+        if let Some(field) = app.external_edit.take() {
+            edit_prompt_in_editor(&mut terminal, &mut app, &input, field).await?;
+        }
+
         // Debounce check
         if matches!(app.mode, Mode::CreateSlotDialog)
             && app.create_form.focus == CreateSlotField::Source
@@ -172,15 +209,88 @@ async fn run_interactive() -> color_eyre::Result<()> {
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableBracketedPaste
+    )?;
     terminal.show_cursor()?;
 
     Ok(())
 }
 
+/// Hand the terminal to `$EDITOR` to edit a prompt, then take it back.
+async fn edit_prompt_in_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    input: &InputControl,
+    field: PromptField,
+) -> color_eyre::Result<()> {
+    let paused = input.pause().await;
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableBracketedPaste
+    )?;
+
+    let text = app.prompt_mut(field).text();
+    let result = tokio::task::spawn_blocking(move || text_area::edit_externally(&text)).await?;
+
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableBracketedPaste
+    )?;
+    terminal.clear()?;
+    drop(paused);
+
+    apply_external_edit(app, field, result);
+    Ok(())
+}
+
+fn apply_external_edit(app: &mut App, field: PromptField, result: io::Result<Option<String>>) {
+    match result {
+        Ok(Some(text)) => app.prompt_mut(field).set_text(&text),
+        Ok(None) => app.set_status("Editor exited with an error; prompt unchanged"),
+        Err(e) => app.set_status(format!("Cannot open editor: {e}")),
+    }
+}
+
+/// Attach to a running daemon, or manage the slots in this process. If another
+/// orchestrator already manages them in-process, exit with a hint instead.
+async fn open_orchestrator(
+    slots_directory: PathBuf,
+    log_tx: mpsc::UnboundedSender<LogLine>,
+) -> color_eyre::Result<Arc<Orchestrator>> {
+    match Orchestrator::connect_or_load(slots_directory, log_tx).await {
+        Ok(orchestrator) => Ok(Arc::new(orchestrator)),
+        Err(e @ OrchestratorError::WorkspaceLocked(_)) => {
+            eprintln!(
+                "error: {e}\nQuit it, or run `ao-tui daemon` so several front ends can share the slots."
+            );
+            std::process::exit(1);
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Compare saved slots with the slots directory. A failure is logged and
+/// treated as nothing to report so it never blocks startup.
+async fn reconcile_on_startup(slot_manager: &Orchestrator) -> Vec<Discrepancy> {
+    match slot_manager.reconcile().await {
+        Ok(found) => found,
+        Err(e) => {
+            tracing::warn!("reconcile slots: {e}");
+            Vec::new()
+        }
+    }
+}
+
 /// Connect to all running agent hosts and start streaming their output.
 async fn connect_running_agents(
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     let slots_dir = slot_manager.slots_directory().to_path_buf();
@@ -245,9 +355,13 @@ async fn run_headless(script_path: PathBuf) -> color_eyre::Result<()> {
     std::fs::create_dir_all(&slots_directory)?;
 
     let (log_tx, mut log_rx) = mpsc::unbounded_channel::<LogLine>();
-    let slot_manager = Arc::new(SlotManager::new(slots_directory, log_tx));
-    slot_manager.load_state().await?;
-    slot_manager.reconnect_existing_sessions().await?;
+    let slot_manager = open_orchestrator(slots_directory, log_tx).await?;
+    // Before reconnecting, which quietly clears stale agent host files
+    let discrepancies = reconcile_on_startup(&slot_manager).await;
+    // A daemon owns its own sessions; only an in-process manager reconnects
+    if let Some(sm) = slot_manager.local() {
+        sm.reconnect_existing_sessions().await?;
+    }
 
     let (event_tx, mut event_rx) = mpsc::unbounded_channel::<AppEvent>();
     // No spawn_input_task — input comes from script
@@ -265,6 +379,10 @@ async fn run_headless(script_path: PathBuf) -> color_eyre::Result<()> {
     let mut terminal = Terminal::new(backend)?;
     let mut app = App::new();
     app.slots = slot_manager.get_slots().await;
+    if !discrepancies.is_empty() {
+        app.reconcile.items = discrepancies;
+        app.mode = Mode::ReconcileDialog;
+    }
 
     for raw_line in &lines {
         let line = raw_line.split('#').next().unwrap_or("").trim();
@@ -313,9 +431,11 @@ async fn run_headless(script_path: PathBuf) -> color_eyre::Result<()> {
         if let Some(key_str) = line.strip_prefix("key:") {
             let trimmed = key_str.trim();
 
-            // Check for Shift+ prefix
+            // Check for Shift+ / Ctrl+ prefix
             let (modifiers, key_name) = if let Some(rest) = trimmed.strip_prefix("shift+") {
                 (KeyModifiers::SHIFT, rest)
+            } else if let Some(rest) = trimmed.strip_prefix("ctrl+") {
+                (KeyModifiers::CONTROL, rest)
             } else {
                 (KeyModifiers::NONE, trimmed)
             };
@@ -359,6 +479,18 @@ async fn run_headless(script_path: PathBuf) -> color_eyre::Result<()> {
                 state: KeyEventState::NONE,
             };
             keys::handle_key(&mut app, key_event, &slot_manager, &event_tx).await;
+            // No terminal to hand over; the editor runs with inherited stdio
+            if let Some(field) = app.external_edit.take() {
+                let text = app.prompt_mut(field).text();
+                let result = text_area::edit_externally(&text);
+                apply_external_edit(&mut app, field, result);
+            }
+            continue;
+        }
+
+        // `\n` in the text stands for a line break
+        if let Some(text) = line.strip_prefix("paste:") {
+            keys::handle_paste(&mut app, &text.replace("\\n", "\n")).await;
             continue;
         }
 
@@ -429,7 +561,7 @@ fn headless_inject_candidates(app: &mut App) {
 async fn headless_wait(
     app: &mut App,
     event_rx: &mut mpsc::UnboundedReceiver<AppEvent>,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
     ms: u64,
 ) {
@@ -457,7 +589,7 @@ async fn headless_wait(
 async fn process_event(
     app: &mut App,
     event: AppEvent,
-    slot_manager: &Arc<SlotManager>,
+    slot_manager: &Arc<Orchestrator>,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
 ) {
     match event {
@@ -466,11 +598,23 @@ async fn process_event(
                 keys::handle_key(app, key, slot_manager, event_tx).await;
             }
         }
+        AppEvent::Paste(text) => keys::handle_paste(app, &text).await,
         AppEvent::Tick => {
+            if let Some(sm) = slot_manager.local() {
+                if let Err(e) = sm.reload_if_changed().await {
+                    tracing::warn!("reload state: {e}");
+                }
+                if let Some(note) = sm.take_state_recovery_note() {
+                    app.set_status(note);
+                }
+            }
             app.slots = slot_manager.get_slots().await;
             if !app.slots.is_empty() && app.selected_index >= app.slots.len() {
                 app.selected_index = app.slots.len() - 1;
             }
+            let slots = &app.slots;
+            app.git_status
+                .retain(|name, _| slots.iter().any(|s| &s.name == name));
             // Recompute dashboard activity on each tick
             app.recompute_activity();
         }
@@ -483,12 +627,16 @@ async fn process_event(
                 log_line.slot_name.clone(),
                 log_line.source,
                 log_line.line.clone(),
+                log_line.timestamp,
             );
 
             // Update single-slot log view if matching
             if let Some(slot) = app.selected_slot() {
                 if slot.name == log_line.slot_name {
-                    app.log_lines.push(log_line.line);
+                    app.log_lines.push(LogViewLine {
+                        timestamp: Some(log_line.timestamp),
+                        text: log_line.line,
+                    });
                     if app.log_auto_follow {
                         app.log_scroll = app.log_lines.len().saturating_sub(1);
                     }
@@ -516,11 +664,28 @@ async fn process_event(
             app.create_form.scan_loading = false;
             app.create_form.apply_filter();
         }
+        AppEvent::BranchesLoaded { source, branches } => {
+            let form = &mut app.create_form;
+            // Ignore a list for a source that has since been changed
+            if form.branches_source.as_deref() == Some(source.as_str()) {
+                form.all_branches = branches;
+                form.branches_loading = false;
+                if form.focus == CreateSlotField::Branch {
+                    form.filter_branches();
+                }
+                if !form.errors.is_empty() {
+                    form.errors = form.validate(&app.slots);
+                }
+            }
+        }
         AppEvent::BlueprintNamesLoaded(names) => {
             app.blueprint_list.names = names;
             app.blueprint_list.loading = false;
             app.blueprint_list.selected = 0;
         }
+        AppEvent::PromptsLoaded(prompts) => {
+            app.prompt_picker.set_prompts(prompts);
+        }
         AppEvent::BatchProgress {
             completed,
             total,
@@ -551,6 +716,47 @@ async fn process_event(
         AppEvent::TerminalOutput { slot_name, bytes } => {
             app.feed_terminal_bytes(&slot_name, &bytes);
         }
+        AppEvent::RebaseConflicted { slot_name } => {
+            app.slots = slot_manager.get_slots().await;
+            if matches!(
+                app.mode,
+                Mode::SlotList | Mode::Loading(_) | Mode::RebaseConflictDialog { .. }
+            ) {
+                app.mode = Mode::RebaseConflictDialog { slot_name };
+            } else {
+                app.set_status(format!(
+                    "Rebase of {slot_name} stopped on conflicts; press R on the slot to resolve"
+                ));
+            }
+        }
+        AppEvent::DiffLoaded { slot_name, result } => {
+            if let Some(ref mut view) = app.diff_view {
+                if view.slot_name == slot_name {
+                    match result {
+                        Ok(files) => view.set_files(files),
+                        Err(e) => {
+                            view.loading = false;
+                            app.set_status(format!("Error: diff failed: {e}"));
+                        }
+                    }
+                }
+            }
+        }
+        AppEvent::LogHistoryLoaded { generation, result } => {
+            if let Some(ref mut history) = app.log_history {
+                match result {
+                    Ok(page) => history.add_page(generation, page),
+                    Err(e) if generation == history.generation => {
+                        history.loading = false;
+                        app.set_status(format!("Error: log history search failed: {e}"));
+                    }
+                    Err(_) => {}
+                }
+            }
+        }
+        AppEvent::GitStatusLoaded { slot_name, status } => {
+            app.git_status.insert(slot_name, status);
+        }
     }
 }
 
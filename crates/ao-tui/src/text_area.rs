@@ -0,0 +1,295 @@
+//! Multi-line text editing state for prompt fields.
+
+use std::io::{self, Write};
+use std::process::Command;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// An editable block of text with a cursor. Rendered by `ui::text_area`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextArea {
+    lines: Vec<String>,
+    /// Cursor line.
+    row: usize,
+    /// Cursor position within the line, in chars.
+    col: usize,
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            row: 0,
+            col: 0,
+        }
+    }
+}
+
+impl TextArea {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A text area holding `text`, with the cursor at its end.
+    pub fn from_text(text: &str) -> Self {
+        let mut area = Self::new();
+        area.insert_str(text);
+        area
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.len() == 1 && self.lines[0].is_empty()
+    }
+
+    /// Cursor position as (line, char column).
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    /// Replace the contents, leaving the cursor at the end.
+    pub fn set_text(&mut self, text: &str) {
+        *self = Self::from_text(text);
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        if c == '\n' {
+            self.insert_newline();
+            return;
+        }
+        let at = self.byte_col();
+        self.lines[self.row].insert(at, c);
+        self.col += 1;
+    }
+
+    /// Insert text at the cursor. `\r\n` and `\r` line endings become `\n`.
+    pub fn insert_str(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        for c in text.chars() {
+            self.insert_char(c);
+        }
+    }
+
+    pub fn insert_newline(&mut self) {
+        let at = self.byte_col();
+        let rest = self.lines[self.row].split_off(at);
+        self.row += 1;
+        self.col = 0;
+        self.lines.insert(self.row, rest);
+    }
+
+    /// Delete the char before the cursor, joining lines at the start of one.
+    pub fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let at = self.byte_col();
+            self.lines[self.row].remove(at);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    /// Delete the char under the cursor, joining the next line at the end of one.
+    pub fn delete(&mut self) {
+        if self.col < self.line_len() {
+            let at = self.byte_col();
+            self.lines[self.row].remove(at);
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    /// Delete back to the start of the previous word, like Ctrl+W in a shell.
+    pub fn delete_word_before(&mut self) {
+        if self.col == 0 {
+            self.backspace();
+            return;
+        }
+        let end = self.col;
+        self.move_word_left();
+        let start = self.byte_col();
+        let end = self.byte_at(end);
+        self.lines[self.row].replace_range(start..end, "");
+    }
+
+    /// Delete from the cursor to the end of the line, like Ctrl+K.
+    pub fn delete_to_line_end(&mut self) {
+        let at = self.byte_col();
+        self.lines[self.row].truncate(at);
+    }
+
+    pub fn move_left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len();
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if self.col < self.line_len() {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if self.row > 0 {
+            self.row -= 1;
+            self.col = self.col.min(self.line_len());
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = self.col.min(self.line_len());
+        }
+    }
+
+    pub fn move_home(&mut self) {
+        self.col = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.col = self.line_len();
+    }
+
+    /// Move to the start of the current or previous word.
+    pub fn move_word_left(&mut self) {
+        if self.col == 0 {
+            self.move_left();
+            return;
+        }
+        let chars: Vec<char> = self.lines[self.row].chars().collect();
+        let mut col = self.col;
+        while col > 0 && chars[col - 1].is_whitespace() {
+            col -= 1;
+        }
+        while col > 0 && !chars[col - 1].is_whitespace() {
+            col -= 1;
+        }
+        self.col = col;
+    }
+
+    /// Move past the end of the current or next word.
+    pub fn move_word_right(&mut self) {
+        let chars: Vec<char> = self.lines[self.row].chars().collect();
+        if self.col == chars.len() {
+            self.move_right();
+            return;
+        }
+        let mut col = self.col;
+        while col < chars.len() && chars[col].is_whitespace() {
+            col += 1;
+        }
+        while col < chars.len() && !chars[col].is_whitespace() {
+            col += 1;
+        }
+        self.col = col;
+    }
+
+    /// Apply an editing key. Returns false for keys the text area doesn't use,
+    /// such as Tab, Esc and plain Enter, so the dialog can handle them.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            // Plain Enter submits the dialog; these insert a line break instead
+            KeyCode::Enter if alt || key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.insert_newline()
+            }
+            KeyCode::Char('j') if ctrl => self.insert_newline(),
+            KeyCode::Char('w') if ctrl => self.delete_word_before(),
+            KeyCode::Backspace if ctrl || alt => self.delete_word_before(),
+            KeyCode::Char('k') if ctrl => self.delete_to_line_end(),
+            KeyCode::Char('a') if ctrl => self.move_home(),
+            KeyCode::Char(_) if ctrl => return false,
+            KeyCode::Char(c) => self.insert_char(c),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left if ctrl || alt => self.move_word_left(),
+            KeyCode::Right if ctrl || alt => self.move_word_right(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Up => self.move_up(),
+            KeyCode::Down => self.move_down(),
+            KeyCode::Home => self.move_home(),
+            KeyCode::End => self.move_end(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn line_len(&self) -> usize {
+        self.lines[self.row].chars().count()
+    }
+
+    fn byte_col(&self) -> usize {
+        self.byte_at(self.col)
+    }
+
+    fn byte_at(&self, col: usize) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len())
+    }
+}
+
+/// Edit `text` in `$VISUAL` or `$EDITOR` and return the saved result, or
+/// `None` if the editor exited with an error. The caller must hand the
+/// terminal over first (leave raw mode and the alternate screen).
+pub fn edit_externally(text: &str) -> io::Result<Option<String>> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| default_editor().to_string());
+    let mut words = editor.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| io::Error::other("$EDITOR is empty"))?;
+
+    // Random name, readable only by us, removed when `file` drops
+    let mut file = tempfile::Builder::new()
+        .prefix("ao-prompt-")
+        .suffix(".md")
+        .tempfile()?;
+    file.write_all(text.as_bytes())?;
+    file.flush()?;
+    let status = Command::new(program).args(words).arg(file.path()).status();
+    // Reopened by path: editors often replace the file rather than write to it
+    let edited = std::fs::read_to_string(file.path());
+
+    if !status?.success() {
+        return Ok(None);
+    }
+    // Editors add a final newline that wasn't part of the prompt
+    let edited = edited?;
+    let edited = edited.strip_suffix('\n').unwrap_or(&edited);
+    Ok(Some(
+        edited.strip_suffix('\r').unwrap_or(edited).to_string(),
+    ))
+}
+
+fn default_editor() -> &'static str {
+    if cfg!(windows) {
+        "notepad"
+    } else {
+        "vi"
+    }
+}
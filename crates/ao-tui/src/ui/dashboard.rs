@@ -4,9 +4,12 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Sparkline};
 use ratatui::Frame;
 
-use ao_core::models::{AgentStatus, SlotStatus};
+use ao_core::models::{AgentStatus, RebaseStatus, SlotStatus};
 
 use crate::app::App;
+use crate::ui::detail_panel::{
+    git_change_summary, git_color, pr_color, pr_summary, problems_color, problems_summary,
+};
 
 /// Render the full dashboard view with slot cards in a grid.
 pub fn render(f: &mut Frame, area: Rect, app: &App) {
@@ -180,7 +183,7 @@ fn render_card(f: &mut Frame, area: Rect, app: &App, idx: usize) {
     lines.push(Line::from(vec![
         Span::styled(" Aspire: ", Style::default().fg(Color::DarkGray)),
         Span::styled(
-            format!("{aspire_icon} {:?}", slot.status),
+            format!("{aspire_icon} {}", slot.status),
             Style::default().fg(aspire_color),
         ),
     ]));
@@ -195,6 +198,72 @@ fn render_card(f: &mut Frame, area: Rect, app: &App, idx: usize) {
         ),
     ]));
 
+    // Rebase conflicts
+    if slot.rebase_status == RebaseStatus::Conflicted {
+        lines.push(Line::from(vec![
+            Span::styled(" Rebase: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("! {} conflicted", slot.conflicted_files.len()),
+                Style::default().fg(Color::Red),
+            ),
+        ]));
+    }
+
+    // Build problems
+    if !slot.problems.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled(" Build:  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                truncate(
+                    &problems_summary(&slot.problems),
+                    (inner.width as usize).saturating_sub(9),
+                ),
+                Style::default().fg(problems_color(&slot.problems)),
+            ),
+        ]));
+    }
+
+    // Failed spans reported over OTLP
+    if let Some(last) = slot.telemetry.as_ref().and_then(|t| t.error_spans.last()) {
+        lines.push(Line::from(vec![
+            Span::styled(" Traces: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                truncate(
+                    &format!("✗ {}", last.name),
+                    (inner.width as usize).saturating_sub(9),
+                ),
+                Style::default().fg(Color::Red),
+            ),
+        ]));
+    }
+
+    // Git summary
+    if let Some(git) = app.git_status.get(&slot.name) {
+        let sync = match git.vs_upstream {
+            Some(ab) => format!("↑{} ↓{} ", ab.ahead, ab.behind),
+            None => String::new(),
+        };
+        let summary = format!("{sync}{}", git_change_summary(git));
+        lines.push(Line::from(vec![
+            Span::styled(" Git:    ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                truncate(&summary, (inner.width as usize).saturating_sub(9)),
+                Style::default().fg(git_color(git)),
+            ),
+        ]));
+    }
+
+    // Pull request
+    if let Some(ref pr) = slot.pull_request {
+        lines.push(Line::from(vec![
+            Span::styled(" PR:     ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                truncate(&pr_summary(pr), (inner.width as usize).saturating_sub(9)),
+                Style::default().fg(pr_color(pr)),
+            ),
+        ]));
+    }
+
     // Idle time
     if let Some(act) = activity {
         if let Some(last_ts) = act.log_timestamps.back() {
@@ -271,6 +340,7 @@ fn aspire_display(status: &SlotStatus) -> (&'static str, Color) {
         SlotStatus::Ready => ("-", Color::DarkGray),
         SlotStatus::Error => ("!", Color::Red),
         SlotStatus::Provisioning => (".", Color::DarkGray),
+        SlotStatus::SettingUp { .. } => ("~", Color::Yellow),
     }
 }
 
@@ -5,7 +5,10 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
-use ao_core::models::{AgentStatus, Slot, SlotStatus};
+use ao_core::models::{
+    AgentStatus, CheckState, GitOperation, GitStatus, PrState, Problem, PullRequest, RebaseStatus,
+    ReviewState, Slot, SlotStatus, TelemetrySummary,
+};
 
 use crate::app::App;
 
@@ -21,17 +24,21 @@ pub fn render_with_now(f: &mut Frame, area: Rect, app: &App, now: DateTime<Utc>)
         return;
     };
 
-    let lines = build_detail_lines(slot, now);
+    let lines = build_detail_lines(slot, app.git_status.get(&slot.name), now);
     let paragraph = Paragraph::new(lines).block(block);
     f.render_widget(paragraph, area);
 }
 
-fn build_detail_lines(slot: &Slot, now: DateTime<Utc>) -> Vec<Line<'static>> {
+fn build_detail_lines(
+    slot: &Slot,
+    git: Option<&GitStatus>,
+    now: DateTime<Utc>,
+) -> Vec<Line<'static>> {
     let mut lines = vec![
         detail_line("Branch", &slot.branch, Color::White),
         detail_line(
             "Status",
-            &format!("{:?}", slot.status),
+            &slot.status.to_string(),
             status_color(&slot.status),
         ),
         detail_line(
@@ -41,6 +48,89 @@ fn build_detail_lines(slot: &Slot, now: DateTime<Utc>) -> Vec<Line<'static>> {
         ),
     ];
 
+    if let Some(ref error) = slot.last_error {
+        lines.push(detail_line("Error", error, Color::Red));
+    }
+    if slot.creation_failed() {
+        lines.push(detail_line(
+            "Create",
+            "[R] retry from failed step · [D] discard",
+            Color::Yellow,
+        ));
+    }
+
+    match slot.rebase_status {
+        // Commits on the upstream that HEAD lacks: pushing has to overwrite them
+        RebaseStatus::None
+            if git
+                .and_then(|g| g.vs_upstream)
+                .is_some_and(|ab| ab.behind > 0) =>
+        {
+            lines.push(detail_line(
+                "Rebase",
+                "diverged from upstream — next push uses --force-with-lease",
+                Color::Yellow,
+            ));
+        }
+        RebaseStatus::None => {}
+        RebaseStatus::Rebasing => {
+            lines.push(detail_line("Rebase", "in progress...", Color::Yellow));
+        }
+        RebaseStatus::Conflicted => lines.push(detail_line(
+            "Rebase",
+            &format!(
+                "{} conflicted file(s) — [R] to resolve",
+                slot.conflicted_files.len()
+            ),
+            Color::Red,
+        )),
+    }
+
+    if !slot.problems.is_empty() {
+        lines.push(detail_line(
+            "Build",
+            &format!("{} — [E] to list", problems_summary(&slot.problems)),
+            problems_color(&slot.problems),
+        ));
+    }
+
+    if let Some(ref telemetry) = slot.telemetry {
+        lines.push(detail_line(
+            "Telemetry",
+            &telemetry_summary(telemetry),
+            telemetry_color(telemetry),
+        ));
+    }
+
+    if let Some(git) = git {
+        let summary = [git_sync_summary(git), git_change_summary(git)]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" · ");
+        lines.push(detail_line("Git", &summary, git_color(git)));
+
+        if let Some(ref commit) = git.last_commit {
+            let d = now.signed_duration_since(commit.time);
+            lines.push(detail_line(
+                "Commit",
+                &format!(
+                    "{} {} ({}, {} ago)",
+                    commit.hash,
+                    commit.subject,
+                    commit.author,
+                    format_duration(d)
+                ),
+                Color::White,
+            ));
+        }
+    }
+
+    if let Some(ref pr) = slot.pull_request {
+        lines.push(detail_line("PR", &pr_summary(pr), pr_color(pr)));
+        lines.push(detail_line("PR URL", &pr.url, Color::Cyan));
+    }
+
     if let Some(started) = slot.aspire_started_at {
         let d = now.signed_duration_since(started);
         lines.push(detail_line("Uptime", &format_duration(d), Color::Green));
@@ -89,6 +179,145 @@ fn build_detail_lines(slot: &Slot, now: DateTime<Utc>) -> Vec<Line<'static>> {
     lines
 }
 
+/// Ahead/behind counts vs upstream and base, e.g. `↑2 ↓0 · base ↑5 ↓1`.
+pub fn git_sync_summary(git: &GitStatus) -> String {
+    let mut parts = Vec::new();
+    match git.vs_upstream {
+        Some(ab) => parts.push(format!("↑{} ↓{}", ab.ahead, ab.behind)),
+        None => parts.push("no upstream".to_string()),
+    }
+    if let Some(ab) = git.vs_base {
+        parts.push(format!("base ↑{} ↓{}", ab.ahead, ab.behind));
+    }
+    parts.join(" · ")
+}
+
+/// Working tree summary, e.g. `2 staged, 1 modified` or `clean`.
+pub fn git_change_summary(git: &GitStatus) -> String {
+    if let Some(op) = git.operation {
+        let op = match op {
+            GitOperation::Rebase => "REBASING",
+            GitOperation::Merge => "MERGING",
+            GitOperation::CherryPick => "CHERRY-PICKING",
+        };
+        return if git.conflicted > 0 {
+            format!("{op}, {} conflicted", git.conflicted)
+        } else {
+            op.to_string()
+        };
+    }
+
+    let mut parts = Vec::new();
+    for (count, label) in [
+        (git.conflicted, "conflicted"),
+        (git.staged, "staged"),
+        (git.unstaged, "modified"),
+        (git.untracked, "untracked"),
+    ] {
+        if count > 0 {
+            parts.push(format!("{count} {label}"));
+        }
+    }
+    if parts.is_empty() {
+        "clean".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+pub fn git_color(git: &GitStatus) -> Color {
+    if git.has_conflicts() {
+        Color::Red
+    } else if git.is_dirty() {
+        Color::Yellow
+    } else {
+        Color::Green
+    }
+}
+
+/// Pull request state, e.g. `#42 open · approved · checks passing`.
+pub fn pr_summary(pr: &PullRequest) -> String {
+    let state = match pr.state {
+        PrState::Open => "open",
+        PrState::Draft => "draft",
+        PrState::Merged => "merged",
+        PrState::Closed => "closed",
+    };
+    let mut parts = vec![format!("#{} {state}", pr.number)];
+    if pr.state.is_open() {
+        match pr.review {
+            ReviewState::None => {}
+            ReviewState::ReviewRequired => parts.push("review required".into()),
+            ReviewState::ChangesRequested => parts.push("changes requested".into()),
+            ReviewState::Approved => parts.push("approved".into()),
+        }
+        match pr.checks {
+            CheckState::None => {}
+            CheckState::Pending => parts.push("checks pending".into()),
+            CheckState::Passing => parts.push("checks passing".into()),
+            CheckState::Failing => parts.push("checks failing".into()),
+        }
+    }
+    parts.join(" · ")
+}
+
+/// Build problem counts, e.g. `2 errors, 1 warning`.
+pub fn problems_summary(problems: &[Problem]) -> String {
+    let errors = problems.iter().filter(|p| p.is_error()).count();
+    let warnings = problems.len() - errors;
+    let count = |n: usize, what: &str| format!("{n} {what}{}", if n == 1 { "" } else { "s" });
+    match (errors, warnings) {
+        (0, w) => count(w, "warning"),
+        (e, 0) => count(e, "error"),
+        (e, w) => format!("{}, {}", count(e, "error"), count(w, "warning")),
+    }
+}
+
+pub fn problems_color(problems: &[Problem]) -> Color {
+    if problems.iter().any(Problem::is_error) {
+        Color::Red
+    } else {
+        Color::Yellow
+    }
+}
+
+/// OTLP receiver counts, e.g. `120 logs · 48 spans · 2 failed, last GET /orders`.
+pub fn telemetry_summary(telemetry: &TelemetrySummary) -> String {
+    let mut summary = format!("{} logs · {} spans", telemetry.logs, telemetry.spans);
+    if let Some(last) = telemetry.error_spans.last() {
+        summary.push_str(&format!(
+            " · {} failed, last {}",
+            telemetry.error_spans.len(),
+            last.name
+        ));
+    }
+    summary
+}
+
+pub fn telemetry_color(telemetry: &TelemetrySummary) -> Color {
+    if telemetry.error_spans.is_empty() {
+        Color::Green
+    } else {
+        Color::Red
+    }
+}
+
+pub fn pr_color(pr: &PullRequest) -> Color {
+    match pr.state {
+        PrState::Merged => Color::Magenta,
+        PrState::Closed => Color::DarkGray,
+        PrState::Open | PrState::Draft => {
+            if pr.checks == CheckState::Failing || pr.review == ReviewState::ChangesRequested {
+                Color::Red
+            } else if pr.checks == CheckState::Pending || pr.review == ReviewState::ReviewRequired {
+                Color::Yellow
+            } else {
+                Color::Green
+            }
+        }
+    }
+}
+
 fn format_duration(d: chrono::Duration) -> String {
     let total_secs = d.num_seconds().max(0);
     let hours = total_secs / 3600;
@@ -117,7 +346,7 @@ fn detail_line(label: &str, value: &str, color: Color) -> Line<'static> {
 fn status_color(status: &SlotStatus) -> Color {
     match status {
         SlotStatus::Running => Color::Green,
-        SlotStatus::Starting | SlotStatus::Stopping => Color::Yellow,
+        SlotStatus::Starting | SlotStatus::Stopping | SlotStatus::SettingUp { .. } => Color::Yellow,
         SlotStatus::Ready => Color::White,
         SlotStatus::Error => Color::Red,
         SlotStatus::Provisioning => Color::DarkGray,
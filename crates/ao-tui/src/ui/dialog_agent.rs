@@ -6,6 +6,7 @@ use ratatui::Frame;
 
 use crate::app::{App, SpawnAgentField};
 use crate::ui::layout::centered_rect;
+use crate::ui::text_area;
 
 pub fn render(f: &mut Frame, app: &App) {
     let slot_name = app
@@ -13,7 +14,7 @@ pub fn render(f: &mut Frame, app: &App) {
         .map(|s| s.name.clone())
         .unwrap_or_default();
 
-    let area = centered_rect(70, 50, f.area());
+    let area = centered_rect(70, 70, f.area());
     f.render_widget(Clear, area);
 
     let block = Block::default()
@@ -50,17 +51,12 @@ pub fn render(f: &mut Frame, app: &App) {
         Paragraph::new(Span::styled("Prompt:", Style::default().fg(Color::White))),
         chunks[0],
     );
-    f.render_widget(
-        Paragraph::new(Span::styled(
-            format!(" {}", app.agent_form.prompt),
-            prompt_style,
-        ))
-        .block(
-            Block::default()
-                .borders(Borders::BOTTOM)
-                .border_style(prompt_style),
-        ),
+    text_area::render(
+        f,
         chunks[1],
+        &app.agent_form.prompt,
+        prompt_style,
+        app.agent_form.focus == SpawnAgentField::Prompt,
     );
 
     // Allowed tools
@@ -75,10 +75,10 @@ pub fn render(f: &mut Frame, app: &App) {
     }
 
     // Help
-    let help_text = if progress.done {
-        " Press Esc to dismiss"
-    } else {
-        " Running..."
+    let help_text = match app.first_conflicted_failure() {
+        Some(name) if progress.done => format!(" Enter: resolve conflicts in {name}  Esc: dismiss"),
+        _ if progress.done => " Press Esc to dismiss".to_string(),
+        _ => " Running...".to_string(),
     };
     f.render_widget(
         Paragraph::new(Span::styled(
@@ -1,4 +1,4 @@
-use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
@@ -6,9 +6,10 @@ use ratatui::Frame;
 
 use crate::app::{App, CreateSlotField};
 use crate::ui::layout::centered_rect;
+use crate::ui::text_area;
 
 pub fn render(f: &mut Frame, app: &App) {
-    let area = centered_rect(60, 55, f.area());
+    let area = centered_rect(60, 80, f.area());
 
     // Clear the background
     f.render_widget(Clear, area);
@@ -21,20 +22,32 @@ pub fn render(f: &mut Frame, app: &App) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
+    let form = &app.create_form;
+
+    // Rows left for a completion list after the fixed rows and margin
+    const FIXED_ROWS: u16 = 16;
+    let room = inner.height.saturating_sub(FIXED_ROWS) as usize;
+
     // Determine how many candidate rows to show (0 to MAX_VISIBLE)
     const MAX_VISIBLE: usize = 6;
-    let show_candidates = app.create_form.focus == CreateSlotField::Source
-        && (!app.create_form.filtered_candidates.is_empty()
-            || (app.create_form.scan_loading && app.create_form.all_candidates.is_empty()));
+    let show_candidates = form.focus == CreateSlotField::Source
+        && (!form.filtered_candidates.is_empty()
+            || (form.scan_loading && form.all_candidates.is_empty()));
     let candidate_rows = if show_candidates {
-        if app.create_form.scan_loading && app.create_form.all_candidates.is_empty() {
+        if form.scan_loading && form.all_candidates.is_empty() {
             1 // "Scanning..." row
         } else {
-            app.create_form.filtered_candidates.len().min(MAX_VISIBLE) as u16
+            form.filtered_candidates.len().min(MAX_VISIBLE).min(room) as u16
         }
     } else {
         0
     };
+    let show_branches = form.focus == CreateSlotField::Branch && !form.filtered_branches.is_empty();
+    let branch_rows = if show_branches {
+        form.filtered_branches.len().min(MAX_VISIBLE).min(room) as u16
+    } else {
+        0
+    };
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -44,27 +57,36 @@ pub fn render(f: &mut Frame, app: &App) {
             Constraint::Length(1),              // source input
             Constraint::Length(candidate_rows), // candidate list (dynamic)
             Constraint::Length(1),              // blank
-            Constraint::Length(1),              // prompt label
-            Constraint::Min(3),                 // prompt input
+            Constraint::Length(1),              // name label
+            Constraint::Length(1),              // name input
+            Constraint::Length(1),              // blank
+            Constraint::Length(1),              // branch label
+            Constraint::Length(1),              // branch input
+            Constraint::Length(branch_rows),    // branch completions (dynamic)
+            Constraint::Length(1),              // new branch toggle
             Constraint::Length(1),              // blank
+            Constraint::Length(1),              // prompt label
+            Constraint::Min(2),                 // prompt input
             Constraint::Length(1),              // help text
         ])
         .split(inner);
 
-    let source_style = field_style(app.create_form.focus == CreateSlotField::Source);
-    let prompt_style = field_style(app.create_form.focus == CreateSlotField::Prompt);
+    let source_style = field_style(form.focus == CreateSlotField::Source);
+    let name_style = field_style(form.focus == CreateSlotField::Name);
+    let branch_style = field_style(form.focus == CreateSlotField::Branch);
+    let prompt_style = field_style(form.focus == CreateSlotField::Prompt);
 
     // Source label
     f.render_widget(
-        Paragraph::new(Span::styled(
+        Paragraph::new(label(
             "Source Repository:",
-            Style::default().fg(Color::White),
+            form.error_for(CreateSlotField::Source),
         )),
         chunks[0],
     );
 
     // Source input — render text directly (no Block border, which would consume the row)
-    let source_display = format!(" {}", app.create_form.source);
+    let source_display = format!(" {}", form.source);
     let underline_style = source_style.add_modifier(Modifier::UNDERLINED);
     f.render_widget(
         Paragraph::new(Span::styled(source_display, underline_style)),
@@ -73,7 +95,7 @@ pub fn render(f: &mut Frame, app: &App) {
 
     // Candidate list area
     if candidate_rows > 0 {
-        if app.create_form.scan_loading && app.create_form.all_candidates.is_empty() {
+        if form.scan_loading && form.all_candidates.is_empty() {
             f.render_widget(
                 Paragraph::new(Span::styled(
                     " Scanning...",
@@ -82,60 +104,69 @@ pub fn render(f: &mut Frame, app: &App) {
                 chunks[2],
             );
         } else {
-            let items: Vec<ListItem> = app
-                .create_form
+            let items: Vec<ListItem> = form
                 .filtered_candidates
                 .iter()
                 .enumerate()
                 .map(|(i, c)| {
-                    let is_selected = app.create_form.selected_candidate == Some(i);
-                    let prefix = if is_selected { " > " } else { "   " };
-                    let hint = c.location_hint();
-
-                    let line = Line::from(vec![
-                        Span::styled(
-                            prefix,
-                            if is_selected {
-                                Style::default()
-                                    .fg(Color::Yellow)
-                                    .add_modifier(Modifier::BOLD)
-                            } else {
-                                Style::default().fg(Color::White)
-                            },
-                        ),
-                        Span::styled(
-                            c.display_label(),
-                            if is_selected {
-                                Style::default()
-                                    .fg(Color::Yellow)
-                                    .add_modifier(Modifier::BOLD)
-                            } else {
-                                Style::default().fg(Color::White)
-                            },
-                        ),
-                        Span::styled("  ", Style::default()),
-                        Span::styled(hint, Style::default().fg(Color::DarkGray)),
-                    ]);
-
-                    let style = if is_selected {
-                        Style::default().bg(Color::DarkGray)
-                    } else {
-                        Style::default()
-                    };
-
-                    ListItem::new(line).style(style)
+                    completion_item(
+                        form.selected_candidate == Some(i),
+                        c.display_label(),
+                        c.location_hint(),
+                    )
                 })
                 .collect();
+            render_completions(f, chunks[2], items, form.selected_candidate);
+        }
+    }
 
-            let list = List::new(items)
-                .highlight_style(Style::default()) // styling handled per-item above
-                .highlight_symbol("");
+    // Name
+    f.render_widget(
+        Paragraph::new(label("Name:", form.error_for(CreateSlotField::Name))),
+        chunks[4],
+    );
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            format!(" {}", form.name),
+            name_style.add_modifier(Modifier::UNDERLINED),
+        )),
+        chunks[5],
+    );
 
-            let mut list_state =
-                ListState::default().with_selected(app.create_form.selected_candidate);
-            f.render_stateful_widget(list, chunks[2], &mut list_state);
-        }
+    // Branch
+    f.render_widget(
+        Paragraph::new(label("Branch:", form.error_for(CreateSlotField::Branch))),
+        chunks[7],
+    );
+    let branch_input = if form.branch.is_empty() && !form.new_branch {
+        Span::styled(" (default branch)", Style::default().fg(Color::DarkGray))
+    } else {
+        Span::styled(
+            format!(" {}", form.branch),
+            branch_style.add_modifier(Modifier::UNDERLINED),
+        )
+    };
+    f.render_widget(Paragraph::new(branch_input), chunks[8]);
+    if branch_rows > 0 {
+        let items: Vec<ListItem> = form
+            .filtered_branches
+            .iter()
+            .enumerate()
+            .map(|(i, b)| completion_item(form.selected_branch == Some(i), b, ""))
+            .collect();
+        render_completions(f, chunks[9], items, form.selected_branch);
     }
+    let checkbox = if form.new_branch { "[x]" } else { "[ ]" };
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!(" {checkbox} New branch from base"),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled("  Ctrl+N", Style::default().fg(Color::DarkGray)),
+        ])),
+        chunks[10],
+    );
 
     // Prompt label
     f.render_widget(
@@ -143,22 +174,20 @@ pub fn render(f: &mut Frame, app: &App) {
             "Prompt (optional):",
             Style::default().fg(Color::White),
         )),
-        chunks[4],
+        chunks[12],
     );
 
     // Prompt input
-    let prompt_text = format!(" {}", app.create_form.prompt);
-    f.render_widget(
-        Paragraph::new(Span::styled(prompt_text, prompt_style)).block(
-            Block::default()
-                .borders(Borders::BOTTOM)
-                .border_style(prompt_style),
-        ),
-        chunks[5],
+    text_area::render(
+        f,
+        chunks[13],
+        &form.prompt,
+        prompt_style,
+        form.focus == CreateSlotField::Prompt,
     );
 
     // Help text
-    let help = if show_candidates && !app.create_form.filtered_candidates.is_empty() {
+    let help = if (show_candidates && !form.filtered_candidates.is_empty()) || show_branches {
         Line::from(vec![
             Span::styled(" Up/Down", Style::default().fg(Color::Yellow)),
             Span::styled(" navigate  ", Style::default().fg(Color::DarkGray)),
@@ -177,7 +206,56 @@ pub fn render(f: &mut Frame, app: &App) {
             Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
         ])
     };
-    f.render_widget(Paragraph::new(help), chunks[7]);
+    f.render_widget(Paragraph::new(help), chunks[14]);
+}
+
+/// A field label, followed by the field's validation error if it has one.
+fn label(text: &str, error: Option<&str>) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        text.to_string(),
+        Style::default().fg(Color::White),
+    )];
+    if let Some(error) = error {
+        spans.push(Span::styled(
+            format!("  {error}"),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn completion_item<'a>(is_selected: bool, label: &'a str, hint: &str) -> ListItem<'a> {
+    let text_style = if is_selected {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    let prefix = if is_selected { " > " } else { "   " };
+
+    let line = Line::from(vec![
+        Span::styled(prefix, text_style),
+        Span::styled(label, text_style),
+        Span::styled("  ", Style::default()),
+        Span::styled(hint.to_string(), Style::default().fg(Color::DarkGray)),
+    ]);
+
+    let style = if is_selected {
+        Style::default().bg(Color::DarkGray)
+    } else {
+        Style::default()
+    };
+    ListItem::new(line).style(style)
+}
+
+fn render_completions(f: &mut Frame, area: Rect, items: Vec<ListItem>, selected: Option<usize>) {
+    let list = List::new(items)
+        .highlight_style(Style::default()) // styling handled per-item above
+        .highlight_symbol("");
+
+    let mut list_state = ListState::default().with_selected(selected);
+    f.render_stateful_widget(list, area, &mut list_state);
 }
 
 fn field_style(focused: bool) -> Style {
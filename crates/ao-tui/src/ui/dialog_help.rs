@@ -21,10 +21,14 @@ pub fn render(f: &mut Frame) {
         key_line("K", "Kill (stop) Aspire stack"),
         key_line("D", "Destroy slot"),
         key_line("A", "Spawn agent"),
-        key_line("R", "Rebase branch"),
+        key_line("R", "Rebase / resolve conflicts / retry create"),
         key_line("G", "Git push"),
+        key_line("O", "Open / refresh pull request"),
         key_line("P", "Open terminal for agent"),
         key_line("L", "Toggle log source"),
+        key_line("Shift+T", "Log times: clock / relative / off"),
+        key_line("V", "View diff vs base branch"),
+        key_line("E", "List build problems"),
         key_line("M", "Open multiplexed log"),
         key_line("B", "Open blueprints"),
         key_line("Tab", "Toggle dashboard view"),
@@ -46,10 +50,32 @@ pub fn render(f: &mut Frame) {
         section_header("Multiplex Log"),
         key_line("1-6", "Toggle slot visibility"),
         key_line("Tab", "Cycle source filter"),
+        key_line("V", "Cycle level: all / warn+ / error"),
         key_line("/", "Enter search"),
         key_line("E / W", "Next / prev error"),
         key_line("F", "Re-engage auto-follow"),
         key_line("C", "Clear search"),
+        key_line("H", "Search log history"),
+        key_line("T", "Cycle history time range"),
+        Line::from(""),
+        section_header("Diff Viewer"),
+        key_line("j / k", "Scroll"),
+        key_line("Tab / S-Tab", "Next / prev file"),
+        key_line("W", "Toggle base / working tree"),
+        key_line("R", "Refresh"),
+        Line::from(""),
+        section_header("Rebase Conflict"),
+        key_line("C / S", "Continue / skip commit"),
+        key_line("X", "Abort rebase"),
+        key_line("A", "Ask agent to resolve"),
+        Line::from(""),
+        section_header("Build Problems"),
+        key_line("j / k", "Scroll"),
+        key_line("A", "Ask agent to fix"),
+        Line::from(""),
+        section_header("Reconcile (startup)"),
+        key_line("A / D / F", "Adopt / remove / fix selected"),
+        key_line("Esc", "Ignore remaining"),
         Line::from(""),
         section_header("Dialogs"),
         key_line("Tab", "Next field"),
@@ -57,6 +83,13 @@ pub fn render(f: &mut Frame) {
         key_line("Enter", "Submit / confirm"),
         key_line("Esc", "Cancel / close"),
         key_line("Up/Down", "Navigate list"),
+        key_line("Ctrl+N", "New branch from base (New Slot)"),
+        Line::from(""),
+        section_header("Prompt Fields"),
+        key_line("Alt+Enter", "Line break (also Ctrl+J)"),
+        key_line("Ctrl+W", "Delete word"),
+        key_line("Ctrl+E", "Edit in $EDITOR"),
+        key_line("Ctrl+P", "Insert from prompt library"),
     ];
 
     let paragraph = Paragraph::new(lines)
@@ -0,0 +1,109 @@
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use ao_core::models::Problem;
+
+use crate::app::App;
+use crate::ui::detail_panel::{problems_color, problems_summary};
+use crate::ui::layout::centered_rect;
+
+/// Render the build problems of a slot, starting `scroll` problems down.
+pub fn render(f: &mut Frame, app: &App, slot_name: &str, scroll: usize) {
+    let area = centered_rect(80, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let slot = app.slots.iter().find(|s| s.name == slot_name);
+    let problems = slot.map_or(&[][..], |s| s.problems.as_slice());
+    let clone_path = slot.map_or("", |s| s.clone_path.as_str());
+
+    let block = Block::default()
+        .title(format!(" Build Problems — {slot_name} "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(problems_color(problems)));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2), // summary
+            Constraint::Min(2),    // problem list
+            Constraint::Length(1), // actions
+        ])
+        .split(inner);
+
+    let summary = if problems.is_empty() {
+        " The last build reported no problems.".to_string()
+    } else {
+        format!(" The last build reported {}.", problems_summary(problems))
+    };
+    f.render_widget(
+        Paragraph::new(Span::styled(summary, Style::default().fg(Color::White))),
+        chunks[0],
+    );
+
+    let lines: Vec<Line> = problems
+        .iter()
+        .skip(scroll)
+        .flat_map(|p| problem_lines(p, clone_path))
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[1]);
+
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            action("A", "gent fix"),
+            action("↑↓", " scroll"),
+            action("Esc", " close"),
+        ])),
+        chunks[2],
+    );
+}
+
+/// A problem's location and code, with its message on the line below.
+fn problem_lines(problem: &Problem, clone_path: &str) -> [Line<'static>; 2] {
+    let (badge, color) = if problem.is_error() {
+        ("  E ", Color::Red)
+    } else {
+        ("  W ", Color::Yellow)
+    };
+    // Paths inside the clone are shown relative to it
+    let location = problem.location().map(|l| {
+        l.strip_prefix(clone_path)
+            .map(|rest| rest.trim_start_matches('/').to_string())
+            .unwrap_or(l)
+    });
+    let mut header = vec![
+        Span::styled(badge, Style::default().fg(color)),
+        Span::styled(
+            problem.code.clone(),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if let Some(location) = location {
+        header.push(Span::styled(
+            format!(" {location}"),
+            Style::default().fg(Color::White),
+        ));
+    }
+    [
+        Line::from(header),
+        Line::from(Span::styled(
+            format!("      {}", problem.message),
+            Style::default().fg(Color::Gray),
+        )),
+    ]
+}
+
+fn action(key: &str, label: &str) -> Span<'static> {
+    Span::styled(
+        format!(" [{key}]{label}"),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+}
@@ -0,0 +1,149 @@
+use ratatui::layout::{Constraint, Direction, Layout, Position};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::ui::layout::centered_rect;
+
+/// Render the prompt library picker over the dialog it inserts into.
+pub fn render(f: &mut Frame, app: &App) {
+    let picker = &app.prompt_picker;
+    let area = centered_rect(60, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Prompt Library ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(1),      // filter
+            Constraint::Length(1),      // blank
+            Constraint::Percentage(50), // list
+            Constraint::Min(2),         // preview
+            Constraint::Length(1),      // actions
+        ])
+        .split(inner);
+
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(" Filter: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(picker.query.as_str(), Style::default().fg(Color::White)),
+        ])),
+        chunks[0],
+    );
+    f.set_cursor_position(Position::new(
+        chunks[0].x + 9 + picker.query.chars().count() as u16,
+        chunks[0].y,
+    ));
+
+    let empty_message = if picker.loading {
+        Some(" Loading prompts...".to_string())
+    } else if picker.prompts.is_empty() {
+        Some(" No saved prompts. Add .md or .yaml files to .slots/prompts/".to_string())
+    } else if picker.filtered.is_empty() {
+        Some(" No prompts match the filter".to_string())
+    } else {
+        None
+    };
+
+    if let Some(message) = empty_message {
+        f.render_widget(
+            Paragraph::new(Span::styled(message, Style::default().fg(Color::DarkGray))),
+            chunks[2],
+        );
+    } else {
+        let name_width = picker
+            .filtered
+            .iter()
+            .map(|&i| picker.prompts[i].name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let items: Vec<ListItem> = picker
+            .filtered
+            .iter()
+            .map(|&i| {
+                let prompt = &picker.prompts[i];
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("  {:<name_width$}  ", prompt.name),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        prompt.description.clone().unwrap_or_default(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Rgb(0x1A, 0x3A, 0x5C))
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ");
+        let mut state = ListState::default();
+        state.select(Some(picker.selected));
+        f.render_stateful_widget(list, chunks[2], &mut state);
+    }
+
+    if let Some(prompt) = picker.selected_prompt() {
+        let mut lines: Vec<Line> = prompt
+            .body
+            .lines()
+            .map(|l| Line::styled(format!(" {l}"), Style::default().fg(Color::Gray)))
+            .collect();
+        let mut defaults = Vec::new();
+        if let Some(tools) = &prompt.allowed_tools {
+            defaults.push(format!("tools: {tools}"));
+        }
+        if let Some(turns) = prompt.max_turns {
+            defaults.push(format!("max turns: {turns}"));
+        }
+        if !defaults.is_empty() {
+            lines.insert(
+                0,
+                Line::styled(
+                    format!(" {}", defaults.join("  ")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            );
+        }
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .borders(Borders::TOP)
+                        .border_style(Style::default().fg(Color::DarkGray)),
+                )
+                .wrap(Wrap { trim: false }),
+            chunks[3],
+        );
+    }
+
+    let actions = Line::from(vec![
+        action("Enter", " insert"),
+        action("\u{2191}\u{2193}", " select"),
+        action("Esc", " back"),
+    ]);
+    f.render_widget(Paragraph::new(actions), chunks[4]);
+}
+
+fn action(key: &str, label: &str) -> Span<'static> {
+    Span::styled(
+        format!(" [{key}]{label}"),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+}
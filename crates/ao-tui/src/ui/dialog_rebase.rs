@@ -0,0 +1,81 @@
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::ui::layout::centered_rect;
+
+/// Render the rebase conflict dialog for a slot.
+pub fn render_conflict(f: &mut Frame, app: &App, slot_name: &str) {
+    let area = centered_rect(60, 50, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Rebase Conflict — {slot_name} "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2), // summary
+            Constraint::Min(2),    // file list
+            Constraint::Length(1), // actions
+        ])
+        .split(inner);
+
+    let slot = app.slots.iter().find(|s| s.name == slot_name);
+    let (base, files) = match slot {
+        Some(s) => (s.base_branch.as_str(), s.conflicted_files.as_slice()),
+        None => ("?", &[][..]),
+    };
+
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            format!(
+                " Rebase onto origin/{base} stopped with {} conflicted file(s).",
+                files.len()
+            ),
+            Style::default().fg(Color::White),
+        ))
+        .wrap(Wrap { trim: false }),
+        chunks[0],
+    );
+
+    let lines: Vec<Line> = files
+        .iter()
+        .map(|file| {
+            Line::from(vec![
+                Span::styled("  U ", Style::default().fg(Color::Red)),
+                Span::styled(file.clone(), Style::default().fg(Color::White)),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[1]);
+
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            action("C", "ontinue"),
+            action("S", "kip"),
+            action("X", "abort"),
+            action("A", "gent resolve"),
+            action("Esc", "close"),
+        ])),
+        chunks[2],
+    );
+}
+
+fn action(key: &str, label: &str) -> Span<'static> {
+    Span::styled(
+        format!(" [{key}]{label}"),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+}
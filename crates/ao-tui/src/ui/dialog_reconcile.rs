@@ -0,0 +1,105 @@
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use ao_core::models::ReconcileAction;
+
+use crate::app::App;
+use crate::ui::layout::centered_rect;
+
+/// Render the startup reconcile dialog listing state/filesystem mismatches.
+pub fn render(f: &mut Frame, app: &App) {
+    let area = centered_rect(70, 50, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Reconcile Slots ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2), // summary
+            Constraint::Min(3),    // list
+            Constraint::Length(1), // actions
+        ])
+        .split(inner);
+
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            format!(
+                " {} difference(s) between saved state and .slots/",
+                app.reconcile.items.len()
+            ),
+            Style::default().fg(Color::White),
+        )),
+        chunks[0],
+    );
+
+    let name_width = app
+        .reconcile
+        .items
+        .iter()
+        .map(|d| d.name().len())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = app
+        .reconcile
+        .items
+        .iter()
+        .map(|d| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("  {:<name_width$}  ", d.name()),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(d.summary(), Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::Rgb(0x1A, 0x3A, 0x5C))
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+    let mut state = ListState::default();
+    if !app.reconcile.items.is_empty() {
+        state.select(Some(app.reconcile.selected));
+    }
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    // Only the actions that apply to the selected item
+    let mut actions = Vec::new();
+    if let Some(selected) = app.reconcile.items.get(app.reconcile.selected) {
+        for (key, kind) in [
+            ("A", ReconcileAction::Adopt),
+            ("D", ReconcileAction::Remove),
+            ("F", ReconcileAction::Fix),
+        ] {
+            if let Some(label) = selected.action_label(kind) {
+                actions.push(action(key, &format!(" {label}")));
+            }
+        }
+    }
+    actions.push(action("Esc", " ignore"));
+    f.render_widget(Paragraph::new(Line::from(actions)), chunks[2]);
+}
+
+fn action(key: &str, label: &str) -> Span<'static> {
+    Span::styled(
+        format!(" [{key}]{label}"),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+}
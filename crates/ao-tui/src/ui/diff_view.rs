@@ -0,0 +1,263 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+use ao_core::models::{DiffLineKind, DiffTarget, FileChange, FileDiff};
+
+use crate::app::{App, DiffViewState};
+
+/// Render the full-screen diff viewer (header + file list + hunks).
+pub fn render(f: &mut Frame, area: Rect, app: &App) {
+    let Some(ref view) = app.diff_view else {
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(5)])
+        .split(area);
+    render_header(f, chunks[0], view);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(chunks[1]);
+    render_file_list(f, body[0], view);
+    render_hunks(f, body[1], view);
+}
+
+fn render_header(f: &mut Frame, area: Rect, view: &DiffViewState) {
+    let target = match view.target {
+        DiffTarget::Base => "base branch",
+        DiffTarget::WorkingTree => "working tree",
+    };
+    let additions: usize = view.files.iter().map(FileDiff::additions).sum();
+    let deletions: usize = view.files.iter().map(FileDiff::deletions).sum();
+
+    let mut spans = vec![
+        Span::styled(
+            format!("  {} ", view.slot_name),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("vs {target}  "),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            format!("{} files ", view.files.len()),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(format!("+{additions} "), Style::default().fg(Color::Green)),
+        Span::styled(format!("-{deletions}"), Style::default().fg(Color::Red)),
+    ];
+    if view.loading {
+        spans.push(Span::styled(
+            "  loading...",
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn render_file_list(f: &mut Frame, area: Rect, view: &DiffViewState) {
+    let block = Block::default()
+        .title(" Files ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let height = block.inner(area).height as usize;
+
+    if view.files.is_empty() {
+        let msg = if view.loading { "" } else { " No changes" };
+        let empty =
+            Paragraph::new(Span::styled(msg, Style::default().fg(Color::DarkGray))).block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    // Keep the selected file visible
+    let offset = view.selected_file.saturating_sub(height.saturating_sub(1));
+    let lines: Vec<Line> = view
+        .files
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .map(|(i, file)| {
+            let selected = i == view.selected_file;
+            let (letter, color) = change_display(file.change);
+            let name_style = if selected {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(vec![
+                Span::styled(if selected { ">" } else { " " }, name_style),
+                Span::styled(format!("{letter} "), Style::default().fg(color)),
+                Span::styled(file.path.clone(), name_style),
+                Span::styled(
+                    format!(" +{}", file.additions()),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::styled(
+                    format!(" -{}", file.deletions()),
+                    Style::default().fg(Color::Red),
+                ),
+            ])
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_hunks(f: &mut Frame, area: Rect, view: &DiffViewState) {
+    let Some(file) = view.selected() else {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+        f.render_widget(block, area);
+        return;
+    };
+
+    let title = match file.old_path {
+        Some(ref old) => format!(" {old} → {} ", file.path),
+        None => format!(" {} ", file.path),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let height = block.inner(area).height as usize;
+
+    if file.binary || file.hunks.is_empty() {
+        let msg = if file.binary {
+            " Binary file"
+        } else {
+            " No content changes"
+        };
+        let empty =
+            Paragraph::new(Span::styled(msg, Style::default().fg(Color::DarkGray))).block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let comment = comment_prefix(&file.path);
+    let mut rows: Vec<Line> = Vec::with_capacity(view.line_count());
+    for hunk in &file.hunks {
+        rows.push(Line::from(Span::styled(
+            hunk.header.clone(),
+            Style::default().fg(Color::Cyan),
+        )));
+        for line in &hunk.lines {
+            let (sign, color) = match line.kind {
+                DiffLineKind::Added => ('+', Color::Green),
+                DiffLineKind::Removed => ('-', Color::Red),
+                DiffLineKind::Context => (' ', Color::White),
+            };
+            let lineno = |n: Option<u32>| {
+                n.map(|n| format!("{n:>4}"))
+                    .unwrap_or_else(|| "    ".into())
+            };
+            let mut spans = vec![
+                Span::styled(
+                    format!("{} {} ", lineno(line.old_lineno), lineno(line.new_lineno)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(format!("{sign} "), Style::default().fg(color)),
+            ];
+            spans.extend(highlight(&line.content, comment, color));
+            rows.push(Line::from(spans));
+        }
+    }
+
+    let scroll = view.scroll.min(rows.len().saturating_sub(height));
+    let visible: Vec<Line> = rows.into_iter().skip(scroll).take(height).collect();
+    f.render_widget(Paragraph::new(visible).block(block), area);
+}
+
+fn change_display(change: FileChange) -> (char, Color) {
+    match change {
+        FileChange::Added => ('A', Color::Green),
+        FileChange::Modified => ('M', Color::Yellow),
+        FileChange::Deleted => ('D', Color::Red),
+        FileChange::Renamed => ('R', Color::Cyan),
+    }
+}
+
+/// Line-comment marker for the file's language, if known.
+fn comment_prefix(path: &str) -> Option<&'static str> {
+    let ext = path.rsplit_once('.').map(|(_, e)| e)?;
+    match ext {
+        "rs" | "cs" | "ts" | "tsx" | "js" | "jsx" | "go" | "java" | "kt" | "swift" | "c" | "h"
+        | "cpp" | "hpp" | "scss" => Some("//"),
+        "py" | "sh" | "bash" | "ps1" | "yaml" | "yml" | "toml" | "rb" => Some("#"),
+        "sql" | "lua" => Some("--"),
+        _ => None,
+    }
+}
+
+/// Split a code line into spans, dimming comments and brightening string literals
+/// while keeping the add/remove/context colour for everything else.
+fn highlight(content: &str, comment: Option<&str>, color: Color) -> Vec<Span<'static>> {
+    let code = Style::default().fg(color);
+    let string = Style::default().fg(string_color(color));
+    let comment_style = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::ITALIC);
+
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut in_string: Option<char> = None;
+    let mut chars = content.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match in_string {
+            Some(quote) => {
+                current.push(c);
+                if c == '\\' {
+                    if let Some((_, escaped)) = chars.next() {
+                        current.push(escaped);
+                    }
+                } else if c == quote {
+                    spans.push(Span::styled(std::mem::take(&mut current), string));
+                    in_string = None;
+                }
+            }
+            None => {
+                if comment.is_some_and(|p| content[i..].starts_with(p)) {
+                    if !current.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut current), code));
+                    }
+                    spans.push(Span::styled(content[i..].to_string(), comment_style));
+                    return spans;
+                }
+                if c == '"' || c == '`' {
+                    if !current.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut current), code));
+                    }
+                    in_string = Some(c);
+                }
+                current.push(c);
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        let style = if in_string.is_some() { string } else { code };
+        spans.push(Span::styled(current, style));
+    }
+    spans
+}
+
+fn string_color(color: Color) -> Color {
+    match color {
+        Color::Green => Color::LightGreen,
+        Color::Red => Color::LightRed,
+        _ => Color::Yellow,
+    }
+}
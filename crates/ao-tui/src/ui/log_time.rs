@@ -0,0 +1,57 @@
+use chrono::{DateTime, Local, Utc};
+use ratatui::style::{Color, Style};
+use ratatui::text::Span;
+
+use crate::app::TimeDisplay;
+
+/// The time column of a log line, or `None` when times are hidden. Lines
+/// without a time get a blank column so the text stays aligned.
+pub fn column(
+    timestamp: Option<DateTime<Utc>>,
+    display: TimeDisplay,
+    now: DateTime<Utc>,
+) -> Option<Span<'static>> {
+    let text = match (display, timestamp) {
+        (TimeDisplay::Hidden, _) => return None,
+        (TimeDisplay::Clock, Some(t)) => t.with_timezone(&Local).format("%H:%M:%S").to_string(),
+        (TimeDisplay::Clock, None) => " ".repeat(8),
+        (TimeDisplay::Relative, Some(t)) => format!("{:>8}", relative(t, now)),
+        (TimeDisplay::Relative, None) => " ".repeat(8),
+    };
+    Some(styled(text))
+}
+
+/// The time column of a history result. History always shows times, with
+/// the date since results span days.
+pub fn history_column(
+    timestamp: DateTime<Utc>,
+    display: TimeDisplay,
+    now: DateTime<Utc>,
+) -> Span<'static> {
+    let text = match display {
+        TimeDisplay::Relative => format!("{:>8}", relative(timestamp, now)),
+        TimeDisplay::Hidden | TimeDisplay::Clock => timestamp
+            .with_timezone(&Local)
+            .format("%m-%d %H:%M:%S")
+            .to_string(),
+    };
+    styled(text)
+}
+
+/// Time elapsed since `timestamp` in its largest whole unit, e.g. `5m ago`.
+fn relative(timestamp: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - timestamp).num_seconds().max(0);
+    if secs < 60 {
+        format!("{secs}s ago")
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 86400 {
+        format!("{}h ago", secs / 3600)
+    } else {
+        format!("{}d ago", secs / 86400)
+    }
+}
+
+fn styled(text: String) -> Span<'static> {
+    Span::styled(format!("{text} "), Style::default().fg(Color::DarkGray))
+}
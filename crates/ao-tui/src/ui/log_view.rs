@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span};
@@ -5,8 +6,9 @@ use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use ratatui::Frame;
 
 use crate::app::{App, LogSource};
+use crate::ui::log_time;
 
-pub fn render(f: &mut Frame, area: Rect, app: &App) {
+pub fn render(f: &mut Frame, area: Rect, app: &App, now: DateTime<Utc>) {
     let source_label = match app.log_source {
         LogSource::Agent => "Agent Log",
         LogSource::Aspire => "Aspire Log",
@@ -42,7 +44,16 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         .iter()
         .skip(scroll)
         .take(inner_height)
-        .map(|l| Line::from(Span::styled(l.clone(), Style::default().fg(Color::White))))
+        .map(|l| {
+            let mut spans: Vec<Span> = log_time::column(l.timestamp, app.log_time, now)
+                .into_iter()
+                .collect();
+            spans.push(Span::styled(
+                l.text.as_str(),
+                Style::default().fg(Color::White),
+            ));
+            Line::from(spans)
+        })
         .collect();
 
     let paragraph = Paragraph::new(lines)
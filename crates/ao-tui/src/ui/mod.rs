@@ -6,18 +6,25 @@ pub mod dialog_blueprint;
 pub mod dialog_confirm;
 pub mod dialog_create;
 pub mod dialog_help;
+pub mod dialog_problems;
+pub mod dialog_prompt_picker;
+pub mod dialog_rebase;
+pub mod dialog_reconcile;
+pub mod diff_view;
 pub mod layout;
+pub mod log_time;
 pub mod log_view;
 pub mod multiplex_filter_bar;
 pub mod multiplex_log;
 pub mod slot_table;
 pub mod status_bar;
 pub mod terminal_view;
+pub mod text_area;
 
 use chrono::{DateTime, Utc};
 use ratatui::Frame;
 
-use crate::app::{App, Mode, ViewMode};
+use crate::app::{App, Mode, PromptField, ViewMode};
 
 /// Top-level render dispatch. Pass `now` to pin timestamps for deterministic output,
 /// or `None` to use the current time.
@@ -34,7 +41,10 @@ pub fn render(f: &mut Frame, app: &App, now: Option<DateTime<Utc>>) {
             terminal_view::render(f, chunks[1], app);
         }
         Mode::MultiplexLog => {
-            multiplex_log::render(f, chunks[1], app);
+            multiplex_log::render(f, chunks[1], app, now);
+        }
+        Mode::DiffView => {
+            diff_view::render(f, chunks[1], app);
         }
         _ => match app.view {
             ViewMode::Dashboard => {
@@ -55,7 +65,7 @@ pub fn render(f: &mut Frame, app: &App, now: Option<DateTime<Utc>>) {
                 if has_terminal {
                     terminal_view::render_embedded(f, right_chunks[1], app);
                 } else {
-                    log_view::render(f, right_chunks[1], app);
+                    log_view::render(f, right_chunks[1], app, now);
                 }
             }
         },
@@ -74,6 +84,20 @@ pub fn render(f: &mut Frame, app: &App, now: Option<DateTime<Utc>>) {
         Mode::BlueprintListDialog => dialog_blueprint::render_list(f, app),
         Mode::BlueprintSaveDialog => dialog_blueprint::render_save(f, app),
         Mode::BatchProgress => dialog_batch_progress::render(f, app),
-        Mode::SlotList | Mode::MultiplexLog | Mode::Terminal => {}
+        Mode::RebaseConflictDialog { slot_name } => {
+            dialog_rebase::render_conflict(f, app, slot_name)
+        }
+        Mode::ProblemsDialog { slot_name, scroll } => {
+            dialog_problems::render(f, app, slot_name, *scroll)
+        }
+        Mode::ReconcileDialog => dialog_reconcile::render(f, app),
+        Mode::PromptPickerDialog => {
+            match app.prompt_picker.target {
+                PromptField::CreateSlot => dialog_create::render(f, app),
+                PromptField::SpawnAgent => dialog_agent::render(f, app),
+            }
+            dialog_prompt_picker::render(f, app);
+        }
+        Mode::SlotList | Mode::MultiplexLog | Mode::Terminal | Mode::DiffView => {}
     }
 }
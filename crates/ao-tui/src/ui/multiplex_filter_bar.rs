@@ -6,7 +6,7 @@ use ratatui::Frame;
 
 use ao_core::services::log_tailer::LogSource;
 
-use crate::app::App;
+use crate::app::{App, Severity};
 
 /// High-contrast color palette for slot prefixes.
 pub const SLOT_COLORS: [Color; 8] = [
@@ -56,15 +56,27 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
     row1_spans.push(Span::styled(" | ", Style::default().fg(Color::DarkGray)));
 
     let src_label = match app.multiplex_filter.source_filter {
-        None => "Both",
+        None => "All",
         Some(LogSource::Agent) => "Agent",
         Some(LogSource::Aspire) => "Aspire",
+        Some(LogSource::Telemetry) => "Telemetry",
     };
     row1_spans.push(Span::styled(
         format!("Src:{src_label}"),
         Style::default().fg(Color::White),
     ));
 
+    let level_label = match app.multiplex_filter.min_severity {
+        None => "All",
+        Some(Severity::Error) => "Error",
+        Some(_) => "Warn+",
+    };
+    row1_spans.push(Span::styled(" | ", Style::default().fg(Color::DarkGray)));
+    row1_spans.push(Span::styled(
+        format!("Lvl:{level_label}"),
+        Style::default().fg(Color::White),
+    ));
+
     if !app.multiplex_filter.search_text.is_empty() || app.multiplex_filter.search_input_active {
         row1_spans.push(Span::styled(" | ", Style::default().fg(Color::DarkGray)));
         let search_style = if app.multiplex_filter.search_input_active {
@@ -90,20 +102,32 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
     let row1 = Line::from(row1_spans);
 
     // Row 2: keybinding hints
-    let row2 = Line::from(vec![
-        Span::styled(" 1-6", Style::default().fg(Color::Yellow)),
-        Span::styled(" toggle  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("Tab", Style::default().fg(Color::Yellow)),
-        Span::styled(" source  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("/", Style::default().fg(Color::Yellow)),
-        Span::styled(" search  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("E/W", Style::default().fg(Color::Yellow)),
-        Span::styled(" err  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("F", Style::default().fg(Color::Yellow)),
-        Span::styled(" follow  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("M/Esc", Style::default().fg(Color::Yellow)),
-        Span::styled(" back", Style::default().fg(Color::DarkGray)),
-    ]);
+    let hints: &[(&str, &str)] = if app.log_history.is_some() {
+        &[
+            ("1-6", " toggle  "),
+            ("Tab", " source  "),
+            ("/", " search  "),
+            ("T", " range  "),
+            ("F", " newest  "),
+            ("H/Esc", " live"),
+        ]
+    } else {
+        &[
+            ("1-6", " toggle  "),
+            ("Tab", " source  "),
+            ("/", " search  "),
+            ("E/W", " err  "),
+            ("F", " follow  "),
+            ("H", " history  "),
+            ("M/Esc", " back"),
+        ]
+    };
+    let mut row2_spans = vec![Span::raw(" ")];
+    for (key, label) in hints {
+        row2_spans.push(Span::styled(*key, Style::default().fg(Color::Yellow)));
+        row2_spans.push(Span::styled(*label, Style::default().fg(Color::DarkGray)));
+    }
+    let row2 = Line::from(row2_spans);
 
     let paragraph = Paragraph::new(vec![row1, row2]);
     f.render_widget(paragraph, area);
@@ -6,11 +6,14 @@ use ratatui::Frame;
 
 use ao_core::services::log_tailer::LogSource;
 
-use crate::app::{App, Severity};
+use chrono::{DateTime, Utc};
+
+use crate::app::{App, LogHistoryState, Severity};
+use crate::ui::log_time;
 use crate::ui::multiplex_filter_bar::SLOT_COLORS;
 
 /// Render the full multiplex log panel (filter bar + log lines).
-pub fn render(f: &mut Frame, area: Rect, app: &App) {
+pub fn render(f: &mut Frame, area: Rect, app: &App, now: DateTime<Utc>) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -20,10 +23,15 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         .split(area);
 
     super::multiplex_filter_bar::render(f, chunks[0], app);
-    render_log_content(f, chunks[1], app);
+    render_log_content(f, chunks[1], app, now);
 }
 
-fn render_log_content(f: &mut Frame, area: Rect, app: &App) {
+fn render_log_content(f: &mut Frame, area: Rect, app: &App, now: DateTime<Utc>) {
+    if let Some(history) = &app.log_history {
+        render_history(f, area, app, history, now);
+        return;
+    }
+
     let block = Block::default()
         .title(" Multiplexed Log ")
         .borders(Borders::ALL)
@@ -67,73 +75,22 @@ fn render_log_content(f: &mut Frame, area: Rect, app: &App) {
         .take(inner_height)
         .collect();
 
-    let max_name_len = 12;
-
     let lines: Vec<Line> = visible_indices
         .iter()
         .map(|&idx| {
             let entry = &app.log_buffer.entries[idx];
             let color = SLOT_COLORS[entry.color_index as usize % 8];
-
-            let source_char = match entry.source {
-                LogSource::Agent => "A",
-                LogSource::Aspire => "S",
-            };
-
-            let name_display = if entry.slot_name.len() > max_name_len {
-                format!(
-                    "{:>width$}",
-                    &entry.slot_name[..max_name_len],
-                    width = max_name_len
-                )
-            } else {
-                format!("{:>width$}", entry.slot_name, width = max_name_len)
-            };
-
-            let text_color = match entry.severity {
-                Severity::Error => Color::Red,
-                Severity::Warn => Color::Yellow,
-                _ => Color::White,
-            };
-
-            let mut spans = vec![
-                Span::styled(name_display, Style::default().fg(color)),
-                Span::styled(
-                    format!(" {source_char} "),
-                    Style::default().fg(Color::DarkGray),
-                ),
-            ];
-
-            // Add text with optional search highlighting
-            if let Some(ref re) = app.multiplex_filter.search_regex {
-                let text = &entry.text;
-                let mut last_end = 0;
-                for mat in re.find_iter(text) {
-                    if mat.start() > last_end {
-                        spans.push(Span::styled(
-                            text[last_end..mat.start()].to_string(),
-                            Style::default().fg(text_color),
-                        ));
-                    }
-                    spans.push(Span::styled(
-                        mat.as_str().to_string(),
-                        Style::default().fg(Color::Black).bg(Color::Yellow),
-                    ));
-                    last_end = mat.end();
-                }
-                if last_end < text.len() {
-                    spans.push(Span::styled(
-                        text[last_end..].to_string(),
-                        Style::default().fg(text_color),
-                    ));
-                }
-            } else {
-                spans.push(Span::styled(
-                    entry.text.clone(),
-                    Style::default().fg(text_color),
-                ));
-            }
-
+            let mut spans: Vec<Span> = log_time::column(Some(entry.timestamp), app.log_time, now)
+                .into_iter()
+                .collect();
+            push_entry_spans(
+                &mut spans,
+                app,
+                (&entry.slot_name, color),
+                entry.source,
+                entry.severity,
+                &entry.text,
+            );
             Line::from(spans)
         })
         .collect();
@@ -143,3 +100,140 @@ fn render_log_content(f: &mut Frame, area: Rect, app: &App) {
         .wrap(Wrap { trim: false });
     f.render_widget(paragraph, area);
 }
+
+/// Render history search results in place of the live buffer.
+fn render_history(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    history: &LogHistoryState,
+    now: DateTime<Utc>,
+) {
+    let mut title = format!(" History ({}) ", history.range.label());
+    if history.loading {
+        title.push_str("loading... ");
+    } else if history.next.is_none() && !history.records.is_empty() {
+        title.push_str("all loaded ");
+    }
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+
+    let inner_height = block.inner(area).height as usize;
+    let total = history.records.len();
+
+    if total == 0 {
+        let message = if history.loading {
+            " Searching log history..."
+        } else {
+            " No history matching filters"
+        };
+        let empty = Paragraph::new(Span::styled(message, Style::default().fg(Color::DarkGray)))
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let end = total - history.scroll_back.min(total);
+    let start = end.saturating_sub(inner_height);
+
+    let lines: Vec<Line> = history.records[start..end]
+        .iter()
+        .map(|record| {
+            let color = app
+                .log_buffer
+                .slot_colors
+                .get(&record.slot_name)
+                .map_or(Color::Gray, |&i| SLOT_COLORS[i as usize % 8]);
+            let mut spans = vec![log_time::history_column(
+                record.timestamp,
+                app.log_time,
+                now,
+            )];
+            push_entry_spans(
+                &mut spans,
+                app,
+                (&record.slot_name, color),
+                record.source,
+                record.severity,
+                &record.text,
+            );
+            Line::from(spans)
+        })
+        .collect();
+
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+/// Append the slot name, source, and text of a log line, highlighting
+/// matches of the search.
+fn push_entry_spans<'a>(
+    spans: &mut Vec<Span<'a>>,
+    app: &App,
+    (slot_name, color): (&str, Color),
+    source: LogSource,
+    severity: Severity,
+    text: &'a str,
+) {
+    let max_name_len = 12;
+
+    let source_char = match source {
+        LogSource::Agent => "A",
+        LogSource::Aspire => "S",
+        LogSource::Telemetry => "T",
+    };
+
+    let name_display = if slot_name.len() > max_name_len {
+        format!(
+            "{:>width$}",
+            &slot_name[..max_name_len],
+            width = max_name_len
+        )
+    } else {
+        format!("{:>width$}", slot_name, width = max_name_len)
+    };
+
+    let text_color = match severity {
+        Severity::Error => Color::Red,
+        Severity::Warn => Color::Yellow,
+        _ => Color::White,
+    };
+
+    spans.push(Span::styled(name_display, Style::default().fg(color)));
+    spans.push(Span::styled(
+        format!(" {source_char} "),
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    // Add text with optional search highlighting
+    if let Some(ref re) = app.multiplex_filter.search_regex {
+        let mut last_end = 0;
+        for mat in re.find_iter(text) {
+            if mat.start() > last_end {
+                spans.push(Span::styled(
+                    &text[last_end..mat.start()],
+                    Style::default().fg(text_color),
+                ));
+            }
+            spans.push(Span::styled(
+                mat.as_str(),
+                Style::default().fg(Color::Black).bg(Color::Yellow),
+            ));
+            last_end = mat.end();
+        }
+        if last_end < text.len() {
+            spans.push(Span::styled(
+                &text[last_end..],
+                Style::default().fg(text_color),
+            ));
+        }
+    } else {
+        spans.push(Span::styled(text, Style::default().fg(text_color)));
+    }
+}
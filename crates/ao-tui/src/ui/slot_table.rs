@@ -4,7 +4,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
 use ratatui::Frame;
 
-use ao_core::models::{AgentStatus, Slot, SlotStatus};
+use ao_core::models::{AgentStatus, RebaseStatus, Slot, SlotStatus};
 
 use crate::app::App;
 
@@ -16,7 +16,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             let aspire_icon = aspire_status_icon(slot);
             let agent_icon = agent_status_icon(slot);
 
-            let line = Line::from(vec![
+            let mut spans = vec![
                 Span::raw(" "),
                 Span::styled(&slot.name, Style::default().fg(Color::White)),
                 Span::raw("  "),
@@ -28,7 +28,12 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                     truncate_branch(&slot.branch, 20),
                     Style::default().fg(Color::DarkGray),
                 ),
-            ]);
+            ];
+            if let Some(badge) = rebase_badge(slot) {
+                spans.push(Span::raw(" "));
+                spans.push(badge);
+            }
+            let line = Line::from(spans);
             ListItem::new(line)
         })
         .collect();
@@ -63,6 +68,7 @@ fn aspire_status_icon(slot: &Slot) -> Span<'static> {
         SlotStatus::Ready => Span::styled("■", Style::default().fg(Color::DarkGray)),
         SlotStatus::Error => Span::styled("✗", Style::default().fg(Color::Red)),
         SlotStatus::Provisioning => Span::styled("…", Style::default().fg(Color::DarkGray)),
+        SlotStatus::SettingUp { .. } => Span::styled("⚙", Style::default().fg(Color::Yellow)),
     }
 }
 
@@ -76,6 +82,14 @@ fn agent_status_icon(slot: &Slot) -> Span<'static> {
     }
 }
 
+fn rebase_badge(slot: &Slot) -> Option<Span<'static>> {
+    match slot.rebase_status {
+        RebaseStatus::None => None,
+        RebaseStatus::Rebasing => Some(Span::styled("⟳", Style::default().fg(Color::Yellow))),
+        RebaseStatus::Conflicted => Some(Span::styled("⚠", Style::default().fg(Color::Red))),
+    }
+}
+
 fn truncate_branch(branch: &str, max_len: usize) -> String {
     if branch.chars().count() <= max_len {
         branch.to_string()
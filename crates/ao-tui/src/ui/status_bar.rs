@@ -38,6 +38,19 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             Span::raw(" "),
             hint("M", "back"),
         ]),
+        (Mode::DiffView, _) => Line::from(vec![
+            hint("j/k", "scroll"),
+            Span::raw(" "),
+            hint("PgUp/PgDn", "page"),
+            Span::raw(" "),
+            hint("Tab/S-Tab", "file"),
+            Span::raw(" "),
+            hint("W", "base/worktree"),
+            Span::raw(" "),
+            hint("R", "efresh"),
+            Span::raw(" "),
+            hint("Esc", "back"),
+        ]),
         (_, ViewMode::Dashboard) => Line::from(vec![
             hint("Tab", "list"),
             Span::raw(" "),
@@ -0,0 +1,46 @@
+use ratatui::layout::{Position, Rect};
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+use crate::text_area::TextArea;
+
+/// Render a text area with a bottom rule, scrolled to keep the cursor in
+/// view. The terminal cursor is placed in it when `focused`.
+pub fn render(f: &mut Frame, area: Rect, text_area: &TextArea, style: Style, focused: bool) {
+    let block = Block::default()
+        .borders(Borders::BOTTOM)
+        .border_style(style);
+    let inner = block.inner(area);
+    if inner.width < 2 || inner.height == 0 {
+        f.render_widget(block, area);
+        return;
+    }
+
+    // One column of padding on the left, as in the single-line fields
+    let width = inner.width as usize - 1;
+    let height = inner.height as usize;
+    let (row, col) = text_area.cursor();
+    let top = row.saturating_sub(height - 1);
+    let left = col.saturating_sub(width - 1);
+
+    let lines: Vec<Line> = text_area
+        .lines()
+        .iter()
+        .skip(top)
+        .take(height)
+        .map(|line| {
+            let visible: String = line.chars().skip(left).take(width).collect();
+            Line::styled(format!(" {visible}"), style)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines).block(block), area);
+
+    if focused {
+        f.set_cursor_position(Position::new(
+            inner.x + 1 + (col - left) as u16,
+            inner.y + (row - top) as u16,
+        ));
+    }
+}
@@ -31,6 +31,7 @@ pub fn make_slot(name: &str, status: SlotStatus, agent_status: AgentStatus) -> S
         format!("feature/{name}"),
         format!("C:/slots/{name}"),
     );
+    slot.base_branch = "master".into();
     slot.status = status;
     slot.agent_status = agent_status;
     slot
@@ -2,6 +2,7 @@ mod common;
 
 use ao_core::models::{AgentStatus, SlotStatus};
 use ao_tui::app::{App, Mode, SpawnAgentField};
+use ao_tui::text_area::TextArea;
 
 use common::{make_slot, render_to_string};
 
@@ -32,7 +33,7 @@ fn agent_dialog_with_prompt() {
     app.slots
         .push(make_slot("auth", SlotStatus::Ready, AgentStatus::None));
     app.mode = Mode::SpawnAgentDialog;
-    app.agent_form.prompt = "Implement dark mode".into();
+    app.agent_form.prompt = TextArea::from_text("Implement dark mode");
     let output = render_to_string(&app, 80, 24);
     insta::assert_snapshot!(output);
 }
@@ -55,9 +56,21 @@ fn agent_dialog_all_fields_filled() {
     app.slots
         .push(make_slot("auth", SlotStatus::Ready, AgentStatus::None));
     app.mode = Mode::SpawnAgentDialog;
-    app.agent_form.prompt = "Fix the login bug on the auth page".into();
+    app.agent_form.prompt = TextArea::from_text("Fix the login bug on the auth page");
     app.agent_form.allowed_tools = "Bash,Read,Write".into();
     app.agent_form.max_turns = "10".into();
     let output = render_to_string(&app, 80, 24);
     insta::assert_snapshot!(output);
 }
+
+#[test]
+fn agent_dialog_multiline_prompt() {
+    let mut app = App::new();
+    app.slots
+        .push(make_slot("auth", SlotStatus::Ready, AgentStatus::None));
+    app.mode = Mode::SpawnAgentDialog;
+    app.agent_form.prompt =
+        TextArea::from_text("Fix the login bug.\n\n- keep the API stable\n- add a test");
+    let output = render_to_string(&app, 80, 24);
+    insta::assert_snapshot!(output);
+}
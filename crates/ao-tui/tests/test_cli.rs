@@ -0,0 +1,79 @@
+use ao_core::models::CloneStrategy;
+use ao_tui::cli::{parse, Command, Invocation};
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn non_subcommand_args_run_the_tui() {
+    assert!(parse(&args("")).is_none());
+    assert!(parse(&args("--debug")).is_none());
+    assert!(parse(&args("--headless script.txt")).is_none());
+}
+
+#[test]
+fn parses_create_with_options() {
+    let parsed = parse(&args(
+        "create auth ../app --branch feature/auth --strategy worktree --json",
+    ))
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        parsed,
+        Invocation {
+            command: Command::Create {
+                name: "auth".into(),
+                source: "../app".into(),
+                branch: Some("feature/auth".into()),
+                prompt: None,
+                strategy: Some(CloneStrategy::Worktree),
+            },
+            json: true,
+        }
+    );
+}
+
+#[test]
+fn parses_single_slot_and_blueprint_commands() {
+    let command = |line: &str| parse(&args(line)).unwrap().unwrap().command;
+    assert_eq!(command("list"), Command::List);
+    assert_eq!(
+        command("rebase auth"),
+        Command::Rebase {
+            name: "auth".into()
+        }
+    );
+    assert_eq!(
+        command("destroy auth"),
+        Command::Destroy {
+            name: "auth".into()
+        }
+    );
+    assert_eq!(
+        command("spawn-agent auth --prompt fix"),
+        Command::SpawnAgent {
+            name: "auth".into(),
+            prompt: Some("fix".into()),
+        }
+    );
+    assert_eq!(
+        command("blueprint load team"),
+        Command::BlueprintLoad {
+            name: "team".into()
+        }
+    );
+    assert_eq!(command("daemon --json"), Command::Daemon);
+}
+
+#[test]
+fn rejects_bad_arguments() {
+    let error = |line: &str| parse(&args(line)).unwrap().unwrap_err();
+    assert!(error("start").contains("expects 1 argument"));
+    assert!(error("push auth --branch x").contains("not valid"));
+    assert!(error("create a b --strategy copy").contains("unknown clone strategy"));
+    assert!(error("create a b --branch").contains("needs a value"));
+    assert!(error("blueprint save team").contains("blueprint load"));
+    assert!(error("list --verbose").contains("unknown option"));
+    assert!(error("daemon now").contains("expects 0 argument"));
+}
@@ -1,8 +1,10 @@
 mod common;
 
+use ao_core::models::{AgentStatus, SlotStatus};
 use ao_tui::app::{App, CreateSlotField, Mode};
+use ao_tui::text_area::TextArea;
 
-use common::{inject_candidates, render_to_string};
+use common::{inject_candidates, make_slot, render_to_string};
 
 #[test]
 fn create_dialog_scanning() {
@@ -70,7 +72,7 @@ fn create_dialog_prompt_with_text() {
     app.mode = Mode::CreateSlotDialog;
     app.create_form.scan_loading = false;
     app.create_form.focus = CreateSlotField::Prompt;
-    app.create_form.prompt = "fix the login bug".into();
+    app.create_form.prompt = TextArea::from_text("fix the login bug");
     let output = render_to_string(&app, 80, 24);
     insta::assert_snapshot!(output);
 }
@@ -85,3 +87,89 @@ fn create_dialog_source_accepted() {
     let output = render_to_string(&app, 80, 24);
     insta::assert_snapshot!(output);
 }
+
+#[test]
+fn slot_name_is_suggested_from_source() {
+    let mut app = App::new();
+    app.slots.push(make_slot(
+        "Receipts-1",
+        SlotStatus::Ready,
+        AgentStatus::None,
+    ));
+    app.create_form.source = "C:/Users/test/Source/Receipts".into();
+    app.create_form.suggest_name(&app.slots);
+    assert_eq!(app.create_form.name, "Receipts-2");
+
+    app.create_form.source = "https://github.com/org/web.app.git".into();
+    app.create_form.suggest_name(&app.slots);
+    assert_eq!(app.create_form.name, "web-app-1");
+
+    // A typed name is kept
+    app.create_form.name = "mine".into();
+    app.create_form.name_edited = true;
+    app.create_form.suggest_name(&app.slots);
+    assert_eq!(app.create_form.name, "mine");
+}
+
+#[test]
+fn validation_checks_name_and_branch() {
+    let mut app = App::new();
+    app.slots
+        .push(make_slot("auth", SlotStatus::Ready, AgentStatus::None));
+    let form = &mut app.create_form;
+    form.source = "/src/app".into();
+    form.branches_source = Some("/src/app".into());
+    form.all_branches = vec!["main".into(), "feature/login".into()];
+
+    form.name = "auth".into();
+    form.branch = "feature/signup".into();
+    let errors = form.validate(&app.slots);
+    assert_eq!(
+        errors,
+        vec![
+            (CreateSlotField::Name, "'auth' already exists".to_string()),
+            (
+                CreateSlotField::Branch,
+                "no branch 'feature/signup'; turn on new branch".to_string()
+            ),
+        ]
+    );
+
+    form.name = "auth-2".into();
+    form.new_branch = true;
+    assert!(form.validate(&app.slots).is_empty());
+
+    form.branch = "feature/login".into();
+    assert_eq!(
+        form.validate(&app.slots)[0].1,
+        "'feature/login' exists; turn off new branch"
+    );
+}
+
+#[test]
+fn create_dialog_shows_branch_completions() {
+    let mut app = App::new();
+    app.mode = Mode::CreateSlotDialog;
+    app.create_form.scan_loading = false;
+    app.create_form.source = "C:/Users/test/Source/Receipts".into();
+    app.create_form.name = "Receipts-1".into();
+    app.create_form.all_branches = vec!["main".into(), "feature/login".into(), "fix/typo".into()];
+    app.create_form.focus = CreateSlotField::Branch;
+    app.create_form.branch = "fe".into();
+    app.create_form.filter_branches();
+    let output = render_to_string(&app, 80, 30);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn create_dialog_shows_errors_inline() {
+    let mut app = App::new();
+    app.mode = Mode::CreateSlotDialog;
+    app.create_form.scan_loading = false;
+    app.create_form.focus = CreateSlotField::Name;
+    app.create_form.name = "my slot".into();
+    app.create_form.new_branch = true;
+    app.create_form.errors = app.create_form.validate(&app.slots);
+    let output = render_to_string(&app, 80, 30);
+    insta::assert_snapshot!(output);
+}
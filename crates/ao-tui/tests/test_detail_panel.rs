@@ -51,3 +51,134 @@ fn detail_panel_short_uptime() {
     let output = render_to_string_at(&app, 80, 24, now);
     insta::assert_snapshot!(output);
 }
+
+#[test]
+fn detail_panel_with_git_status() {
+    use ao_core::models::{AheadBehind, CommitSummary, GitStatus};
+
+    let now = fixed_now();
+    let mut app = App::new();
+    let slot = make_slot("auth", SlotStatus::Running, AgentStatus::Active);
+    app.git_status.insert(
+        slot.name.clone(),
+        GitStatus {
+            branch: slot.branch.clone(),
+            upstream: Some(format!("origin/{}", slot.branch)),
+            vs_upstream: Some(AheadBehind {
+                ahead: 2,
+                behind: 0,
+            }),
+            vs_base: Some(AheadBehind {
+                ahead: 5,
+                behind: 1,
+            }),
+            staged: 1,
+            unstaged: 2,
+            untracked: 0,
+            conflicted: 0,
+            operation: None,
+            last_commit: Some(CommitSummary {
+                hash: "abc1234".into(),
+                subject: "Add login".into(),
+                author: "Jane".into(),
+                time: now - chrono::Duration::minutes(5),
+            }),
+        },
+    );
+    app.slots.push(slot);
+    let output = render_to_string_at(&app, 100, 24, now);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn detail_panel_with_pull_request() {
+    use ao_core::models::{CheckState, PrState, PullRequest, ReviewState};
+
+    let now = fixed_now();
+    let mut app = App::new();
+    let mut slot = make_slot("auth", SlotStatus::Running, AgentStatus::Active);
+    slot.pull_request = Some(PullRequest {
+        number: 42,
+        url: "https://github.com/acme/app/pull/42".into(),
+        state: PrState::Open,
+        review: ReviewState::Approved,
+        checks: CheckState::Failing,
+        checked_at: Some(now),
+    });
+    app.slots.push(slot);
+    let output = render_to_string_at(&app, 100, 24, now);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn detail_panel_setup_failed() {
+    let now = fixed_now();
+    let mut app = App::new();
+    let mut slot = make_slot("auth", SlotStatus::Error, AgentStatus::None);
+    slot.last_error = Some("setup: `npm install` timed out after 900s".into());
+    slot.creation = Some(ao_core::models::CreationState {
+        step: ao_core::models::CreationStep::Setup,
+        failed: true,
+        setup_index: 1,
+        branch: None,
+        prompt: None,
+    });
+    app.slots.push(slot);
+    app.slots.push(make_slot(
+        "billing",
+        SlotStatus::SettingUp { step: 1, total: 3 },
+        AgentStatus::None,
+    ));
+    let output = render_to_string_at(&app, 100, 24, now);
+    insta::assert_snapshot!(output);
+}
+
+fn failed_span(time: chrono::DateTime<Utc>, name: &str) -> ao_core::models::ErrorSpan {
+    ao_core::models::ErrorSpan {
+        time,
+        service: Some("api".into()),
+        name: name.into(),
+        message: Some("Sequence contains no elements".into()),
+        trace_id: "5b8efff798038103d269b633813fc60c".into(),
+    }
+}
+
+#[test]
+fn detail_panel_with_telemetry() {
+    use ao_core::models::TelemetrySummary;
+
+    let now = fixed_now();
+    let mut app = App::new();
+    let mut slot = make_slot("auth", SlotStatus::Running, AgentStatus::Active);
+    slot.telemetry = Some(TelemetrySummary {
+        logs: 120,
+        spans: 48,
+        error_spans: vec![failed_span(now, "POST /orders")],
+    });
+    app.slots.push(slot);
+    let output = render_to_string_at(&app, 100, 24, now);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn recent_failed_span_needs_attention() {
+    use ao_core::models::TelemetrySummary;
+
+    let mut app = App::new();
+    for (name, age) in [("fresh", 1), ("stale", 10)] {
+        let mut slot = make_slot(name, SlotStatus::Running, AgentStatus::None);
+        let mut telemetry = TelemetrySummary::default();
+        telemetry.push_error(failed_span(
+            Utc::now() - chrono::Duration::minutes(age),
+            "GET /cart",
+        ));
+        slot.telemetry = Some(telemetry);
+        app.slots.push(slot);
+    }
+    app.recompute_activity();
+    assert_eq!(
+        app.activity["fresh"].attention_reason.as_deref(),
+        Some("Failed: GET /cart")
+    );
+    assert!(!app.activity["stale"].needs_attention);
+}
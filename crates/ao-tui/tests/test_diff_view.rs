@@ -0,0 +1,107 @@
+mod common;
+
+use ao_core::models::{
+    AgentStatus, DiffHunk, DiffLine, DiffLineKind, DiffTarget, FileChange, FileDiff, SlotStatus,
+};
+use ao_tui::app::{App, DiffViewState, Mode};
+
+use common::{make_slot, render_to_string};
+
+fn line(kind: DiffLineKind, content: &str, old: Option<u32>, new: Option<u32>) -> DiffLine {
+    DiffLine {
+        kind,
+        content: content.into(),
+        old_lineno: old,
+        new_lineno: new,
+    }
+}
+
+fn sample_files() -> Vec<FileDiff> {
+    vec![
+        FileDiff {
+            path: "src/Api/Program.cs".into(),
+            old_path: None,
+            change: FileChange::Modified,
+            binary: false,
+            hunks: vec![DiffHunk {
+                header: "@@ -10,3 +10,4 @@ public class Program".into(),
+                old_start: 10,
+                old_lines: 3,
+                new_start: 10,
+                new_lines: 4,
+                lines: vec![
+                    line(
+                        DiffLineKind::Context,
+                        "var app = builder.Build();",
+                        Some(10),
+                        Some(10),
+                    ),
+                    line(
+                        DiffLineKind::Removed,
+                        "app.MapGet(\"/\", () => \"hi\");",
+                        Some(11),
+                        None,
+                    ),
+                    line(DiffLineKind::Added, "// health endpoint", None, Some(11)),
+                    line(
+                        DiffLineKind::Added,
+                        "app.MapHealthChecks(\"/health\");",
+                        None,
+                        Some(12),
+                    ),
+                    line(DiffLineKind::Context, "app.Run();", Some(12), Some(13)),
+                ],
+            }],
+        },
+        FileDiff {
+            path: "docs/receipts.md".into(),
+            old_path: None,
+            change: FileChange::Added,
+            binary: false,
+            hunks: vec![DiffHunk {
+                header: "@@ -0,0 +1 @@".into(),
+                old_start: 0,
+                old_lines: 0,
+                new_start: 1,
+                new_lines: 1,
+                lines: vec![line(DiffLineKind::Added, "# Receipts", None, Some(1))],
+            }],
+        },
+    ]
+}
+
+fn diff_app() -> App {
+    let mut app = App::new();
+    app.slots
+        .push(make_slot("auth", SlotStatus::Running, AgentStatus::Active));
+    let mut view = DiffViewState::new("auth", DiffTarget::Base);
+    view.set_files(sample_files());
+    app.diff_view = Some(view);
+    app.mode = Mode::DiffView;
+    app
+}
+
+#[test]
+fn diff_view_renders_hunks() {
+    let app = diff_app();
+    let output = render_to_string(&app, 100, 16);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn diff_view_next_file() {
+    let mut app = diff_app();
+    app.diff_view.as_mut().unwrap().next_file();
+    let output = render_to_string(&app, 100, 16);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn diff_view_scroll_is_clamped() {
+    let mut app = diff_app();
+    let view = app.diff_view.as_mut().unwrap();
+    view.scroll_by(100);
+    assert_eq!(view.scroll, view.line_count() - 1);
+    view.scroll_by(-100);
+    assert_eq!(view.scroll, 0);
+}
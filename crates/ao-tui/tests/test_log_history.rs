@@ -0,0 +1,124 @@
+mod common;
+
+use ao_core::models::{AgentStatus, SlotStatus};
+use ao_core::services::log_parser;
+use ao_core::services::log_store::{LogPage, LogRecord};
+use ao_core::services::log_tailer::LogSource;
+use ao_tui::app::{App, HistoryRange, LogHistoryState, Mode, Severity};
+use chrono::{TimeZone, Utc};
+
+use common::{make_slot, render_to_string};
+
+fn record(minute: u32, slot: &str, source: LogSource, text: &str) -> LogRecord {
+    let parsed = log_parser::parse_line(source, text);
+    LogRecord {
+        timestamp: Utc.with_ymd_and_hms(2026, 3, 14, 9, minute, 0).unwrap(),
+        slot_name: slot.into(),
+        source,
+        severity: parsed.severity,
+        category: parsed.category,
+        text: text.into(),
+    }
+}
+
+fn history_app() -> App {
+    // History times are shown in local time
+    std::env::set_var("TZ", "UTC");
+
+    let mut app = App::new();
+    for name in ["auth", "billing"] {
+        app.slots
+            .push(make_slot(name, SlotStatus::Ready, AgentStatus::None));
+        app.log_buffer.push(
+            name.into(),
+            LogSource::Agent,
+            "live line".into(),
+            Utc::now(),
+        );
+    }
+    app.mode = Mode::MultiplexLog;
+
+    let mut history = LogHistoryState::new(HistoryRange::LastDay);
+    let generation = history.restart(app.history_query(HistoryRange::LastDay, Utc::now()));
+    history.add_page(
+        generation,
+        LogPage {
+            records: vec![
+                record(
+                    1,
+                    "auth",
+                    LogSource::Aspire,
+                    "Now listening on http://localhost:5000",
+                ),
+                record(2, "old-slot", LogSource::Agent, "Reading src/lib.rs"),
+                record(3, "billing", LogSource::Agent, "error: tests failed"),
+            ],
+            next: None,
+        },
+    );
+    app.log_history = Some(history);
+    app
+}
+
+#[test]
+fn log_history_renders_in_place_of_live_buffer() {
+    let app = history_app();
+    let output = render_to_string(&app, 100, 12);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn log_history_drops_pages_of_replaced_search() {
+    let mut app = history_app();
+    let history = app.log_history.as_mut().unwrap();
+    let stale = history.generation;
+    history.restart(Default::default());
+
+    history.add_page(
+        stale,
+        LogPage {
+            records: vec![record(4, "auth", LogSource::Agent, "stale")],
+            next: None,
+        },
+    );
+    assert!(history.records.is_empty());
+    assert!(history.loading);
+}
+
+#[test]
+fn log_history_prepends_older_pages() {
+    let mut app = history_app();
+    let history = app.log_history.as_mut().unwrap();
+    let generation = history.generation;
+    history.add_page(
+        generation,
+        LogPage {
+            records: vec![record(0, "auth", LogSource::Agent, "first")],
+            next: None,
+        },
+    );
+    let texts: Vec<_> = history.records.iter().map(|r| r.text.as_str()).collect();
+    assert_eq!(texts[0], "first");
+    assert_eq!(texts.len(), 4);
+}
+
+#[test]
+fn history_query_follows_multiplex_filters() {
+    let mut app = history_app();
+    let now = Utc::now();
+
+    let query = app.history_query(HistoryRange::All, now);
+    assert!(query.slots.is_empty());
+    assert_eq!(query.since, None);
+
+    app.multiplex_filter.hidden_slots.insert("auth".into());
+    app.multiplex_filter.source_filter = Some(LogSource::Agent);
+    app.multiplex_filter.search_text = "failed".into();
+    app.multiplex_filter.cycle_severity();
+    let query = app.history_query(HistoryRange::LastHour, now);
+    assert_eq!(query.slots, ["billing"]);
+    assert_eq!(query.source, Some(LogSource::Agent));
+    assert_eq!(query.text, "failed");
+    assert_eq!(query.min_severity, Some(Severity::Warn));
+    assert_eq!(query.since, Some(now - chrono::Duration::hours(1)));
+}
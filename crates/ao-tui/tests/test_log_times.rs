@@ -0,0 +1,106 @@
+mod common;
+
+use ao_core::models::{AgentStatus, SlotStatus};
+use ao_core::services::log_tailer::{format_timestamped, LogSource};
+use ao_tui::app::{self, App, LogBuffer, LogViewLine, Mode, TimeDisplay};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use common::{make_slot, render_to_string_at};
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 14, 9, 30, 0).unwrap()
+}
+
+fn timed_app(display: TimeDisplay) -> App {
+    // Clock times are shown in local time
+    std::env::set_var("TZ", "UTC");
+
+    let mut app = App::new();
+    app.slots
+        .push(make_slot("auth", SlotStatus::Running, AgentStatus::Active));
+    app.log_source = app::LogSource::Aspire;
+    app.log_time = display;
+    app.log_lines = vec![
+        format_timestamped(now() - Duration::hours(2), "info: Building AppHost")
+            .as_str()
+            .into(),
+        format_timestamped(
+            now() - Duration::seconds(95),
+            "info: Dashboard at http://localhost:15000",
+        )
+        .as_str()
+        .into(),
+        "written before times were recorded".into(),
+        LogViewLine {
+            timestamp: Some(now() - Duration::seconds(4)),
+            text: "warn: Service 'api' restarted".into(),
+        },
+    ];
+    app
+}
+
+#[test]
+fn log_view_with_clock_times() {
+    let app = timed_app(TimeDisplay::Clock);
+    let output = render_to_string_at(&app, 80, 24, now());
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn log_view_with_relative_times() {
+    let app = timed_app(TimeDisplay::Relative);
+    let output = render_to_string_at(&app, 80, 24, now());
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn multiplex_log_with_relative_times() {
+    let mut app = timed_app(TimeDisplay::Relative);
+    app.slots
+        .push(make_slot("billing", SlotStatus::Running, AgentStatus::None));
+    for (secs_ago, slot, text) in [
+        (30, "auth", "Reading src/auth.rs"),
+        (12, "billing", "error: build failed"),
+        (3, "auth", "Editing src/auth.rs"),
+    ] {
+        app.log_buffer.push(
+            slot.into(),
+            LogSource::Agent,
+            text.into(),
+            now() - Duration::seconds(secs_ago),
+        );
+    }
+    app.mode = Mode::MultiplexLog;
+    let output = render_to_string_at(&app, 80, 12, now());
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn log_buffer_orders_entries_by_capture_time() {
+    let mut buffer = LogBuffer::new();
+    for (secs, text) in [
+        (1, "one"),
+        (3, "three"),
+        (2, "two"),
+        (3, "three again"),
+        (0, "zero"),
+    ] {
+        buffer.push(
+            "auth".into(),
+            LogSource::Agent,
+            text.into(),
+            now() + Duration::seconds(secs),
+        );
+    }
+    let texts: Vec<_> = buffer.entries.iter().map(|e| e.text.as_str()).collect();
+    assert_eq!(texts, ["zero", "one", "two", "three", "three again"]);
+}
+
+#[test]
+fn time_display_cycles_through_hidden() {
+    let display = TimeDisplay::default();
+    assert_eq!(display, TimeDisplay::Hidden);
+    assert_eq!(display.next(), TimeDisplay::Clock);
+    assert_eq!(display.next().next(), TimeDisplay::Relative);
+    assert_eq!(display.next().next().next(), TimeDisplay::Hidden);
+}
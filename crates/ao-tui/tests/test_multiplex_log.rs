@@ -0,0 +1,112 @@
+mod common;
+
+use ao_core::models::{AgentStatus, SlotStatus};
+use ao_core::services::log_tailer::LogSource;
+use ao_tui::app::{App, Mode, Severity};
+use chrono::Utc;
+
+use common::{make_slot, render_to_string};
+
+fn multiplex_app() -> App {
+    let mut app = App::new();
+    for name in ["api", "web"] {
+        app.slots
+            .push(make_slot(name, SlotStatus::Running, AgentStatus::Active));
+    }
+    for (slot, source, text) in [
+        (
+            "api",
+            LogSource::Aspire,
+            "info: Microsoft.Hosting.Lifetime[14]",
+        ),
+        (
+            "api",
+            LogSource::Aspire,
+            "      Now listening on: http://localhost:5000",
+        ),
+        (
+            "web",
+            LogSource::Agent,
+            "I'll look at the error in OrderService next",
+        ),
+        ("api", LogSource::Aspire, "fail: Api.Orders.OrderService[3]"),
+        ("api", LogSource::Aspire, "      Order 7 could not be saved"),
+        (
+            "api",
+            LogSource::Aspire,
+            "   at Api.Orders.OrderService.Save()",
+        ),
+        (
+            "web",
+            LogSource::Agent,
+            "Web.csproj: warning NU1603: Dependency resolved to a later version",
+        ),
+        ("web", LogSource::Agent, "    0 Error(s)"),
+    ] {
+        app.log_buffer
+            .push(slot.into(), source, text.into(), Utc::now());
+    }
+    app.mode = Mode::MultiplexLog;
+    app
+}
+
+#[test]
+fn multiplex_log_colours_lines_by_parsed_level() {
+    let app = multiplex_app();
+    let severities: Vec<_> = app.log_buffer.entries.iter().map(|e| e.severity).collect();
+    assert_eq!(
+        severities,
+        [
+            Severity::Info,
+            Severity::Info,
+            Severity::Info,
+            Severity::Error,
+            Severity::Error,
+            Severity::Error,
+            Severity::Warn,
+            Severity::Info,
+        ]
+    );
+    let continued: Vec<_> = app
+        .log_buffer
+        .entries
+        .iter()
+        .map(|e| e.continuation)
+        .collect();
+    assert_eq!(
+        continued,
+        [false, true, false, false, true, true, false, false]
+    );
+}
+
+#[test]
+fn multiplex_log_filtered_to_warnings() {
+    let mut app = multiplex_app();
+    app.multiplex_filter.cycle_severity();
+    assert_eq!(app.multiplex_filter.min_severity, Some(Severity::Warn));
+    let output = render_to_string(&app, 100, 12);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn multiplex_search_matches_category() {
+    let mut app = multiplex_app();
+    app.multiplex_filter.search_text = "Api.Orders".into();
+    app.multiplex_filter.update_regex();
+    app.multiplex_filter.search_filter_mode = true;
+    let shown: Vec<_> = app
+        .log_buffer
+        .entries
+        .iter()
+        .filter(|e| app.multiplex_filter.matches_entry(e))
+        .map(|e| e.text.trim())
+        .collect();
+    assert_eq!(
+        shown,
+        [
+            "fail: Api.Orders.OrderService[3]",
+            "Order 7 could not be saved",
+            "at Api.Orders.OrderService.Save()",
+        ]
+    );
+}
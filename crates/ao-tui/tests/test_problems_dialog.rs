@@ -0,0 +1,78 @@
+mod common;
+
+use ao_core::models::{AgentStatus, Problem, ProblemSeverity, SlotStatus};
+use ao_tui::app::{App, Mode};
+
+use common::{make_slot, render_to_string};
+
+fn problem(severity: ProblemSeverity, code: &str, file: &str, line: u32, message: &str) -> Problem {
+    Problem {
+        severity,
+        code: code.into(),
+        file: Some(file.into()),
+        line: Some(line),
+        column: Some(5),
+        message: message.into(),
+        project: Some("C:/slots/auth/src/Api/Api.csproj".into()),
+    }
+}
+
+fn broken_app() -> App {
+    let mut app = App::new();
+    let mut slot = make_slot("auth", SlotStatus::Running, AgentStatus::Active);
+    slot.problems = vec![
+        problem(
+            ProblemSeverity::Error,
+            "CS1002",
+            "C:/slots/auth/src/Api/Orders.cs",
+            12,
+            "; expected",
+        ),
+        problem(
+            ProblemSeverity::Error,
+            "CS0103",
+            "C:/slots/auth/src/Api/Orders.cs",
+            40,
+            "The name 'total' does not exist in the current context",
+        ),
+        problem(
+            ProblemSeverity::Warning,
+            "CS0168",
+            "C:/slots/auth/src/Api/Program.cs",
+            8,
+            "The variable 'e' is declared but never used",
+        ),
+    ];
+    app.slots.push(slot);
+    app
+}
+
+#[test]
+fn problems_dialog_renders() {
+    let mut app = broken_app();
+    app.mode = Mode::ProblemsDialog {
+        slot_name: "auth".into(),
+        scroll: 0,
+    };
+    let output = render_to_string(&app, 100, 30);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn problems_dialog_scrolls_by_problem() {
+    let mut app = broken_app();
+    app.mode = Mode::ProblemsDialog {
+        slot_name: "auth".into(),
+        scroll: 2,
+    };
+    let output = render_to_string(&app, 100, 30);
+    assert!(output.contains("CS0168 src/Api/Program.cs(8,5)"));
+    assert!(!output.contains("CS1002"));
+}
+
+#[test]
+fn detail_panel_counts_problems() {
+    let app = broken_app();
+    let output = render_to_string(&app, 120, 30);
+    assert!(output.contains("2 errors, 1 warning — [E] to list"));
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use ao_core::models::{AgentStatus, SavedPrompt, SlotStatus};
+use ao_tui::app::{App, Mode, PromptField, PromptPickerState, SpawnAgentField};
+use ao_tui::text_area::TextArea;
+
+use common::{make_slot, render_to_string};
+
+fn prompt(name: &str, description: &str, body: &str) -> SavedPrompt {
+    SavedPrompt {
+        name: name.into(),
+        description: Some(description.into()),
+        body: body.into(),
+        allowed_tools: None,
+        max_turns: None,
+    }
+}
+
+fn picker_app(target: PromptField) -> App {
+    let mut app = App::new();
+    app.slots
+        .push(make_slot("auth", SlotStatus::Ready, AgentStatus::None));
+    app.prompt_picker = PromptPickerState::new(target);
+    app.prompt_picker.set_prompts(vec![
+        SavedPrompt {
+            allowed_tools: Some("Bash,Read,Edit".into()),
+            max_turns: Some(30),
+            ..prompt(
+                "fix-tests",
+                "Fix failing tests",
+                "Fix the failing tests on {branch}.\nDon't change test expectations.",
+            )
+        },
+        prompt(
+            "integration-test",
+            "Add an integration test",
+            "Add an integration test for {slot_name}.",
+        ),
+        prompt(
+            "review",
+            "Review and refactor",
+            "Review the changes on {branch}.",
+        ),
+    ]);
+    app.mode = Mode::PromptPickerDialog;
+    app
+}
+
+#[test]
+fn prompt_picker_renders_over_agent_dialog() {
+    let app = picker_app(PromptField::SpawnAgent);
+    let output = render_to_string(&app, 100, 30);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn prompt_picker_filters_by_name_and_description() {
+    let mut app = picker_app(PromptField::SpawnAgent);
+    app.prompt_picker.query = "refactor".into();
+    app.prompt_picker.apply_filter();
+    assert_eq!(app.prompt_picker.selected_prompt().unwrap().name, "review");
+
+    app.prompt_picker.query = "zzz".into();
+    app.prompt_picker.apply_filter();
+    assert!(app.prompt_picker.selected_prompt().is_none());
+}
+
+#[test]
+fn picked_prompt_fills_agent_dialog() {
+    let mut app = picker_app(PromptField::SpawnAgent);
+    app.agent_form.focus = SpawnAgentField::MaxTurns;
+    let picked = app.prompt_picker.selected_prompt().cloned().unwrap();
+    app.insert_library_prompt(&picked);
+
+    assert_eq!(app.mode, Mode::SpawnAgentDialog);
+    assert_eq!(app.agent_form.focus, SpawnAgentField::Prompt);
+    let branch = &app.slots[0].branch;
+    assert_eq!(
+        app.agent_form.prompt.text(),
+        format!("Fix the failing tests on {branch}.\nDon't change test expectations.")
+    );
+    assert_eq!(app.agent_form.allowed_tools, "Bash,Read,Edit");
+    assert_eq!(app.agent_form.max_turns, "30");
+}
+
+#[test]
+fn picked_prompt_is_inserted_at_cursor_in_create_dialog() {
+    let mut app = picker_app(PromptField::CreateSlot);
+    app.create_form.name = "billing-2".into();
+    app.create_form.prompt = TextArea::from_text("Context: ");
+    app.prompt_picker.select_next();
+    let picked = app.prompt_picker.selected_prompt().cloned().unwrap();
+    app.insert_library_prompt(&picked);
+
+    assert_eq!(app.mode, Mode::CreateSlotDialog);
+    assert_eq!(
+        app.create_form.prompt.text(),
+        "Context: Add an integration test for billing-2."
+    );
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use ao_core::models::{AgentStatus, RebaseStatus, SlotStatus};
+use ao_tui::app::{App, BatchProgressState, Mode};
+
+use common::{make_slot, render_to_string};
+
+fn conflicted_app() -> App {
+    let mut app = App::new();
+    let mut slot = make_slot("auth", SlotStatus::Ready, AgentStatus::None);
+    slot.rebase_status = RebaseStatus::Conflicted;
+    slot.conflicted_files = vec!["src/Api/Program.cs".into(), "README.md".into()];
+    app.slots.push(slot);
+    app
+}
+
+#[test]
+fn rebase_conflict_dialog_renders() {
+    let mut app = conflicted_app();
+    app.mode = Mode::RebaseConflictDialog {
+        slot_name: "auth".into(),
+    };
+    let output = render_to_string(&app, 100, 30);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn batch_failure_points_at_conflicted_slot() {
+    let mut app = conflicted_app();
+    app.slots
+        .push(make_slot("billing", SlotStatus::Ready, AgentStatus::None));
+    let mut progress = BatchProgressState::new("Rebase All", 2);
+    progress
+        .failures
+        .push(("billing".into(), "fetch failed".into()));
+    progress
+        .failures
+        .push(("auth".into(), "conflicts in 2 file(s)".into()));
+    app.batch_progress = Some(progress);
+
+    assert_eq!(app.first_conflicted_failure().as_deref(), Some("auth"));
+}
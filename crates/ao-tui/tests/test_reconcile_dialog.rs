@@ -0,0 +1,49 @@
+mod common;
+
+use ao_core::models::{AgentStatus, Discrepancy, SlotStatus};
+use ao_tui::app::{App, Mode};
+
+use common::{make_slot, render_to_string};
+
+fn reconcile_app() -> App {
+    let mut app = App::new();
+    app.slots
+        .push(make_slot("auth", SlotStatus::Ready, AgentStatus::None));
+    app.reconcile.items = vec![
+        Discrepancy::MissingClone {
+            slot: "billing".into(),
+            path: "/w/.slots/billing".into(),
+        },
+        Discrepancy::BranchMismatch {
+            slot: "auth".into(),
+            expected: "feature/auth".into(),
+            actual: "main".into(),
+        },
+        Discrepancy::OrphanDirectory {
+            name: "scratch".into(),
+            path: "/w/.slots/scratch".into(),
+            is_repo: true,
+        },
+    ];
+    app.mode = Mode::ReconcileDialog;
+    app
+}
+
+#[test]
+fn reconcile_dialog_renders() {
+    let mut app = reconcile_app();
+    app.reconcile.selected = 1;
+    let output = render_to_string(&app, 100, 30);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn resolved_item_is_removed_from_the_list() {
+    let mut app = reconcile_app();
+    app.reconcile.selected = 2;
+
+    let taken = app.reconcile.take_selected().unwrap();
+    assert_eq!(taken.name(), "scratch");
+    assert_eq!(app.reconcile.items.len(), 2);
+    assert_eq!(app.reconcile.selected, 1);
+}
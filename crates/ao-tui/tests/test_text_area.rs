@@ -0,0 +1,105 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use ao_tui::text_area::TextArea;
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+#[test]
+fn typing_and_cursor_movement() {
+    let mut area = TextArea::new();
+    for c in "hello world".chars() {
+        assert!(area.handle_key(key(KeyCode::Char(c), KeyModifiers::NONE)));
+    }
+    area.handle_key(key(KeyCode::Left, KeyModifiers::CONTROL));
+    area.handle_key(key(KeyCode::Char('X'), KeyModifiers::NONE));
+    assert_eq!(area.text(), "hello Xworld");
+
+    area.handle_key(key(KeyCode::Home, KeyModifiers::NONE));
+    area.handle_key(key(KeyCode::Delete, KeyModifiers::NONE));
+    assert_eq!(area.text(), "ello Xworld");
+    assert_eq!(area.cursor(), (0, 0));
+}
+
+#[test]
+fn newlines_join_and_split_lines() {
+    let mut area = TextArea::from_text("first second");
+    area.handle_key(key(KeyCode::Left, KeyModifiers::ALT));
+    // Plain Enter belongs to the dialog
+    assert!(!area.handle_key(key(KeyCode::Enter, KeyModifiers::NONE)));
+    area.handle_key(key(KeyCode::Enter, KeyModifiers::ALT));
+    assert_eq!(area.lines(), ["first ", "second"]);
+    assert_eq!(area.cursor(), (1, 0));
+
+    area.handle_key(key(KeyCode::Backspace, KeyModifiers::NONE));
+    assert_eq!(area.text(), "first second");
+
+    area.handle_key(key(KeyCode::Char('j'), KeyModifiers::CONTROL));
+    area.handle_key(key(KeyCode::Up, KeyModifiers::NONE));
+    area.handle_key(key(KeyCode::End, KeyModifiers::NONE));
+    assert_eq!(area.cursor(), (0, 6));
+}
+
+#[test]
+fn word_deletion() {
+    let mut area = TextArea::from_text("fix the  bug");
+    area.handle_key(key(KeyCode::Char('w'), KeyModifiers::CONTROL));
+    assert_eq!(area.text(), "fix the  ");
+    area.handle_key(key(KeyCode::Backspace, KeyModifiers::ALT));
+    assert_eq!(area.text(), "fix ");
+    area.handle_key(key(KeyCode::Home, KeyModifiers::NONE));
+    area.handle_key(key(KeyCode::Char('k'), KeyModifiers::CONTROL));
+    assert!(area.is_empty());
+}
+
+#[test]
+fn pasted_line_endings_are_normalized() {
+    let mut area = TextArea::from_text("ab");
+    area.move_left();
+    area.insert_str("1\r\n2\r3");
+    assert_eq!(area.lines(), ["a1", "2", "3b"]);
+    assert_eq!(area.cursor(), (2, 1));
+
+    // Multi-byte chars are edited by char, not byte
+    let mut area = TextArea::from_text("héllo");
+    area.move_word_left();
+    area.move_right();
+    area.move_right();
+    area.backspace();
+    assert_eq!(area.text(), "hllo");
+}
+
+#[cfg(unix)]
+#[test]
+fn external_edit_uses_a_private_temp_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let seen = dir.path().join("seen");
+    let editor = dir.path().join("editor.sh");
+    std::fs::write(
+        &editor,
+        format!(
+            "#!/bin/sh\nstat -c %a \"$1\" > {seen}\necho \"$1\" >> {seen}\nprintf 'edited\\n' > \"$1\"\n",
+            seen = seen.display()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&editor, std::fs::Permissions::from_mode(0o755)).unwrap();
+    std::env::set_var("VISUAL", &editor);
+
+    let edited = ao_tui::text_area::edit_externally("draft").unwrap();
+    assert_eq!(edited.as_deref(), Some("edited"));
+
+    let seen = std::fs::read_to_string(seen).unwrap();
+    let (mode, path) = seen.trim().split_once('\n').unwrap();
+    assert_eq!(mode, "600");
+    let name = std::path::Path::new(path)
+        .file_name()
+        .unwrap()
+        .to_string_lossy();
+    assert!(name.starts_with("ao-prompt-") && name.ends_with(".md"));
+    assert_ne!(name, format!("ao-prompt-{}.md", std::process::id()));
+    assert!(!std::path::Path::new(path).exists());
+}